@@ -0,0 +1,17 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ParseError {
+    #[error("Transaction meta is missing")]
+    MissingTransactionMeta,
+    #[error("Failed to build transaction context: {0}")]
+    TransactionContext(String),
+    #[error("Instruction does not originate from the Address Lookup Table program: {0}")]
+    UnexpectedProgram(String),
+    #[error("Missing account at index {0}")]
+    MissingAccount(usize),
+    #[error("Invalid instruction data: {0}")]
+    InvalidInstructionData(String),
+    #[error("Expected a {0} instruction")]
+    InstructionMismatch(&'static str),
+}
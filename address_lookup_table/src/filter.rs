@@ -0,0 +1,51 @@
+use std::collections::HashSet;
+
+use solana_substreams_common::params::{matches, Params};
+
+use crate::pb::address_lookup_table::address_lookup_table_event::Event;
+
+/// Filters events by the `lookup_tables` and `authorities` module params.
+///
+/// An event matches when its lookup table and authority are in the respective filters. Unset
+/// filters match every event.
+#[derive(Clone, Debug, Default)]
+pub struct Filter {
+    lookup_tables: Option<HashSet<String>>,
+    authorities: Option<HashSet<String>>,
+}
+
+impl Filter {
+    pub fn new(params: &Params) -> Self {
+        Self {
+            lookup_tables: params.get_set("lookup_tables"),
+            authorities: params.get_set("authorities"),
+        }
+    }
+
+    pub fn matches(&self, event: &Event) -> bool {
+        matches(&self.lookup_tables, event_lookup_table(event))
+            && matches(&self.authorities, event_authority(event))
+    }
+}
+
+pub fn event_lookup_table(event: &Event) -> Option<&str> {
+    match event {
+        Event::CreateLookupTable(create) => Some(&create.lookup_table),
+        Event::FreezeLookupTable(freeze) => Some(&freeze.lookup_table),
+        Event::ExtendLookupTable(extend) => Some(&extend.lookup_table),
+        Event::DeactivateLookupTable(deactivate) => Some(&deactivate.lookup_table),
+        Event::CloseLookupTable(close) => Some(&close.lookup_table),
+        Event::FailedInstruction(_) | Event::UnknownInstruction(_) => None,
+    }
+}
+
+pub fn event_authority(event: &Event) -> Option<&str> {
+    match event {
+        Event::CreateLookupTable(create) => Some(&create.authority),
+        Event::FreezeLookupTable(freeze) => Some(&freeze.authority),
+        Event::ExtendLookupTable(extend) => Some(&extend.authority),
+        Event::DeactivateLookupTable(deactivate) => Some(&deactivate.authority),
+        Event::CloseLookupTable(close) => Some(&close.authority),
+        Event::FailedInstruction(_) | Event::UnknownInstruction(_) => None,
+    }
+}
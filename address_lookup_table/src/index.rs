@@ -0,0 +1,24 @@
+use std::collections::BTreeSet;
+
+use substreams::pb::sf::substreams::index::v1::Keys;
+
+use crate::instruction::ADDRESS_LOOKUP_TABLE_PROGRAM_ID;
+use crate::filter::{event_authority, event_lookup_table};
+use crate::pb::address_lookup_table::{AddressLookupTableEvent, AddressLookupTableTransactionEvents};
+
+/// Block index keys of the events: `program:`, `lookup_table:` and `authority:`.
+pub fn index_keys(transactions: &[AddressLookupTableTransactionEvents]) -> Keys {
+    let keys: BTreeSet<String> = transactions.iter().flat_map(|x| x.events.iter()).flat_map(event_keys).collect();
+    Keys { keys: keys.into_iter().collect() }
+}
+
+/// Index keys of a single event, which `filtered_address_lookup_table_events` matches its query against.
+pub fn event_keys(event: &AddressLookupTableEvent) -> BTreeSet<String> {
+    let mut keys = BTreeSet::new();
+    if let Some(event) = event.event.as_ref() {
+        keys.insert(format!("program:{}", ADDRESS_LOOKUP_TABLE_PROGRAM_ID));
+        keys.extend(event_lookup_table(event).map(|x| format!("lookup_table:{}", x)));
+        keys.extend(event_authority(event).map(|x| format!("authority:{}", x)));
+    }
+    keys
+}
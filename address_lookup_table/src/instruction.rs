@@ -0,0 +1,106 @@
+use substreams_solana::b58;
+use substreams_solana_utils::pubkey::Pubkey;
+
+pub const ADDRESS_LOOKUP_TABLE_PROGRAM_ID: Pubkey = Pubkey(b58!("AddressLookupTab1e1111111111111111111111111"));
+
+/// Instructions of the Address Lookup Table program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AddressLookupTableInstruction {
+    CreateLookupTable { recent_slot: u64, bump_seed: u8 },
+    FreezeLookupTable,
+    ExtendLookupTable { new_addresses: Vec<Pubkey> },
+    DeactivateLookupTable,
+    CloseLookupTable,
+}
+
+impl AddressLookupTableInstruction {
+    /// Unpacks the bincode encoded instruction data.
+    pub fn unpack(data: &[u8]) -> Result<Self, &'static str> {
+        let tag = _u32(data, 0)?;
+        let data = &data[4..];
+        match tag {
+            0 => Ok(Self::CreateLookupTable {
+                recent_slot: _u64(data, 0)?,
+                bump_seed: *data.get(8).ok_or("Instruction data is too short.")?,
+            }),
+            1 => Ok(Self::FreezeLookupTable),
+            2 => {
+                let len = _u64(data, 0)? as usize;
+                let new_addresses = data.get(8..)
+                    .ok_or("Instruction data is too short.")?
+                    .chunks_exact(32)
+                    .take(len)
+                    .map(|x| Pubkey(x.try_into().unwrap()))
+                    .collect::<Vec<_>>();
+                if new_addresses.len() != len {
+                    return Err("Instruction data is too short.");
+                }
+                Ok(Self::ExtendLookupTable { new_addresses })
+            },
+            3 => Ok(Self::DeactivateLookupTable),
+            4 => Ok(Self::CloseLookupTable),
+            _ => Err("Unknown instruction discriminator."),
+        }
+    }
+}
+
+fn _u32(data: &[u8], offset: usize) -> Result<u32, &'static str> {
+    data.get(offset..offset + 4)
+        .and_then(|x| x.try_into().ok())
+        .map(u32::from_le_bytes)
+        .ok_or("Instruction data is too short.")
+}
+
+fn _u64(data: &[u8], offset: usize) -> Result<u64, &'static str> {
+    data.get(offset..offset + 8)
+        .and_then(|x| x.try_into().ok())
+        .map(u64::from_le_bytes)
+        .ok_or("Instruction data is too short.")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extend_lookup_table(len: u64, addresses: &[[u8; 32]]) -> Vec<u8> {
+        let mut data = [&2u32.to_le_bytes()[..], &len.to_le_bytes()].concat();
+        data.extend(addresses.iter().flatten());
+        data
+    }
+
+    #[test]
+    fn test_unpack_create_lookup_table() {
+        let data = [&0u32.to_le_bytes()[..], &250_000_000u64.to_le_bytes(), &[254]].concat();
+        assert_eq!(
+            AddressLookupTableInstruction::unpack(&data),
+            Ok(AddressLookupTableInstruction::CreateLookupTable { recent_slot: 250_000_000, bump_seed: 254 }),
+        );
+        assert!(AddressLookupTableInstruction::unpack(&data[..12]).is_err());
+    }
+
+    #[test]
+    fn test_unpack_extend_lookup_table() {
+        let data = extend_lookup_table(2, &[[1; 32], [2; 32]]);
+        assert_eq!(
+            AddressLookupTableInstruction::unpack(&data),
+            Ok(AddressLookupTableInstruction::ExtendLookupTable { new_addresses: vec![Pubkey([1; 32]), Pubkey([2; 32])] }),
+        );
+    }
+
+    #[test]
+    fn test_unpack_extend_lookup_table_with_missing_addresses() {
+        // The length prefix announces more addresses than the data carries.
+        assert!(AddressLookupTableInstruction::unpack(&extend_lookup_table(3, &[[1; 32], [2; 32]])).is_err());
+        assert!(AddressLookupTableInstruction::unpack(&extend_lookup_table(u64::MAX, &[[1; 32]])).is_err());
+        // A trailing partial address isn't counted.
+        let mut data = extend_lookup_table(2, &[[1; 32]]);
+        data.extend([2; 31]);
+        assert!(AddressLookupTableInstruction::unpack(&data).is_err());
+    }
+
+    #[test]
+    fn test_unpack_unknown_instruction() {
+        assert_eq!(AddressLookupTableInstruction::unpack(&5u32.to_le_bytes()), Err("Unknown instruction discriminator."));
+        assert!(AddressLookupTableInstruction::unpack(&[2, 0]).is_err());
+    }
+}
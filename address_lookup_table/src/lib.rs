@@ -0,0 +1,314 @@
+use substreams::errors::Error;
+use substreams::pb::sf::substreams::index::v1::Keys;
+use substreams_solana::pb::sf::solana::r#type::v1::ConfirmedTransaction;
+use substreams_solana::pb::sf::solana::r#type::v1::Block;
+
+use substreams_solana_utils as utils;
+use utils::transaction::{get_context, TransactionContext};
+use utils::instruction::{get_structured_instructions, StructuredInstructions, StructuredInstruction};
+use utils::pubkey::Pubkey;
+
+use solana_substreams_common::{get_failed_instruction, get_transaction_envelope, is_program_invoked};
+use solana_substreams_common::params::Params;
+use solana_substreams_common::query::Query;
+use solana_substreams_common::instruction::{flattened_index, get_call_path, get_unknown_instruction, stack_depth};
+
+pub mod error;
+use error::ParseError;
+
+pub mod instruction;
+use instruction::{AddressLookupTableInstruction, ADDRESS_LOOKUP_TABLE_PROGRAM_ID};
+
+pub mod filter;
+use filter::Filter;
+
+pub mod index;
+use index::{event_keys, index_keys};
+
+pub mod resolution;
+use resolution::get_account_resolutions;
+
+pub mod pb;
+use pb::address_lookup_table::*;
+use pb::address_lookup_table::address_lookup_table_event::Event;
+
+#[substreams::handlers::map]
+fn address_lookup_table_events(params: String, block: Block) -> Result<AddressLookupTableBlockEvents, Error> {
+    Ok(_block_events(&block, &Params::parse(&params)))
+}
+
+#[substreams::handlers::map]
+fn address_lookup_table_index(block: Block) -> Result<Keys, Error> {
+    let transactions = parse_block(&block, &Params::default());
+    Ok(index_keys(&transactions))
+}
+
+/// Events matching the block filter query given as params, such as `lookup_table:<lookup_table>`, on their own index keys.
+#[substreams::handlers::map]
+fn filtered_address_lookup_table_events(query: String, block: Block) -> Result<AddressLookupTableBlockEvents, Error> {
+    let query = Query::parse(&query).map_err(Error::msg)?;
+    let mut block_events = _block_events(&block, &Params::default());
+    for transaction in block_events.transactions.iter_mut() {
+        transaction.events.retain(|x| query.matches(&event_keys(x)));
+    }
+    block_events.transactions.retain(|x| !x.events.is_empty());
+    Ok(block_events)
+}
+
+/// Accounts resolved from address lookup tables by each versioned transaction of the block.
+#[substreams::handlers::map]
+fn address_lookup_table_resolutions(block: Block) -> Result<AddressLookupTableResolutions, Error> {
+    let mut transactions: Vec<TransactionResolutions> = Vec::new();
+    for (i, transaction) in block.transactions.iter().enumerate() {
+        let message = match transaction.transaction.as_ref().and_then(|x| x.message.as_ref()) {
+            Some(message) if message.versioned && !message.address_table_lookups.is_empty() => message,
+            _ => continue,
+        };
+        let signature = utils::transaction::get_signature(&transaction);
+        match get_account_resolutions(transaction) {
+            Ok(accounts) => transactions.push(TransactionResolutions {
+                signature,
+                transaction_index: i as u32,
+                static_account_count: message.account_keys.len() as u32,
+                accounts,
+            }),
+            Err(error) => substreams::log::println(format!("Failed to process transaction {}: {}", signature, error)),
+        }
+    }
+    Ok(AddressLookupTableResolutions {
+        slot: block.slot,
+        transactions,
+    })
+}
+
+fn _block_events(block: &Block, params: &Params) -> AddressLookupTableBlockEvents {
+    let transactions = parse_block(block, params);
+    AddressLookupTableBlockEvents {
+        slot: block.slot,
+        block_time: block.block_time.as_ref().map(|x| x.timestamp).unwrap_or_default(),
+        block_hash: block.blockhash.clone(),
+        transactions,
+    }
+}
+
+pub fn parse_block(block: &Block, params: &Params) -> Vec<AddressLookupTableTransactionEvents> {
+    let include_failed = params.get_bool("include_failed");
+    let filter = Filter::new(params);
+    let mut block_events: Vec<AddressLookupTableTransactionEvents> = Vec::new();
+    for (i, transaction) in block.transactions.iter().enumerate() {
+        let failed = transaction.meta.as_ref().map_or(false, |x| x.err.is_some());
+        if failed && !include_failed {
+            continue;
+        }
+        let signature = utils::transaction::get_signature(&transaction);
+        let result = if failed {
+            Ok(parse_failed_transaction(transaction, &filter))
+        } else {
+            parse_transaction(transaction, &filter)
+        };
+        match result {
+            Ok(events) if !events.is_empty() => {
+                block_events.push(AddressLookupTableTransactionEvents {
+                    signature,
+                    transaction_index: i as u32,
+                    envelope: Some(get_transaction_envelope(transaction)),
+                    events,
+                });
+            },
+            Ok(_) => (),
+            Err(error) => substreams::log::println(format!("Failed to process transaction {}: {}", signature, error)),
+        }
+    }
+    block_events
+}
+
+pub fn parse_transaction(transaction: &ConfirmedTransaction, filter: &Filter) -> Result<Vec<AddressLookupTableEvent>, ParseError> {
+    if let Some(_) = transaction.meta.as_ref().ok_or(ParseError::MissingTransactionMeta)?.err {
+        return Ok(Vec::new())
+    }
+
+    let mut events: Vec<AddressLookupTableEvent> = Vec::new();
+
+    let context = get_context(transaction).map_err(|x| ParseError::TransactionContext(x.to_string()))?;
+    let instructions = get_structured_instructions(transaction).map_err(|x| ParseError::TransactionContext(x.to_string()))?;
+
+    let flattened = instructions.flattened();
+    for (i, instruction) in flattened.iter().enumerate() {
+        if instruction.program_id() != ADDRESS_LOOKUP_TABLE_PROGRAM_ID {
+            continue;
+        }
+
+        let call_path = get_call_path(instruction, &flattened);
+        match parse_instruction(instruction, &context) {
+            Ok(event) if filter.matches(&event) => {
+                events.push(AddressLookupTableEvent {
+                    instruction_index: i as u32,
+                    stack_depth: stack_depth(instruction),
+                    top_instruction_program_id: call_path.top_instruction_program_id,
+                    parent_instruction_program_id: call_path.parent_instruction_program_id,
+                    top_instruction_index: call_path.top_instruction_index,
+                    parent_instruction_index: call_path.parent_instruction_index,
+                    event: Some(event),
+                });
+            },
+            Ok(_) => (),
+            Err(error) => substreams::log::println(format!("Failed to process instruction of transaction {}: {}", &context.signature, error)),
+        }
+    }
+
+    Ok(events)
+}
+
+/// Parses a failed transaction into a `FailedInstruction` event, if it invoked the Address Lookup Table program.
+pub fn parse_failed_transaction(transaction: &ConfirmedTransaction, filter: &Filter) -> Vec<AddressLookupTableEvent> {
+    if !is_program_invoked(transaction, &ADDRESS_LOOKUP_TABLE_PROGRAM_ID.to_string()) {
+        return Vec::new();
+    }
+    // The Address Lookup Table program doesn't define custom errors.
+    get_failed_instruction(transaction, |_, _| None)
+        .map(|x| AddressLookupTableEvent {
+            instruction_index: flattened_index(transaction, x.instruction_index),
+            stack_depth: 0,
+            top_instruction_program_id: None,
+            parent_instruction_program_id: None,
+            top_instruction_index: None,
+            parent_instruction_index: None,
+            event: Some(Event::FailedInstruction(x)),
+        })
+        .filter(|x| x.event.as_ref().map_or(false, |x| filter.matches(x)))
+        .into_iter()
+        .collect()
+}
+
+pub fn parse_instruction<'a>(
+    instruction: &StructuredInstruction<'a>,
+    context: &TransactionContext
+) -> Result<Event, ParseError> {
+    if instruction.program_id() != ADDRESS_LOOKUP_TABLE_PROGRAM_ID {
+        return Err(ParseError::UnexpectedProgram(instruction.program_id().to_string()));
+    }
+    let unpacked = match AddressLookupTableInstruction::unpack(instruction.data()) {
+        Ok(unpacked) => unpacked,
+        Err(_) => return Ok(Event::UnknownInstruction(get_unknown_instruction(instruction, 4))),
+    };
+    match unpacked {
+        AddressLookupTableInstruction::CreateLookupTable { recent_slot, bump_seed } => {
+            _parse_create_lookup_table_instruction(instruction, context, recent_slot, bump_seed).map(Event::CreateLookupTable)
+        },
+        AddressLookupTableInstruction::FreezeLookupTable => {
+            _parse_freeze_lookup_table_instruction(instruction, context).map(Event::FreezeLookupTable)
+        },
+        AddressLookupTableInstruction::ExtendLookupTable { new_addresses } => {
+            _parse_extend_lookup_table_instruction(instruction, context, new_addresses).map(Event::ExtendLookupTable)
+        },
+        AddressLookupTableInstruction::DeactivateLookupTable => {
+            _parse_deactivate_lookup_table_instruction(instruction, context).map(Event::DeactivateLookupTable)
+        },
+        AddressLookupTableInstruction::CloseLookupTable => {
+            _parse_close_lookup_table_instruction(instruction, context).map(Event::CloseLookupTable)
+        },
+    }
+}
+
+fn _account(instruction: &StructuredInstruction, index: usize) -> Result<String, ParseError> {
+    instruction.accounts().get(index).map(|x| x.to_string()).ok_or(ParseError::MissingAccount(index))
+}
+
+fn _parse_create_lookup_table_instruction(
+    instruction: &StructuredInstruction,
+    _context: &TransactionContext,
+    recent_slot: u64,
+    bump_seed: u8,
+) -> Result<CreateLookupTableEvent, ParseError> {
+    let lookup_table = _account(instruction, 0)?;
+    let authority = _account(instruction, 1)?;
+    let payer = _account(instruction, 2)?;
+
+    Ok(CreateLookupTableEvent {
+        lookup_table,
+        authority,
+        payer,
+        recent_slot,
+        bump_seed: bump_seed.into(),
+    })
+}
+
+fn _parse_freeze_lookup_table_instruction(
+    instruction: &StructuredInstruction,
+    _context: &TransactionContext,
+) -> Result<FreezeLookupTableEvent, ParseError> {
+    let lookup_table = _account(instruction, 0)?;
+    let authority = _account(instruction, 1)?;
+
+    Ok(FreezeLookupTableEvent {
+        lookup_table,
+        authority,
+    })
+}
+
+fn _parse_extend_lookup_table_instruction(
+    instruction: &StructuredInstruction,
+    _context: &TransactionContext,
+    new_addresses: Vec<Pubkey>,
+) -> Result<ExtendLookupTableEvent, ParseError> {
+    let lookup_table = _account(instruction, 0)?;
+    let authority = _account(instruction, 1)?;
+    // The payer is only needed when the table must be funded for its new size.
+    let payer = _account(instruction, 2).ok();
+    let new_addresses = new_addresses.iter().map(|x| x.to_string()).collect();
+
+    Ok(ExtendLookupTableEvent {
+        lookup_table,
+        authority,
+        payer,
+        new_addresses,
+    })
+}
+
+fn _parse_deactivate_lookup_table_instruction(
+    instruction: &StructuredInstruction,
+    _context: &TransactionContext,
+) -> Result<DeactivateLookupTableEvent, ParseError> {
+    let lookup_table = _account(instruction, 0)?;
+    let authority = _account(instruction, 1)?;
+
+    Ok(DeactivateLookupTableEvent {
+        lookup_table,
+        authority,
+    })
+}
+
+fn _parse_close_lookup_table_instruction(
+    instruction: &StructuredInstruction,
+    _context: &TransactionContext,
+) -> Result<CloseLookupTableEvent, ParseError> {
+    let lookup_table = _account(instruction, 0)?;
+    let authority = _account(instruction, 1)?;
+    let recipient = _account(instruction, 2)?;
+
+    Ok(CloseLookupTableEvent {
+        lookup_table,
+        authority,
+        recipient,
+    })
+}
+
+pub fn parse_create_lookup_table_instruction<'a>(
+    instruction: &StructuredInstruction<'a>,
+    context: &TransactionContext,
+) -> Result<CreateLookupTableEvent, ParseError> {
+    match parse_instruction(instruction, context)? {
+        Event::CreateLookupTable(event) => Ok(event),
+        _ => Err(ParseError::InstructionMismatch("CreateLookupTable")),
+    }
+}
+
+pub fn parse_extend_lookup_table_instruction<'a>(
+    instruction: &StructuredInstruction<'a>,
+    context: &TransactionContext,
+) -> Result<ExtendLookupTableEvent, ParseError> {
+    match parse_instruction(instruction, context)? {
+        Event::ExtendLookupTable(event) => Ok(event),
+        _ => Err(ParseError::InstructionMismatch("ExtendLookupTable")),
+    }
+}
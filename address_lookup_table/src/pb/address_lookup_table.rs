@@ -0,0 +1,158 @@
+// @generated
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AddressLookupTableBlockEvents {
+    #[prost(uint64, tag="1")]
+    pub slot: u64,
+    #[prost(message, repeated, tag="2")]
+    pub transactions: ::prost::alloc::vec::Vec<AddressLookupTableTransactionEvents>,
+    #[prost(int64, tag="3")]
+    pub block_time: i64,
+    #[prost(string, tag="4")]
+    pub block_hash: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AddressLookupTableTransactionEvents {
+    #[prost(string, tag="1")]
+    pub signature: ::prost::alloc::string::String,
+    #[prost(uint32, tag="2")]
+    pub transaction_index: u32,
+    #[prost(message, repeated, tag="3")]
+    pub events: ::prost::alloc::vec::Vec<AddressLookupTableEvent>,
+    #[prost(message, optional, tag="4")]
+    pub envelope: ::core::option::Option<::solana_substreams_common::pb::common::TransactionEnvelope>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AddressLookupTableEvent {
+    #[prost(uint32, tag="1")]
+    pub instruction_index: u32,
+    /// Depth in the invocation stack, 0 for top level instructions.
+    #[prost(uint32, tag="8")]
+    pub stack_depth: u32,
+    #[prost(string, optional, tag="9")]
+    pub top_instruction_program_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag="10")]
+    pub parent_instruction_program_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(uint32, optional, tag="11")]
+    pub top_instruction_index: ::core::option::Option<u32>,
+    #[prost(uint32, optional, tag="12")]
+    pub parent_instruction_index: ::core::option::Option<u32>,
+    #[prost(oneof="address_lookup_table_event::Event", tags="2, 3, 4, 5, 6, 7, 13")]
+    pub event: ::core::option::Option<address_lookup_table_event::Event>,
+}
+/// Nested message and enum types in `AddressLookupTableEvent`.
+pub mod address_lookup_table_event {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Event {
+        #[prost(message, tag="2")]
+        CreateLookupTable(super::CreateLookupTableEvent),
+        #[prost(message, tag="3")]
+        FreezeLookupTable(super::FreezeLookupTableEvent),
+        #[prost(message, tag="4")]
+        ExtendLookupTable(super::ExtendLookupTableEvent),
+        #[prost(message, tag="5")]
+        DeactivateLookupTable(super::DeactivateLookupTableEvent),
+        #[prost(message, tag="6")]
+        CloseLookupTable(super::CloseLookupTableEvent),
+        #[prost(message, tag="7")]
+        FailedInstruction(::solana_substreams_common::pb::common::FailedInstruction),
+        #[prost(message, tag="13")]
+        UnknownInstruction(::solana_substreams_common::pb::common::UnknownInstruction),
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateLookupTableEvent {
+    #[prost(string, tag="1")]
+    pub lookup_table: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub authority: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub payer: ::prost::alloc::string::String,
+    #[prost(uint64, tag="4")]
+    pub recent_slot: u64,
+    #[prost(uint32, tag="5")]
+    pub bump_seed: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FreezeLookupTableEvent {
+    #[prost(string, tag="1")]
+    pub lookup_table: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub authority: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExtendLookupTableEvent {
+    #[prost(string, tag="1")]
+    pub lookup_table: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub authority: ::prost::alloc::string::String,
+    #[prost(string, optional, tag="3")]
+    pub payer: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, repeated, tag="4")]
+    pub new_addresses: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeactivateLookupTableEvent {
+    #[prost(string, tag="1")]
+    pub lookup_table: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub authority: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CloseLookupTableEvent {
+    #[prost(string, tag="1")]
+    pub lookup_table: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub authority: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub recipient: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AddressLookupTableResolutions {
+    #[prost(uint64, tag="1")]
+    pub slot: u64,
+    #[prost(message, repeated, tag="2")]
+    pub transactions: ::prost::alloc::vec::Vec<TransactionResolutions>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TransactionResolutions {
+    #[prost(string, tag="1")]
+    pub signature: ::prost::alloc::string::String,
+    #[prost(uint32, tag="2")]
+    pub transaction_index: u32,
+    /// Number of accounts in the message, preceding the loaded addresses.
+    #[prost(uint32, tag="3")]
+    pub static_account_count: u32,
+    #[prost(message, repeated, tag="4")]
+    pub accounts: ::prost::alloc::vec::Vec<AccountResolution>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AccountResolution {
+    /// Index of the account in the transaction, as referenced by its instructions.
+    #[prost(uint32, tag="1")]
+    pub account_index: u32,
+    #[prost(string, tag="2")]
+    pub lookup_table: ::prost::alloc::string::String,
+    #[prost(uint32, tag="3")]
+    pub lookup_table_index: u32,
+    #[prost(bool, tag="4")]
+    pub writable: bool,
+    /// Address loaded at this index, from `loaded_writable_addresses` or `loaded_readonly_addresses`.
+    #[prost(string, tag="5")]
+    pub address: ::prost::alloc::string::String,
+    /// Address at this index in the transaction context, expected to match the loaded address.
+    #[prost(string, optional, tag="6")]
+    pub context_address: ::core::option::Option<::prost::alloc::string::String>,
+}
+// @@protoc_insertion_point(module)
@@ -0,0 +1,6 @@
+// @generated
+// @@protoc_insertion_point(attribute:address_lookup_table)
+pub mod address_lookup_table {
+    include!("address_lookup_table.rs");
+    // @@protoc_insertion_point(address_lookup_table)
+}
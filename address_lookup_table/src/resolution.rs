@@ -0,0 +1,101 @@
+use substreams_solana::pb::sf::solana::r#type::v1::ConfirmedTransaction;
+
+use substreams_solana_utils as utils;
+use utils::transaction::get_context;
+
+use crate::error::ParseError;
+use crate::pb::address_lookup_table::AccountResolution;
+
+/// Lists the accounts of a transaction resolved from address lookup tables, in account index order.
+///
+/// The loaded writable addresses follow the message accounts, and the loaded readonly addresses
+/// follow them, each in the order of the lookups and their indexes. Every resolution carries the
+/// address found at the same index in the transaction context, so that index mismatches show up.
+pub fn get_account_resolutions(transaction: &ConfirmedTransaction) -> Result<Vec<AccountResolution>, ParseError> {
+    let meta = transaction.meta.as_ref().ok_or(ParseError::MissingTransactionMeta)?;
+    let message = match transaction.transaction.as_ref().and_then(|x| x.message.as_ref()) {
+        Some(message) => message,
+        None => return Ok(Vec::new()),
+    };
+    let context = get_context(transaction).map_err(|x| ParseError::TransactionContext(x.to_string()))?;
+
+    let writable = message.address_table_lookups.iter()
+        .flat_map(|x| x.writable_indexes.iter().map(move |index| (&x.account_key, *index, true)))
+        .zip(meta.loaded_writable_addresses.iter());
+    let readonly = message.address_table_lookups.iter()
+        .flat_map(|x| x.readonly_indexes.iter().map(move |index| (&x.account_key, *index, false)))
+        .zip(meta.loaded_readonly_addresses.iter());
+
+    let static_account_count = message.account_keys.len();
+    let resolutions = writable.chain(readonly)
+        .enumerate()
+        .map(|(i, ((lookup_table, lookup_table_index, writable), address))| {
+            let account_index = static_account_count + i;
+            AccountResolution {
+                account_index: account_index as u32,
+                lookup_table: bs58::encode(lookup_table).into_string(),
+                lookup_table_index: lookup_table_index as u32,
+                writable,
+                address: bs58::encode(address).into_string(),
+                context_address: context.accounts.get(account_index).map(|x| x.to_string()),
+            }
+        })
+        .collect();
+
+    Ok(resolutions)
+}
+
+#[cfg(test)]
+mod tests {
+    use substreams_solana::pb::sf::solana::r#type::v1::{Message, MessageAddressTableLookup, MessageHeader, Transaction, TransactionStatusMeta};
+    use utils::pubkey::Pubkey;
+
+    use super::*;
+
+    #[test]
+    fn test_two_lookup_tables() {
+        let (first_table, second_table) = (vec![10; 32], vec![20; 32]);
+        let message = Message {
+            header: Some(MessageHeader { num_required_signatures: 1, ..Default::default() }),
+            account_keys: (1..4).map(|x| vec![x; 32]).collect(),
+            versioned: true,
+            address_table_lookups: vec![
+                MessageAddressTableLookup { account_key: first_table.clone(), writable_indexes: vec![5, 1], readonly_indexes: vec![2] },
+                MessageAddressTableLookup { account_key: second_table.clone(), writable_indexes: vec![7], readonly_indexes: vec![0, 3] },
+            ],
+            ..Default::default()
+        };
+        let transaction = ConfirmedTransaction {
+            transaction: Some(Transaction { signatures: vec![vec![0; 64]], message: Some(message) }),
+            meta: Some(TransactionStatusMeta {
+                pre_balances: vec![0; 9],
+                post_balances: vec![0; 9],
+                loaded_writable_addresses: vec![vec![15; 32], vec![11; 32], vec![27; 32]],
+                loaded_readonly_addresses: vec![vec![12; 32], vec![20; 32], vec![23; 32]],
+                ..Default::default()
+            }),
+        };
+
+        let resolutions = get_account_resolutions(&transaction).unwrap();
+        let first_table = Pubkey(first_table.try_into().unwrap()).to_string();
+        let second_table = Pubkey(second_table.try_into().unwrap()).to_string();
+        // The writable lookups of every table come first, then the readonly ones, after the 3 message accounts.
+        let expected = [
+            (3, &first_table, 5, true, 15),
+            (4, &first_table, 1, true, 11),
+            (5, &second_table, 7, true, 27),
+            (6, &first_table, 2, false, 12),
+            (7, &second_table, 0, false, 20),
+            (8, &second_table, 3, false, 23),
+        ];
+        assert_eq!(resolutions.len(), expected.len());
+        for (resolution, (account_index, lookup_table, lookup_table_index, writable, address)) in resolutions.iter().zip(expected) {
+            assert_eq!(resolution.account_index, account_index);
+            assert_eq!(&resolution.lookup_table, lookup_table);
+            assert_eq!(resolution.lookup_table_index, lookup_table_index);
+            assert_eq!(resolution.writable, writable);
+            assert_eq!(resolution.address, Pubkey([address; 32]).to_string());
+            assert_eq!(resolution.context_address.as_ref(), Some(&resolution.address));
+        }
+    }
+}
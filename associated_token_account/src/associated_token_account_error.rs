@@ -0,0 +1,18 @@
+//! Error types of the Associated Token Account program.
+
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+use thiserror::Error;
+
+/// Errors that may be returned by the Associated Token Account program.
+#[derive(Clone, Debug, Eq, Error, FromPrimitive, PartialEq)]
+pub enum AssociatedTokenAccountError {
+    /// 0 Associated token account owner does not match address derivation.
+    #[error("Associated token account owner does not match address derivation")]
+    InvalidOwner,
+}
+
+/// Name of an Associated Token Account program custom error code, if any.
+pub fn associated_token_account_error_name(code: u32) -> Option<String> {
+    AssociatedTokenAccountError::from_u32(code).map(|x| format!("{:?}", x))
+}
@@ -0,0 +1,15 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ParseError {
+    #[error("Transaction meta is missing")]
+    MissingTransactionMeta,
+    #[error("Failed to build transaction context: {0}")]
+    TransactionContext(String),
+    #[error("Instruction does not originate from the Associated Token Account program: {0}")]
+    UnexpectedProgram(String),
+    #[error("Missing account at index {0}")]
+    MissingAccount(usize),
+    #[error("Expected a {0} instruction")]
+    InstructionMismatch(&'static str),
+}
@@ -0,0 +1,45 @@
+use std::collections::HashSet;
+
+use solana_substreams_common::params::{matches, Params};
+
+use crate::pb::associated_token_account::associated_token_account_event::Event;
+
+/// Filters events by the `wallets` and `mints` module params.
+///
+/// An event matches when its wallet and mints are in the respective filters. Unset filters match
+/// every event.
+#[derive(Clone, Debug, Default)]
+pub struct Filter {
+    wallets: Option<HashSet<String>>,
+    mints: Option<HashSet<String>>,
+}
+
+impl Filter {
+    pub fn new(params: &Params) -> Self {
+        Self {
+            wallets: params.get_set("wallets"),
+            mints: params.get_set("mints"),
+        }
+    }
+
+    pub fn matches(&self, event: &Event) -> bool {
+        matches(&self.wallets, event_wallet(event))
+            && matches(&self.mints, event_mints(event))
+    }
+}
+
+pub fn event_wallet(event: &Event) -> Option<&str> {
+    match event {
+        Event::Create(create) | Event::CreateIdempotent(create) => Some(&create.wallet),
+        Event::RecoverNested(recover_nested) => Some(&recover_nested.wallet),
+        _ => None,
+    }
+}
+
+pub fn event_mints(event: &Event) -> Vec<&str> {
+    match event {
+        Event::Create(create) | Event::CreateIdempotent(create) => vec![create.mint.as_str()],
+        Event::RecoverNested(recover_nested) => vec![recover_nested.nested_mint.as_str(), recover_nested.owner_mint.as_str()],
+        _ => Vec::new(),
+    }
+}
@@ -0,0 +1,24 @@
+use std::collections::BTreeSet;
+
+use substreams::pb::sf::substreams::index::v1::Keys;
+
+use crate::instruction::ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID;
+use crate::filter::{event_mints, event_wallet};
+use crate::pb::associated_token_account::{AssociatedTokenAccountEvent, AssociatedTokenAccountTransactionEvents};
+
+/// Block index keys of the events: `program:`, `wallet:` and `mint:`.
+pub fn index_keys(transactions: &[AssociatedTokenAccountTransactionEvents]) -> Keys {
+    let keys: BTreeSet<String> = transactions.iter().flat_map(|x| x.events.iter()).flat_map(event_keys).collect();
+    Keys { keys: keys.into_iter().collect() }
+}
+
+/// Index keys of a single event, which `filtered_associated_token_account_events` matches its query against.
+pub fn event_keys(event: &AssociatedTokenAccountEvent) -> BTreeSet<String> {
+    let mut keys = BTreeSet::new();
+    if let Some(event) = event.event.as_ref() {
+        keys.insert(format!("program:{}", ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID));
+        keys.extend(event_wallet(event).map(|x| format!("wallet:{}", x)));
+        keys.extend(event_mints(event).into_iter().map(|x| format!("mint:{}", x)));
+    }
+    keys
+}
@@ -0,0 +1,38 @@
+use substreams_solana::b58;
+use substreams_solana_utils::pubkey::Pubkey;
+
+pub const ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID: Pubkey = Pubkey(b58!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"));
+
+/// Instructions of the Associated Token Account program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssociatedTokenAccountInstruction {
+    Create,
+    CreateIdempotent,
+    RecoverNested,
+}
+
+impl AssociatedTokenAccountInstruction {
+    /// Unpacks the instruction data, where an empty data is the legacy encoding of `Create`.
+    pub fn unpack(data: &[u8]) -> Result<Self, u8> {
+        match data.first() {
+            None | Some(0) => Ok(Self::Create),
+            Some(1) => Ok(Self::CreateIdempotent),
+            Some(2) => Ok(Self::RecoverNested),
+            Some(discriminator) => Err(*discriminator),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unpack() {
+        assert_eq!(AssociatedTokenAccountInstruction::unpack(&[]), Ok(AssociatedTokenAccountInstruction::Create));
+        assert_eq!(AssociatedTokenAccountInstruction::unpack(&[0]), Ok(AssociatedTokenAccountInstruction::Create));
+        assert_eq!(AssociatedTokenAccountInstruction::unpack(&[1]), Ok(AssociatedTokenAccountInstruction::CreateIdempotent));
+        assert_eq!(AssociatedTokenAccountInstruction::unpack(&[2, 0]), Ok(AssociatedTokenAccountInstruction::RecoverNested));
+        assert_eq!(AssociatedTokenAccountInstruction::unpack(&[3]), Err(3));
+    }
+}
@@ -0,0 +1,366 @@
+use substreams::errors::Error;
+use substreams::pb::sf::substreams::index::v1::Keys;
+use substreams_solana::pb::sf::solana::r#type::v1::ConfirmedTransaction;
+use substreams_solana::pb::sf::solana::r#type::v1::Block;
+
+use substreams_solana_utils as utils;
+use utils::transaction::{get_context, TransactionContext};
+use utils::instruction::{get_structured_instructions, StructuredInstructions, StructuredInstruction};
+use utils::system_program::SYSTEM_PROGRAM_ID;
+use utils::spl_token::TokenInstruction;
+
+use solana_substreams_common::{get_failed_instruction, get_transaction_envelope, is_program_invoked};
+use solana_substreams_common::params::Params;
+use solana_substreams_common::query::Query;
+use solana_substreams_common::instruction::{flattened_index, get_call_path, get_unknown_instruction, stack_depth};
+
+use spl_token_substream::is_token_instruction;
+
+pub mod error;
+use error::ParseError;
+
+pub mod instruction;
+use instruction::{AssociatedTokenAccountInstruction, ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID};
+
+pub mod associated_token_account_error;
+use associated_token_account_error::associated_token_account_error_name;
+
+pub mod filter;
+use filter::Filter;
+
+pub mod index;
+use index::{event_keys, index_keys};
+
+pub mod pb;
+use pb::associated_token_account::*;
+use pb::associated_token_account::associated_token_account_event::Event;
+
+#[substreams::handlers::map]
+fn associated_token_account_events(params: String, block: Block) -> Result<AssociatedTokenAccountBlockEvents, Error> {
+    Ok(_block_events(&block, &Params::parse(&params)))
+}
+
+#[substreams::handlers::map]
+fn associated_token_account_index(block: Block) -> Result<Keys, Error> {
+    let transactions = parse_block(&block, &Params::default());
+    Ok(index_keys(&transactions))
+}
+
+/// Events matching the block filter query given as params, such as `wallet:<wallet>`, on their own index keys.
+#[substreams::handlers::map]
+fn filtered_associated_token_account_events(query: String, block: Block) -> Result<AssociatedTokenAccountBlockEvents, Error> {
+    let query = Query::parse(&query).map_err(Error::msg)?;
+    let mut block_events = _block_events(&block, &Params::default());
+    for transaction in block_events.transactions.iter_mut() {
+        transaction.events.retain(|x| query.matches(&event_keys(x)));
+    }
+    block_events.transactions.retain(|x| !x.events.is_empty());
+    Ok(block_events)
+}
+
+fn _block_events(block: &Block, params: &Params) -> AssociatedTokenAccountBlockEvents {
+    let transactions = parse_block(block, params);
+    AssociatedTokenAccountBlockEvents {
+        slot: block.slot,
+        block_time: block.block_time.as_ref().map(|x| x.timestamp).unwrap_or_default(),
+        block_hash: block.blockhash.clone(),
+        transactions,
+    }
+}
+
+pub fn parse_block(block: &Block, params: &Params) -> Vec<AssociatedTokenAccountTransactionEvents> {
+    let include_failed = params.get_bool("include_failed");
+    let filter = Filter::new(params);
+    let mut block_events: Vec<AssociatedTokenAccountTransactionEvents> = Vec::new();
+    for (i, transaction) in block.transactions.iter().enumerate() {
+        let failed = transaction.meta.as_ref().map_or(false, |x| x.err.is_some());
+        if failed && !include_failed {
+            continue;
+        }
+        let signature = utils::transaction::get_signature(&transaction);
+        let result = if failed {
+            Ok(parse_failed_transaction(transaction, &filter))
+        } else {
+            parse_transaction(transaction, &filter)
+        };
+        match result {
+            Ok(events) if !events.is_empty() => {
+                block_events.push(AssociatedTokenAccountTransactionEvents {
+                    signature,
+                    transaction_index: i as u32,
+                    envelope: Some(get_transaction_envelope(transaction)),
+                    events,
+                });
+            },
+            Ok(_) => (),
+            Err(error) => substreams::log::println(format!("Failed to process transaction {}: {}", signature, error)),
+        }
+    }
+    block_events
+}
+
+pub fn parse_transaction(transaction: &ConfirmedTransaction, filter: &Filter) -> Result<Vec<AssociatedTokenAccountEvent>, ParseError> {
+    if let Some(_) = transaction.meta.as_ref().ok_or(ParseError::MissingTransactionMeta)?.err {
+        return Ok(Vec::new())
+    }
+
+    let mut events: Vec<AssociatedTokenAccountEvent> = Vec::new();
+
+    let context = get_context(transaction).map_err(|x| ParseError::TransactionContext(x.to_string()))?;
+    let instructions = get_structured_instructions(transaction).map_err(|x| ParseError::TransactionContext(x.to_string()))?;
+
+    let flattened = instructions.flattened();
+    for (i, instruction) in flattened.iter().enumerate() {
+        if instruction.program_id() != ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID {
+            continue;
+        }
+
+        let call_path = get_call_path(instruction, &flattened);
+        match parse_instruction(instruction, &context) {
+            Ok(event) if filter.matches(&event) => {
+                events.push(AssociatedTokenAccountEvent {
+                    instruction_index: i as u32,
+                    stack_depth: stack_depth(instruction),
+                    top_instruction_program_id: call_path.top_instruction_program_id,
+                    parent_instruction_program_id: call_path.parent_instruction_program_id,
+                    top_instruction_index: call_path.top_instruction_index,
+                    parent_instruction_index: call_path.parent_instruction_index,
+                    event: Some(event),
+                });
+            },
+            Ok(_) => (),
+            Err(error) => substreams::log::println(format!("Failed to process instruction of transaction {}: {}", &context.signature, error)),
+        }
+    }
+
+    Ok(events)
+}
+
+/// Parses a failed transaction into a `FailedInstruction` event, if it invoked the Associated Token Account program.
+pub fn parse_failed_transaction(transaction: &ConfirmedTransaction, filter: &Filter) -> Vec<AssociatedTokenAccountEvent> {
+    if !is_program_invoked(transaction, &ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID.to_string()) {
+        return Vec::new();
+    }
+    get_failed_instruction(transaction, custom_error_name)
+        .map(|x| AssociatedTokenAccountEvent {
+            instruction_index: flattened_index(transaction, x.instruction_index),
+            stack_depth: 0,
+            top_instruction_program_id: None,
+            parent_instruction_program_id: None,
+            top_instruction_index: None,
+            parent_instruction_index: None,
+            event: Some(Event::FailedInstruction(x)),
+        })
+        .filter(|x| x.event.as_ref().map_or(false, |x| filter.matches(x)))
+        .into_iter()
+        .collect()
+}
+
+/// Name of a custom error code raised by the Associated Token Account program or one of the programs it invokes.
+pub fn custom_error_name(program_id: &str, code: u32) -> Option<String> {
+    if program_id == ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID.to_string() {
+        associated_token_account_error_name(code)
+    } else if program_id == SYSTEM_PROGRAM_ID.to_string() {
+        system_program_substream::custom_error_name(program_id, code)
+    } else {
+        spl_token_substream::custom_error_name(program_id, code)
+    }
+}
+
+pub fn parse_instruction<'a>(
+    instruction: &StructuredInstruction<'a>,
+    context: &TransactionContext
+) -> Result<Event, ParseError> {
+    if instruction.program_id() != ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID {
+        return Err(ParseError::UnexpectedProgram(instruction.program_id().to_string()));
+    }
+    let unpacked = match AssociatedTokenAccountInstruction::unpack(instruction.data()) {
+        Ok(unpacked) => unpacked,
+        Err(_) => return Ok(Event::UnknownInstruction(get_unknown_instruction(instruction, 1))),
+    };
+    match unpacked {
+        AssociatedTokenAccountInstruction::Create => {
+            _parse_create_instruction(instruction, context).map(Event::Create)
+        },
+        AssociatedTokenAccountInstruction::CreateIdempotent => {
+            _parse_create_instruction(instruction, context).map(Event::CreateIdempotent)
+        },
+        AssociatedTokenAccountInstruction::RecoverNested => {
+            _parse_recover_nested_instruction(instruction, context).map(Event::RecoverNested)
+        },
+    }
+}
+
+fn _account(instruction: &StructuredInstruction, index: usize) -> Result<String, ParseError> {
+    instruction.accounts().get(index).map(|x| x.to_string()).ok_or(ParseError::MissingAccount(index))
+}
+
+fn _parse_create_instruction(
+    instruction: &StructuredInstruction,
+    _context: &TransactionContext,
+) -> Result<CreateEvent, ParseError> {
+    let funder = _account(instruction, 0)?;
+    let associated_token_account = _account(instruction, 1)?;
+    let wallet = _account(instruction, 2)?;
+    let mint = _account(instruction, 3)?;
+    let token_program = _account(instruction, 5)?;
+
+    // Creating the account goes through the System program and initializes it with the Token
+    // program, neither of which is invoked when the account already exists.
+    let already_existed = !instruction.inner_instructions().iter().any(|x| {
+        x.program_id() == SYSTEM_PROGRAM_ID || (
+            is_token_instruction(x)
+            && matches!(TokenInstruction::unpack(x.data()), Ok(TokenInstruction::InitializeAccount3 { .. }))
+        )
+    });
+
+    Ok(CreateEvent {
+        wallet,
+        mint,
+        associated_token_account,
+        token_program,
+        funder,
+        already_existed,
+    })
+}
+
+fn _parse_recover_nested_instruction(
+    instruction: &StructuredInstruction,
+    _context: &TransactionContext,
+) -> Result<RecoverNestedEvent, ParseError> {
+    let nested_associated_token_account = _account(instruction, 0)?;
+    let nested_mint = _account(instruction, 1)?;
+    let destination_associated_token_account = _account(instruction, 2)?;
+    let owner_associated_token_account = _account(instruction, 3)?;
+    let owner_mint = _account(instruction, 4)?;
+    let wallet = _account(instruction, 5)?;
+    let token_program = _account(instruction, 6)?;
+
+    let amount = instruction.inner_instructions().iter()
+        .filter(|x| is_token_instruction(x))
+        .find_map(|x| match TokenInstruction::unpack(x.data()) {
+            Ok(TokenInstruction::TransferChecked { amount, decimals: _ }) => Some(amount),
+            _ => None,
+        });
+
+    Ok(RecoverNestedEvent {
+        wallet,
+        nested_associated_token_account,
+        nested_mint,
+        destination_associated_token_account,
+        owner_associated_token_account,
+        owner_mint,
+        token_program,
+        amount,
+    })
+}
+
+pub fn parse_create_instruction<'a>(
+    instruction: &StructuredInstruction<'a>,
+    context: &TransactionContext,
+) -> Result<CreateEvent, ParseError> {
+    match parse_instruction(instruction, context)? {
+        Event::Create(event) | Event::CreateIdempotent(event) => Ok(event),
+        _ => Err(ParseError::InstructionMismatch("Create")),
+    }
+}
+
+pub fn parse_recover_nested_instruction<'a>(
+    instruction: &StructuredInstruction<'a>,
+    context: &TransactionContext,
+) -> Result<RecoverNestedEvent, ParseError> {
+    match parse_instruction(instruction, context)? {
+        Event::RecoverNested(event) => Ok(event),
+        _ => Err(ParseError::InstructionMismatch("RecoverNested")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use substreams_solana::pb::sf::solana::r#type::v1::{
+        CompiledInstruction, InnerInstruction, InnerInstructions, Message, MessageHeader, Transaction, TransactionStatusMeta,
+    };
+    use utils::pubkey::Pubkey;
+    use utils::spl_token::TOKEN_PROGRAM_ID;
+
+    use super::*;
+
+    // Funder, associated token account, wallet, mint, System program, Token program and Associated
+    // Token Account program.
+    fn account_keys() -> Vec<Vec<u8>> {
+        let mut account_keys: Vec<Vec<u8>> = (1..5).map(|x| vec![x; 32]).collect();
+        account_keys.push(SYSTEM_PROGRAM_ID.0.to_vec());
+        account_keys.push(TOKEN_PROGRAM_ID.0.to_vec());
+        account_keys.push(ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID.0.to_vec());
+        account_keys
+    }
+
+    fn transaction(data: Vec<u8>, inner_instructions: Vec<InnerInstruction>) -> ConfirmedTransaction {
+        ConfirmedTransaction {
+            transaction: Some(Transaction {
+                signatures: vec![vec![0; 64]],
+                message: Some(Message {
+                    header: Some(MessageHeader { num_required_signatures: 1, ..Default::default() }),
+                    account_keys: account_keys(),
+                    instructions: vec![CompiledInstruction { program_id_index: 6, accounts: vec![0, 1, 2, 3, 4, 5], data }],
+                    ..Default::default()
+                }),
+            }),
+            meta: Some(TransactionStatusMeta {
+                pre_balances: vec![0; 7],
+                post_balances: vec![0; 7],
+                inner_instructions: vec![InnerInstructions { index: 0, instructions: inner_instructions }],
+                ..Default::default()
+            }),
+        }
+    }
+
+    // The System program CreateAccount and Token program InitializeAccount3 invoked to create the account.
+    fn create_account_instructions() -> Vec<InnerInstruction> {
+        let create_account = [&0u32.to_le_bytes()[..], &2_039_280u64.to_le_bytes(), &165u64.to_le_bytes(), &TOKEN_PROGRAM_ID.0].concat();
+        let initialize_account = [&[18u8][..], &[3; 32]].concat();
+        vec![
+            InnerInstruction { program_id_index: 4, accounts: vec![0, 1], data: create_account, stack_height: Some(2) },
+            InnerInstruction { program_id_index: 5, accounts: vec![1, 3], data: initialize_account, stack_height: Some(2) },
+        ]
+    }
+
+    fn parse(transaction: &ConfirmedTransaction) -> Event {
+        let context = get_context(transaction).unwrap();
+        let instructions = get_structured_instructions(transaction).unwrap();
+        parse_instruction(&instructions.flattened()[0], &context).unwrap()
+    }
+
+    #[test]
+    fn test_create_idempotent_creating_the_account() {
+        let event = match parse(&transaction(vec![1], create_account_instructions())) {
+            Event::CreateIdempotent(event) => event,
+            event => panic!("unexpected event {:?}", event),
+        };
+        assert!(!event.already_existed);
+        assert_eq!(event.funder, Pubkey([1; 32]).to_string());
+        assert_eq!(event.associated_token_account, Pubkey([2; 32]).to_string());
+        assert_eq!(event.wallet, Pubkey([3; 32]).to_string());
+        assert_eq!(event.mint, Pubkey([4; 32]).to_string());
+        assert_eq!(event.token_program, TOKEN_PROGRAM_ID.to_string());
+    }
+
+    #[test]
+    fn test_create_idempotent_on_existing_account() {
+        // The account already exists, so the program only checks it and invokes no other program.
+        let event = match parse(&transaction(vec![1], vec![])) {
+            Event::CreateIdempotent(event) => event,
+            event => panic!("unexpected event {:?}", event),
+        };
+        assert!(event.already_existed);
+    }
+
+    #[test]
+    fn test_legacy_create() {
+        let event = match parse(&transaction(vec![], create_account_instructions())) {
+            Event::Create(event) => event,
+            event => panic!("unexpected event {:?}", event),
+        };
+        assert!(!event.already_existed);
+    }
+}
@@ -0,0 +1,101 @@
+// @generated
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AssociatedTokenAccountBlockEvents {
+    #[prost(uint64, tag="1")]
+    pub slot: u64,
+    #[prost(message, repeated, tag="2")]
+    pub transactions: ::prost::alloc::vec::Vec<AssociatedTokenAccountTransactionEvents>,
+    #[prost(int64, tag="3")]
+    pub block_time: i64,
+    #[prost(string, tag="4")]
+    pub block_hash: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AssociatedTokenAccountTransactionEvents {
+    #[prost(string, tag="1")]
+    pub signature: ::prost::alloc::string::String,
+    #[prost(uint32, tag="2")]
+    pub transaction_index: u32,
+    #[prost(message, repeated, tag="3")]
+    pub events: ::prost::alloc::vec::Vec<AssociatedTokenAccountEvent>,
+    #[prost(message, optional, tag="4")]
+    pub envelope: ::core::option::Option<::solana_substreams_common::pb::common::TransactionEnvelope>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AssociatedTokenAccountEvent {
+    #[prost(uint32, tag="1")]
+    pub instruction_index: u32,
+    /// Depth in the invocation stack, 0 for top level instructions.
+    #[prost(uint32, tag="6")]
+    pub stack_depth: u32,
+    #[prost(string, optional, tag="7")]
+    pub top_instruction_program_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag="8")]
+    pub parent_instruction_program_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(uint32, optional, tag="9")]
+    pub top_instruction_index: ::core::option::Option<u32>,
+    #[prost(uint32, optional, tag="10")]
+    pub parent_instruction_index: ::core::option::Option<u32>,
+    #[prost(oneof="associated_token_account_event::Event", tags="2, 3, 4, 5, 11")]
+    pub event: ::core::option::Option<associated_token_account_event::Event>,
+}
+/// Nested message and enum types in `AssociatedTokenAccountEvent`.
+pub mod associated_token_account_event {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Event {
+        #[prost(message, tag="2")]
+        Create(super::CreateEvent),
+        #[prost(message, tag="3")]
+        CreateIdempotent(super::CreateEvent),
+        #[prost(message, tag="4")]
+        RecoverNested(super::RecoverNestedEvent),
+        #[prost(message, tag="5")]
+        FailedInstruction(::solana_substreams_common::pb::common::FailedInstruction),
+        #[prost(message, tag="11")]
+        UnknownInstruction(::solana_substreams_common::pb::common::UnknownInstruction),
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateEvent {
+    #[prost(string, tag="1")]
+    pub wallet: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub mint: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub associated_token_account: ::prost::alloc::string::String,
+    #[prost(string, tag="4")]
+    pub token_program: ::prost::alloc::string::String,
+    #[prost(string, tag="5")]
+    pub funder: ::prost::alloc::string::String,
+    /// Whether the account existed before the instruction, which then did not create it.
+    #[prost(bool, tag="6")]
+    pub already_existed: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RecoverNestedEvent {
+    #[prost(string, tag="1")]
+    pub wallet: ::prost::alloc::string::String,
+    /// Associated token account owned by the wallet's associated token account.
+    #[prost(string, tag="2")]
+    pub nested_associated_token_account: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub nested_mint: ::prost::alloc::string::String,
+    /// Associated token account of the wallet receiving the nested tokens.
+    #[prost(string, tag="4")]
+    pub destination_associated_token_account: ::prost::alloc::string::String,
+    #[prost(string, tag="5")]
+    pub owner_associated_token_account: ::prost::alloc::string::String,
+    #[prost(string, tag="6")]
+    pub owner_mint: ::prost::alloc::string::String,
+    #[prost(string, tag="7")]
+    pub token_program: ::prost::alloc::string::String,
+    #[prost(uint64, optional, tag="8")]
+    pub amount: ::core::option::Option<u64>,
+}
+// @@protoc_insertion_point(module)
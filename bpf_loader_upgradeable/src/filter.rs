@@ -0,0 +1,54 @@
+use std::collections::HashSet;
+
+use solana_substreams_common::params::{matches, Params};
+
+use crate::pb::bpf_loader_upgradeable::bpf_loader_upgradeable_event::Event;
+
+/// Filters events by the `program_ids` and `authorities` module params.
+///
+/// An event matches when its program and authorities are in the respective filters. Unset
+/// filters match every event.
+#[derive(Clone, Debug, Default)]
+pub struct Filter {
+    program_ids: Option<HashSet<String>>,
+    authorities: Option<HashSet<String>>,
+}
+
+impl Filter {
+    pub fn new(params: &Params) -> Self {
+        Self {
+            program_ids: params.get_set("program_ids"),
+            authorities: params.get_set("authorities"),
+        }
+    }
+
+    pub fn matches(&self, event: &Event) -> bool {
+        matches(&self.program_ids, event_program_id(event))
+            && matches(&self.authorities, event_authorities(event))
+    }
+}
+
+pub fn event_program_id(event: &Event) -> Option<&str> {
+    match event {
+        Event::DeployWithMaxDataLen(deploy) => Some(&deploy.program_id),
+        Event::Upgrade(upgrade) => Some(&upgrade.program_id),
+        Event::Close(close) => close.program_id.as_deref(),
+        Event::ExtendProgram(extend) => Some(&extend.program_id),
+        _ => None,
+    }
+}
+
+/// Authorities signing the event or set by it.
+pub fn event_authorities(event: &Event) -> Vec<&str> {
+    match event {
+        Event::InitializeBuffer(initialize) => initialize.authority.iter().map(|x| x.as_str()).collect(),
+        Event::Write(write) => vec![write.authority.as_str()],
+        Event::DeployWithMaxDataLen(deploy) => vec![deploy.authority.as_str()],
+        Event::Upgrade(upgrade) => vec![upgrade.authority.as_str()],
+        Event::SetAuthority(set_authority) | Event::SetAuthorityChecked(set_authority) => {
+            std::iter::once(&set_authority.authority).chain(set_authority.new_authority.iter()).map(|x| x.as_str()).collect()
+        },
+        Event::Close(close) => close.authority.iter().map(|x| x.as_str()).collect(),
+        Event::ExtendProgram(_) | Event::FailedInstruction(_) | Event::UnknownInstruction(_) => Vec::new(),
+    }
+}
@@ -0,0 +1,24 @@
+use std::collections::BTreeSet;
+
+use substreams::pb::sf::substreams::index::v1::Keys;
+
+use crate::instruction::BPF_LOADER_UPGRADEABLE_PROGRAM_ID;
+use crate::filter::{event_authorities, event_program_id};
+use crate::pb::bpf_loader_upgradeable::{BpfLoaderUpgradeableEvent, BpfLoaderUpgradeableTransactionEvents};
+
+/// Block index keys of the events: `program:`, `program_id:` and `authority:`.
+pub fn index_keys(transactions: &[BpfLoaderUpgradeableTransactionEvents]) -> Keys {
+    let keys: BTreeSet<String> = transactions.iter().flat_map(|x| x.events.iter()).flat_map(event_keys).collect();
+    Keys { keys: keys.into_iter().collect() }
+}
+
+/// Index keys of a single event, which `filtered_bpf_loader_upgradeable_events` matches its query against.
+pub fn event_keys(event: &BpfLoaderUpgradeableEvent) -> BTreeSet<String> {
+    let mut keys = BTreeSet::new();
+    if let Some(event) = event.event.as_ref() {
+        keys.insert(format!("program:{}", BPF_LOADER_UPGRADEABLE_PROGRAM_ID));
+        keys.extend(event_program_id(event).map(|x| format!("program_id:{}", x)));
+        keys.extend(event_authorities(event).into_iter().map(|x| format!("authority:{}", x)));
+    }
+    keys
+}
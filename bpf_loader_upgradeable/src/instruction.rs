@@ -0,0 +1,97 @@
+use substreams_solana::b58;
+use substreams_solana_utils::pubkey::Pubkey;
+
+pub const BPF_LOADER_UPGRADEABLE_PROGRAM_ID: Pubkey = Pubkey(b58!("BPFLoaderUpgradeab1e11111111111111111111111"));
+
+/// Instructions of the BPF Upgradeable Loader.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoaderInstruction {
+    InitializeBuffer,
+    /// Offset and length of the bytes written to the buffer.
+    Write { offset: u32, length: u32 },
+    DeployWithMaxDataLen { max_data_len: u64 },
+    Upgrade,
+    SetAuthority,
+    Close,
+    ExtendProgram { additional_bytes: u32 },
+    SetAuthorityChecked,
+    Unknown,
+}
+
+impl LoaderInstruction {
+    /// Unpacks the bincode encoded instruction data.
+    pub fn unpack(data: &[u8]) -> Result<Self, &'static str> {
+        match _u32(data, 0)? {
+            0 => Ok(Self::InitializeBuffer),
+            1 => Ok(Self::Write { offset: _u32(data, 4)?, length: _u64(data, 8)? as u32 }),
+            2 => Ok(Self::DeployWithMaxDataLen { max_data_len: _u64(data, 4)? }),
+            3 => Ok(Self::Upgrade),
+            4 => Ok(Self::SetAuthority),
+            5 => Ok(Self::Close),
+            6 => Ok(Self::ExtendProgram { additional_bytes: _u32(data, 4)? }),
+            7 => Ok(Self::SetAuthorityChecked),
+            _ => Ok(Self::Unknown),
+        }
+    }
+}
+
+fn _u32(data: &[u8], offset: usize) -> Result<u32, &'static str> {
+    data.get(offset..offset + 4)
+        .and_then(|x| x.try_into().ok())
+        .map(u32::from_le_bytes)
+        .ok_or("Instruction data is too short.")
+}
+
+fn _u64(data: &[u8], offset: usize) -> Result<u64, &'static str> {
+    data.get(offset..offset + 8)
+        .and_then(|x| x.try_into().ok())
+        .map(u64::from_le_bytes)
+        .ok_or("Instruction data is too short.")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(tag: u32, fields: &[&[u8]]) -> Vec<u8> {
+        let mut data = tag.to_le_bytes().to_vec();
+        for field in fields {
+            data.extend_from_slice(field);
+        }
+        data
+    }
+
+    #[test]
+    fn test_unpack_write() {
+        let data = encode(1, &[&16u32.to_le_bytes(), &3u64.to_le_bytes(), &[1, 2, 3]]);
+        assert_eq!(LoaderInstruction::unpack(&data), Ok(LoaderInstruction::Write { offset: 16, length: 3 }));
+    }
+
+    #[test]
+    fn test_unpack_arguments() {
+        assert_eq!(
+            LoaderInstruction::unpack(&encode(2, &[&1_000_000u64.to_le_bytes()])),
+            Ok(LoaderInstruction::DeployWithMaxDataLen { max_data_len: 1_000_000 }),
+        );
+        assert_eq!(
+            LoaderInstruction::unpack(&encode(6, &[&4096u32.to_le_bytes()])),
+            Ok(LoaderInstruction::ExtendProgram { additional_bytes: 4096 }),
+        );
+    }
+
+    #[test]
+    fn test_unpack_unit_variants() {
+        assert_eq!(LoaderInstruction::unpack(&encode(0, &[])), Ok(LoaderInstruction::InitializeBuffer));
+        assert_eq!(LoaderInstruction::unpack(&encode(3, &[])), Ok(LoaderInstruction::Upgrade));
+        assert_eq!(LoaderInstruction::unpack(&encode(7, &[])), Ok(LoaderInstruction::SetAuthorityChecked));
+        assert_eq!(LoaderInstruction::unpack(&encode(8, &[])), Ok(LoaderInstruction::Unknown));
+    }
+
+    #[test]
+    fn test_unpack_invalid_data() {
+        assert!(LoaderInstruction::unpack(&[]).is_err());
+        assert!(LoaderInstruction::unpack(&[1, 0, 0]).is_err());
+        assert!(LoaderInstruction::unpack(&encode(1, &[&0u32.to_le_bytes()])).is_err());
+        assert!(LoaderInstruction::unpack(&encode(2, &[&[0; 7]])).is_err());
+    }
+}
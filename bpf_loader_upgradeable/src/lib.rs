@@ -0,0 +1,329 @@
+use substreams::errors::Error;
+use substreams::pb::sf::substreams::index::v1::Keys;
+use substreams_solana::pb::sf::solana::r#type::v1::ConfirmedTransaction;
+use substreams_solana::pb::sf::solana::r#type::v1::Block;
+
+use substreams_solana_utils as utils;
+use utils::transaction::{get_context, TransactionContext};
+use utils::instruction::{get_structured_instructions, StructuredInstructions, StructuredInstruction};
+
+use solana_substreams_common::{get_failed_instruction, get_transaction_envelope, is_program_invoked};
+use solana_substreams_common::params::Params;
+use solana_substreams_common::query::Query;
+use solana_substreams_common::instruction::{flattened_index, get_call_path, get_unknown_instruction, stack_depth};
+
+pub mod error;
+use error::ParseError;
+
+pub mod instruction;
+use instruction::{LoaderInstruction, BPF_LOADER_UPGRADEABLE_PROGRAM_ID};
+
+pub mod workspace;
+use workspace::workspace_program;
+
+pub mod filter;
+use filter::Filter;
+
+pub mod index;
+use index::{event_keys, index_keys};
+
+pub mod pb;
+use pb::bpf_loader_upgradeable::*;
+use pb::bpf_loader_upgradeable::bpf_loader_upgradeable_event::Event;
+
+#[substreams::handlers::map]
+fn bpf_loader_upgradeable_events(params: String, block: Block) -> Result<BpfLoaderUpgradeableBlockEvents, Error> {
+    Ok(_block_events(&block, &Params::parse(&params)))
+}
+
+#[substreams::handlers::map]
+fn bpf_loader_upgradeable_index(block: Block) -> Result<Keys, Error> {
+    let transactions = parse_block(&block, &Params::default());
+    Ok(index_keys(&transactions))
+}
+
+/// Events matching the block filter query given as params, such as `program_id:<program_id>`, on their own index keys.
+#[substreams::handlers::map]
+fn filtered_bpf_loader_upgradeable_events(query: String, block: Block) -> Result<BpfLoaderUpgradeableBlockEvents, Error> {
+    let query = Query::parse(&query).map_err(Error::msg)?;
+    let mut block_events = _block_events(&block, &Params::default());
+    for transaction in block_events.transactions.iter_mut() {
+        transaction.events.retain(|x| query.matches(&event_keys(x)));
+    }
+    block_events.transactions.retain(|x| !x.events.is_empty());
+    Ok(block_events)
+}
+
+fn _block_events(block: &Block, params: &Params) -> BpfLoaderUpgradeableBlockEvents {
+    let transactions = parse_block(block, params);
+    BpfLoaderUpgradeableBlockEvents {
+        slot: block.slot,
+        block_time: block.block_time.as_ref().map(|x| x.timestamp).unwrap_or_default(),
+        block_hash: block.blockhash.clone(),
+        transactions,
+    }
+}
+
+pub fn parse_block(block: &Block, params: &Params) -> Vec<BpfLoaderUpgradeableTransactionEvents> {
+    let include_failed = params.get_bool("include_failed");
+    let filter = Filter::new(params);
+    let mut block_events: Vec<BpfLoaderUpgradeableTransactionEvents> = Vec::new();
+    for (i, transaction) in block.transactions.iter().enumerate() {
+        let failed = transaction.meta.as_ref().map_or(false, |x| x.err.is_some());
+        if failed && !include_failed {
+            continue;
+        }
+        let signature = utils::transaction::get_signature(&transaction);
+        let result = if failed {
+            Ok(parse_failed_transaction(transaction, &filter))
+        } else {
+            parse_transaction(transaction, &filter)
+        };
+        match result {
+            Ok(events) if !events.is_empty() => {
+                block_events.push(BpfLoaderUpgradeableTransactionEvents {
+                    signature,
+                    transaction_index: i as u32,
+                    envelope: Some(get_transaction_envelope(transaction)),
+                    events,
+                });
+            },
+            Ok(_) => (),
+            Err(error) => substreams::log::println(format!("Failed to process transaction {}: {}", signature, error)),
+        }
+    }
+    block_events
+}
+
+pub fn parse_transaction(transaction: &ConfirmedTransaction, filter: &Filter) -> Result<Vec<BpfLoaderUpgradeableEvent>, ParseError> {
+    if let Some(_) = transaction.meta.as_ref().ok_or(ParseError::MissingTransactionMeta)?.err {
+        return Ok(Vec::new())
+    }
+
+    let mut events: Vec<BpfLoaderUpgradeableEvent> = Vec::new();
+
+    let context = get_context(transaction).map_err(|x| ParseError::TransactionContext(x.to_string()))?;
+    let instructions = get_structured_instructions(transaction).map_err(|x| ParseError::TransactionContext(x.to_string()))?;
+
+    let flattened = instructions.flattened();
+    for (i, instruction) in flattened.iter().enumerate() {
+        if instruction.program_id() != BPF_LOADER_UPGRADEABLE_PROGRAM_ID {
+            continue;
+        }
+
+        let call_path = get_call_path(instruction, &flattened);
+        match parse_instruction(instruction, &context) {
+            Ok(Some(event)) if filter.matches(&event) => {
+                events.push(BpfLoaderUpgradeableEvent {
+                    instruction_index: i as u32,
+                    stack_depth: stack_depth(instruction),
+                    top_instruction_program_id: call_path.top_instruction_program_id,
+                    parent_instruction_program_id: call_path.parent_instruction_program_id,
+                    top_instruction_index: call_path.top_instruction_index,
+                    parent_instruction_index: call_path.parent_instruction_index,
+                    event: Some(event),
+                });
+            },
+            Ok(_) => (),
+            Err(error) => substreams::log::println(format!("Failed to process instruction of transaction {}: {}", &context.signature, error)),
+        }
+    }
+
+    Ok(events)
+}
+
+/// Parses a failed transaction into a `FailedInstruction` event, if it invoked the BPF Upgradeable Loader.
+pub fn parse_failed_transaction(transaction: &ConfirmedTransaction, filter: &Filter) -> Vec<BpfLoaderUpgradeableEvent> {
+    if !is_program_invoked(transaction, &BPF_LOADER_UPGRADEABLE_PROGRAM_ID.to_string()) {
+        return Vec::new();
+    }
+    // The loader doesn't define custom errors.
+    get_failed_instruction(transaction, |_, _| None)
+        .map(|x| BpfLoaderUpgradeableEvent {
+            instruction_index: flattened_index(transaction, x.instruction_index),
+            stack_depth: 0,
+            top_instruction_program_id: None,
+            parent_instruction_program_id: None,
+            top_instruction_index: None,
+            parent_instruction_index: None,
+            event: Some(Event::FailedInstruction(x)),
+        })
+        .filter(|x| x.event.as_ref().map_or(false, |x| filter.matches(x)))
+        .into_iter()
+        .collect()
+}
+
+pub fn parse_instruction<'a>(
+    instruction: &StructuredInstruction<'a>,
+    context: &TransactionContext
+) -> Result<Option<Event>, ParseError> {
+    if instruction.program_id() != BPF_LOADER_UPGRADEABLE_PROGRAM_ID {
+        return Err(ParseError::UnexpectedProgram(instruction.program_id().to_string()));
+    }
+    let unpacked = match LoaderInstruction::unpack(instruction.data()) {
+        Ok(unpacked) => unpacked,
+        Err(_) => return Ok(Some(Event::UnknownInstruction(get_unknown_instruction(instruction, 4)))),
+    };
+    match unpacked {
+        LoaderInstruction::InitializeBuffer => {
+            _parse_initialize_buffer_instruction(instruction, context).map(|x| Some(Event::InitializeBuffer(x)))
+        },
+        LoaderInstruction::Write { offset, length } => {
+            _parse_write_instruction(instruction, context, offset, length).map(|x| Some(Event::Write(x)))
+        },
+        LoaderInstruction::DeployWithMaxDataLen { max_data_len } => {
+            _parse_deploy_with_max_data_len_instruction(instruction, context, max_data_len).map(|x| Some(Event::DeployWithMaxDataLen(x)))
+        },
+        LoaderInstruction::Upgrade => {
+            _parse_upgrade_instruction(instruction, context).map(|x| Some(Event::Upgrade(x)))
+        },
+        LoaderInstruction::SetAuthority => {
+            _parse_set_authority_instruction(instruction, context).map(|x| Some(Event::SetAuthority(x)))
+        },
+        LoaderInstruction::SetAuthorityChecked => {
+            _parse_set_authority_instruction(instruction, context).map(|x| Some(Event::SetAuthorityChecked(x)))
+        },
+        LoaderInstruction::Close => {
+            _parse_close_instruction(instruction, context).map(|x| Some(Event::Close(x)))
+        },
+        LoaderInstruction::ExtendProgram { additional_bytes } => {
+            _parse_extend_program_instruction(instruction, context, additional_bytes).map(|x| Some(Event::ExtendProgram(x)))
+        },
+        LoaderInstruction::Unknown => Ok(Some(Event::UnknownInstruction(get_unknown_instruction(instruction, 4)))),
+    }
+}
+
+fn _account(instruction: &StructuredInstruction, index: usize) -> Result<String, ParseError> {
+    instruction.accounts().get(index).map(|x| x.to_string()).ok_or(ParseError::MissingAccount(index))
+}
+
+fn _parse_initialize_buffer_instruction(
+    instruction: &StructuredInstruction,
+    _context: &TransactionContext,
+) -> Result<InitializeBufferEvent, ParseError> {
+    let buffer = _account(instruction, 0)?;
+    let authority = _account(instruction, 1).ok();
+
+    Ok(InitializeBufferEvent {
+        buffer,
+        authority,
+    })
+}
+
+fn _parse_write_instruction(
+    instruction: &StructuredInstruction,
+    _context: &TransactionContext,
+    offset: u32,
+    length: u32,
+) -> Result<WriteEvent, ParseError> {
+    let buffer = _account(instruction, 0)?;
+    let authority = _account(instruction, 1)?;
+
+    Ok(WriteEvent {
+        buffer,
+        authority,
+        offset,
+        length,
+    })
+}
+
+fn _parse_deploy_with_max_data_len_instruction(
+    instruction: &StructuredInstruction,
+    _context: &TransactionContext,
+    max_data_len: u64,
+) -> Result<DeployWithMaxDataLenEvent, ParseError> {
+    let payer = _account(instruction, 0)?;
+    let program_data = _account(instruction, 1)?;
+    let program_id = _account(instruction, 2)?;
+    let buffer = _account(instruction, 3)?;
+    let authority = _account(instruction, 7)?;
+
+    Ok(DeployWithMaxDataLenEvent {
+        payer,
+        program_id,
+        program_data,
+        buffer,
+        authority,
+        max_data_len,
+    })
+}
+
+fn _parse_upgrade_instruction(
+    instruction: &StructuredInstruction,
+    _context: &TransactionContext,
+) -> Result<UpgradeEvent, ParseError> {
+    let program_data = _account(instruction, 0)?;
+    let program_id = _account(instruction, 1)?;
+    let buffer = _account(instruction, 2)?;
+    let spill = _account(instruction, 3)?;
+    let authority = _account(instruction, 6)?;
+    let workspace_program = instruction.accounts().get(1).and_then(|x| workspace_program(x)).map(|x| x.to_string());
+
+    Ok(UpgradeEvent {
+        program_id,
+        program_data,
+        buffer,
+        spill,
+        authority,
+        workspace_program,
+    })
+}
+
+fn _parse_set_authority_instruction(
+    instruction: &StructuredInstruction,
+    _context: &TransactionContext,
+) -> Result<SetAuthorityEvent, ParseError> {
+    let account = _account(instruction, 0)?;
+    let authority = _account(instruction, 1)?;
+    let new_authority = _account(instruction, 2).ok();
+
+    Ok(SetAuthorityEvent {
+        account,
+        authority,
+        new_authority,
+    })
+}
+
+fn _parse_close_instruction(
+    instruction: &StructuredInstruction,
+    _context: &TransactionContext,
+) -> Result<CloseEvent, ParseError> {
+    let account = _account(instruction, 0)?;
+    let recipient = _account(instruction, 1)?;
+    let authority = _account(instruction, 2).ok();
+    let program_id = _account(instruction, 3).ok();
+
+    Ok(CloseEvent {
+        account,
+        recipient,
+        authority,
+        program_id,
+    })
+}
+
+fn _parse_extend_program_instruction(
+    instruction: &StructuredInstruction,
+    _context: &TransactionContext,
+    additional_bytes: u32,
+) -> Result<ExtendProgramEvent, ParseError> {
+    let program_data = _account(instruction, 0)?;
+    let program_id = _account(instruction, 1)?;
+    let payer = _account(instruction, 3).ok();
+
+    Ok(ExtendProgramEvent {
+        program_id,
+        program_data,
+        payer,
+        additional_bytes,
+    })
+}
+
+pub fn parse_upgrade_instruction<'a>(
+    instruction: &StructuredInstruction<'a>,
+    context: &TransactionContext,
+) -> Result<UpgradeEvent, ParseError> {
+    match parse_instruction(instruction, context)? {
+        Some(Event::Upgrade(event)) => Ok(event),
+        _ => Err(ParseError::InstructionMismatch("Upgrade")),
+    }
+}
@@ -0,0 +1,165 @@
+// @generated
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BpfLoaderUpgradeableBlockEvents {
+    #[prost(uint64, tag="1")]
+    pub slot: u64,
+    #[prost(message, repeated, tag="2")]
+    pub transactions: ::prost::alloc::vec::Vec<BpfLoaderUpgradeableTransactionEvents>,
+    #[prost(int64, tag="3")]
+    pub block_time: i64,
+    #[prost(string, tag="4")]
+    pub block_hash: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BpfLoaderUpgradeableTransactionEvents {
+    #[prost(string, tag="1")]
+    pub signature: ::prost::alloc::string::String,
+    #[prost(uint32, tag="2")]
+    pub transaction_index: u32,
+    #[prost(message, repeated, tag="3")]
+    pub events: ::prost::alloc::vec::Vec<BpfLoaderUpgradeableEvent>,
+    #[prost(message, optional, tag="4")]
+    pub envelope: ::core::option::Option<::solana_substreams_common::pb::common::TransactionEnvelope>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BpfLoaderUpgradeableEvent {
+    #[prost(uint32, tag="1")]
+    pub instruction_index: u32,
+    /// Depth in the invocation stack, 0 for top level instructions.
+    #[prost(uint32, tag="11")]
+    pub stack_depth: u32,
+    #[prost(string, optional, tag="12")]
+    pub top_instruction_program_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag="13")]
+    pub parent_instruction_program_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(uint32, optional, tag="14")]
+    pub top_instruction_index: ::core::option::Option<u32>,
+    #[prost(uint32, optional, tag="15")]
+    pub parent_instruction_index: ::core::option::Option<u32>,
+    #[prost(oneof="bpf_loader_upgradeable_event::Event", tags="2, 3, 4, 5, 6, 7, 8, 9, 10, 16")]
+    pub event: ::core::option::Option<bpf_loader_upgradeable_event::Event>,
+}
+/// Nested message and enum types in `BpfLoaderUpgradeableEvent`.
+pub mod bpf_loader_upgradeable_event {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Event {
+        #[prost(message, tag="2")]
+        InitializeBuffer(super::InitializeBufferEvent),
+        #[prost(message, tag="3")]
+        Write(super::WriteEvent),
+        #[prost(message, tag="4")]
+        DeployWithMaxDataLen(super::DeployWithMaxDataLenEvent),
+        #[prost(message, tag="5")]
+        Upgrade(super::UpgradeEvent),
+        #[prost(message, tag="6")]
+        SetAuthority(super::SetAuthorityEvent),
+        #[prost(message, tag="7")]
+        SetAuthorityChecked(super::SetAuthorityEvent),
+        #[prost(message, tag="8")]
+        Close(super::CloseEvent),
+        #[prost(message, tag="9")]
+        ExtendProgram(super::ExtendProgramEvent),
+        #[prost(message, tag="10")]
+        FailedInstruction(::solana_substreams_common::pb::common::FailedInstruction),
+        #[prost(message, tag="16")]
+        UnknownInstruction(::solana_substreams_common::pb::common::UnknownInstruction),
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct InitializeBufferEvent {
+    #[prost(string, tag="1")]
+    pub buffer: ::prost::alloc::string::String,
+    #[prost(string, optional, tag="2")]
+    pub authority: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WriteEvent {
+    #[prost(string, tag="1")]
+    pub buffer: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub authority: ::prost::alloc::string::String,
+    #[prost(uint32, tag="3")]
+    pub offset: u32,
+    /// Number of bytes written.
+    #[prost(uint32, tag="4")]
+    pub length: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeployWithMaxDataLenEvent {
+    #[prost(string, tag="1")]
+    pub payer: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub program_id: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub program_data: ::prost::alloc::string::String,
+    #[prost(string, tag="4")]
+    pub buffer: ::prost::alloc::string::String,
+    #[prost(string, tag="5")]
+    pub authority: ::prost::alloc::string::String,
+    #[prost(uint64, tag="6")]
+    pub max_data_len: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpgradeEvent {
+    #[prost(string, tag="1")]
+    pub program_id: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub program_data: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub buffer: ::prost::alloc::string::String,
+    /// Recipient of the buffer lamports.
+    #[prost(string, tag="4")]
+    pub spill: ::prost::alloc::string::String,
+    #[prost(string, tag="5")]
+    pub authority: ::prost::alloc::string::String,
+    /// Name of the workspace crate decoding the program, set when the upgraded program is one of them.
+    #[prost(string, optional, tag="6")]
+    pub workspace_program: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetAuthorityEvent {
+    /// Buffer or program data account.
+    #[prost(string, tag="1")]
+    pub account: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub authority: ::prost::alloc::string::String,
+    /// Unset when the account is made immutable.
+    #[prost(string, optional, tag="3")]
+    pub new_authority: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CloseEvent {
+    /// Buffer, program data or uninitialized account closed.
+    #[prost(string, tag="1")]
+    pub account: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub recipient: ::prost::alloc::string::String,
+    #[prost(string, optional, tag="3")]
+    pub authority: ::core::option::Option<::prost::alloc::string::String>,
+    /// Program of the closed program data account.
+    #[prost(string, optional, tag="4")]
+    pub program_id: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExtendProgramEvent {
+    #[prost(string, tag="1")]
+    pub program_id: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub program_data: ::prost::alloc::string::String,
+    #[prost(string, optional, tag="3")]
+    pub payer: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(uint32, tag="4")]
+    pub additional_bytes: u32,
+}
+// @@protoc_insertion_point(module)
@@ -0,0 +1,6 @@
+// @generated
+// @@protoc_insertion_point(attribute:bpf_loader_upgradeable)
+pub mod bpf_loader_upgradeable {
+    include!("bpf_loader_upgradeable.rs");
+    // @@protoc_insertion_point(bpf_loader_upgradeable)
+}
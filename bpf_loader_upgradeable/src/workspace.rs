@@ -0,0 +1,35 @@
+use substreams_solana_utils::pubkey::Pubkey;
+
+use solana_substreams_common::programs::{
+    MPL_TOKEN_METADATA_PROGRAM_ID,
+    PUMPFUN_PROGRAM_ID,
+    RAYDIUM_AMM_PROGRAM_ID,
+    TOKEN_2022_PROGRAM_ID,
+};
+
+/// Upgradeable programs decoded by the workspace crates, along with the crate name.
+pub const WORKSPACE_PROGRAMS: [(Pubkey, &str); 4] = [
+    (PUMPFUN_PROGRAM_ID, "pumpfun"),
+    (RAYDIUM_AMM_PROGRAM_ID, "raydium_amm"),
+    (MPL_TOKEN_METADATA_PROGRAM_ID, "mpl_token_metadata"),
+    (TOKEN_2022_PROGRAM_ID, "spl_token"),
+];
+
+/// Name of the workspace crate decoding the program, if any.
+pub fn workspace_program(program_id: &Pubkey) -> Option<&'static str> {
+    WORKSPACE_PROGRAMS.iter()
+        .find(|(x, _)| x == program_id)
+        .map(|(_, name)| *name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_workspace_program() {
+        assert_eq!(workspace_program(&PUMPFUN_PROGRAM_ID), Some("pumpfun"));
+        assert_eq!(workspace_program(&TOKEN_2022_PROGRAM_ID), Some("spl_token"));
+        assert_eq!(workspace_program(&Pubkey([0; 32])), None);
+    }
+}
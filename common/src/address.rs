@@ -0,0 +1,106 @@
+use sha2::{Digest, Sha256};
+use substreams_solana_utils::pubkey::Pubkey;
+
+/// Maximum length of a seed, for both seed addresses and program derived addresses.
+pub const MAX_SEED_LEN: usize = 32;
+
+/// Maximum number of seeds of a program derived address, the bump seed included.
+pub const MAX_SEEDS: usize = 16;
+
+const PDA_MARKER: &[u8] = b"ProgramDerivedAddress";
+
+/// Derives the address of an account created by a System program `*WithSeed` instruction, as
+/// `sha256(base || seed || owner)`.
+///
+/// Returns `None` for seeds longer than `MAX_SEED_LEN`, or an owner that would let the address
+/// collide with a program derived address.
+pub fn create_with_seed(base: &Pubkey, seed: &str, owner: &Pubkey) -> Option<Pubkey> {
+    if seed.len() > MAX_SEED_LEN || owner.0.ends_with(PDA_MARKER) {
+        return None;
+    }
+    let hash = Sha256::new()
+        .chain_update(base.0)
+        .chain_update(seed.as_bytes())
+        .chain_update(owner.0)
+        .finalize();
+    Some(Pubkey(hash.into()))
+}
+
+/// Derives a program address from its seeds, the bump seed included, as
+/// `sha256(seeds || program_id || "ProgramDerivedAddress")`.
+///
+/// The address is not checked to be off the ed25519 curve, so this only verifies addresses known
+/// to be program derived, such as the accounts of an executed instruction.
+pub fn create_program_address(seeds: &[&[u8]], program_id: &Pubkey) -> Option<Pubkey> {
+    if seeds.len() > MAX_SEEDS || seeds.iter().any(|x| x.len() > MAX_SEED_LEN) {
+        return None;
+    }
+    let mut hasher = Sha256::new();
+    for seed in seeds {
+        hasher.update(seed);
+    }
+    let hash = hasher
+        .chain_update(program_id.0)
+        .chain_update(PDA_MARKER)
+        .finalize();
+    Some(Pubkey(hash.into()))
+}
+
+/// Whether the address is the one derived by a System program `*WithSeed` instruction.
+pub fn is_seed_address(address: &str, base: &Pubkey, seed: &str, owner: &Pubkey) -> bool {
+    create_with_seed(base, seed, owner).map_or(false, |x| x.to_string() == address)
+}
+
+/// Whether the address is derived from the seeds and program id.
+pub fn is_program_address(address: &str, seeds: &[&[u8]], program_id: &Pubkey) -> bool {
+    create_program_address(seeds, program_id).map_or(false, |x| x.to_string() == address)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pubkey(address: &str) -> Pubkey {
+        Pubkey(bs58::decode(address).into_vec().unwrap().try_into().unwrap())
+    }
+
+    #[test]
+    fn test_create_program_address() {
+        // Vectors of the Solana SDK.
+        let program_id = pubkey("BPFLoaderUpgradeab1e11111111111111111111111");
+        let seed_pubkey = pubkey("SeedPubey1111111111111111111111111111111111");
+        assert_eq!(create_program_address(&[b"", &[1]], &program_id), Some(pubkey("BwqrghZA2htAcqq8dzP1WDAhTXYTYWj7CHxF5j7TDBAe")));
+        assert_eq!(create_program_address(&["☉".as_bytes(), &[0]], &program_id), Some(pubkey("13yWmRpaTR4r5nAktwLqMpRNr28tnVUZw26rTvPSSB19")));
+        assert_eq!(create_program_address(&[b"Talking", b"Squirrels"], &program_id), Some(pubkey("2fnQrngrQT4SeLcdToJAD96phoEjNL2man2kfRLCASVk")));
+        assert_eq!(create_program_address(&[&seed_pubkey.0, &[1]], &program_id), Some(pubkey("976ymqVnfE32QFe6NfGDctSvVa36LWnvYxhU6G2232YL")));
+        assert!(is_program_address("2fnQrngrQT4SeLcdToJAD96phoEjNL2man2kfRLCASVk", &[b"Talking", b"Squirrels"], &program_id));
+    }
+
+    #[test]
+    fn test_create_program_address_invalid_seeds() {
+        let program_id = pubkey("BPFLoaderUpgradeab1e11111111111111111111111");
+        assert_eq!(create_program_address(&[&[0; MAX_SEED_LEN + 1]], &program_id), None);
+        assert_eq!(create_program_address(&[&b"seed"[..]; MAX_SEEDS + 1], &program_id), None);
+    }
+
+    #[test]
+    fn test_create_with_seed() {
+        let base = pubkey("SeedPubey1111111111111111111111111111111111");
+        let owner = pubkey("Stake11111111111111111111111111111111111111");
+        let address = "Et5nhG4gmHUuyyooonHmgrbSnwyePsDNU3AYgvSQW3LX";
+        assert_eq!(create_with_seed(&base, "stake:0", &owner), Some(pubkey(address)));
+        assert!(is_seed_address(address, &base, "stake:0", &owner));
+        assert!(!is_seed_address(address, &base, "stake:1", &owner));
+    }
+
+    #[test]
+    fn test_create_with_seed_invalid() {
+        let base = pubkey("SeedPubey1111111111111111111111111111111111");
+        let owner = pubkey("Stake11111111111111111111111111111111111111");
+        assert_eq!(create_with_seed(&base, &"x".repeat(MAX_SEED_LEN + 1), &owner), None);
+
+        let mut pda_owner = [0; 32];
+        pda_owner[32 - PDA_MARKER.len()..].copy_from_slice(PDA_MARKER);
+        assert_eq!(create_with_seed(&base, "seed", &Pubkey(pda_owner)), None);
+    }
+}
@@ -0,0 +1,65 @@
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+
+/// Errors raised by the Anchor framework itself, shared by every Anchor program.
+#[derive(Clone, Copy, Debug, Eq, FromPrimitive, PartialEq)]
+pub enum AnchorError {
+    InstructionMissing = 100,
+    InstructionFallbackNotFound = 101,
+    InstructionDidNotDeserialize = 102,
+    InstructionDidNotSerialize = 103,
+    IdlInstructionStub = 1000,
+    IdlInstructionInvalidProgram = 1001,
+    ConstraintMut = 2000,
+    ConstraintHasOne = 2001,
+    ConstraintSigner = 2002,
+    ConstraintRaw = 2003,
+    ConstraintOwner = 2004,
+    ConstraintRentExempt = 2005,
+    ConstraintSeeds = 2006,
+    ConstraintExecutable = 2007,
+    ConstraintState = 2008,
+    ConstraintAssociated = 2009,
+    ConstraintAssociatedInit = 2010,
+    ConstraintClose = 2011,
+    ConstraintAddress = 2012,
+    ConstraintZero = 2013,
+    ConstraintTokenMint = 2014,
+    ConstraintTokenOwner = 2015,
+    ConstraintMintMintAuthority = 2016,
+    ConstraintMintFreezeAuthority = 2017,
+    ConstraintMintDecimals = 2018,
+    ConstraintSpace = 2019,
+    RequireViolated = 2500,
+    RequireEqViolated = 2501,
+    RequireKeysEqViolated = 2502,
+    RequireNeqViolated = 2503,
+    RequireKeysNeqViolated = 2504,
+    RequireGtViolated = 2505,
+    RequireGteViolated = 2506,
+    AccountDiscriminatorAlreadySet = 3000,
+    AccountDiscriminatorNotFound = 3001,
+    AccountDiscriminatorMismatch = 3002,
+    AccountDidNotDeserialize = 3003,
+    AccountDidNotSerialize = 3004,
+    AccountNotEnoughKeys = 3005,
+    AccountNotMutable = 3006,
+    AccountOwnedByWrongProgram = 3007,
+    InvalidProgramId = 3008,
+    InvalidProgramExecutable = 3009,
+    AccountNotSigner = 3010,
+    AccountNotSystemOwned = 3011,
+    AccountNotInitialized = 3012,
+    AccountNotProgramData = 3013,
+    AccountNotAssociatedTokenAccount = 3014,
+    AccountSysvarMismatch = 3015,
+    AccountReallocExceedsLimit = 3016,
+    AccountDuplicateReallocs = 3017,
+    DeclaredProgramIdMismatch = 4100,
+    Deprecated = 5000,
+}
+
+/// Name of an Anchor framework error code, if any.
+pub fn anchor_error_name(code: u32) -> Option<String> {
+    AnchorError::from_u32(code).map(|x| format!("{:?}", x))
+}
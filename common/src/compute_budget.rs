@@ -0,0 +1,181 @@
+use substreams_solana::b58;
+use substreams_solana::pb::sf::solana::r#type::v1::ConfirmedTransaction;
+use substreams_solana_utils::pubkey::Pubkey;
+
+pub const COMPUTE_BUDGET_PROGRAM_ID: Pubkey = Pubkey(b58!("ComputeBudget111111111111111111111111111111"));
+
+/// Instructions of the Compute Budget program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComputeBudgetInstruction {
+    RequestUnits { units: u32, additional_fee: u32 },
+    RequestHeapFrame(u32),
+    SetComputeUnitLimit(u32),
+    SetComputeUnitPrice(u64),
+    SetLoadedAccountsDataSizeLimit(u32),
+}
+
+impl ComputeBudgetInstruction {
+    pub fn unpack(data: &[u8]) -> Result<Self, &'static str> {
+        let (&tag, rest) = data.split_first().ok_or("Instruction data is empty.")?;
+        match tag {
+            0 => Ok(Self::RequestUnits { units: _u32(rest, 0)?, additional_fee: _u32(rest, 4)? }),
+            1 => Ok(Self::RequestHeapFrame(_u32(rest, 0)?)),
+            2 => Ok(Self::SetComputeUnitLimit(_u32(rest, 0)?)),
+            3 => Ok(Self::SetComputeUnitPrice(_u64(rest, 0)?)),
+            4 => Ok(Self::SetLoadedAccountsDataSizeLimit(_u32(rest, 0)?)),
+            _ => Err("Unknown instruction discriminator."),
+        }
+    }
+}
+
+/// Compute units allotted to each instruction when the transaction doesn't set a limit.
+pub const DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT: u32 = 200_000;
+/// Maximum compute units a transaction can consume.
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+pub const MICRO_LAMPORTS_PER_LAMPORT: u64 = 1_000_000;
+
+/// Compute budget of a transaction, as set by its Compute Budget instructions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ComputeBudget {
+    pub compute_unit_limit: u32,
+    /// Compute unit price in micro-lamports.
+    pub compute_unit_price: u64,
+    /// Priority fee in lamports.
+    pub priority_fee: u64,
+}
+
+/// Reads the compute budget of a transaction from its top level instructions, which are the
+/// only ones the runtime applies.
+pub fn get_compute_budget(transaction: &ConfirmedTransaction) -> ComputeBudget {
+    let accounts = transaction.resolved_accounts();
+    let instructions = transaction.transaction.iter()
+        .flat_map(|x| x.message.iter())
+        .flat_map(|x| x.instructions.iter());
+
+    let mut budget_instructions: Vec<&[u8]> = Vec::new();
+    let mut non_budget_instructions: u32 = 0;
+    for instruction in instructions {
+        let is_budget_instruction = accounts.get(instruction.program_id_index as usize)
+            .map_or(false, |x| x.as_slice() == COMPUTE_BUDGET_PROGRAM_ID.0);
+        if is_budget_instruction {
+            budget_instructions.push(&instruction.data);
+        } else {
+            non_budget_instructions += 1;
+        }
+    }
+
+    compute_budget(budget_instructions, non_budget_instructions)
+}
+
+/// Computes the compute budget set by the data of the Compute Budget instructions of a
+/// transaction, given the number of its other top level instructions.
+pub fn compute_budget<'a, I>(budget_instructions: I, non_budget_instructions: u32) -> ComputeBudget
+where
+    I: IntoIterator<Item = &'a [u8]>,
+{
+    let mut compute_unit_limit: Option<u32> = None;
+    let mut compute_unit_price: u64 = 0;
+    let mut deprecated_fee: Option<u64> = None;
+    for data in budget_instructions {
+        match ComputeBudgetInstruction::unpack(data) {
+            Ok(ComputeBudgetInstruction::RequestUnits { units, additional_fee }) => {
+                compute_unit_limit = Some(units);
+                deprecated_fee = Some(additional_fee.into());
+            },
+            Ok(ComputeBudgetInstruction::SetComputeUnitLimit(units)) => compute_unit_limit = Some(units),
+            Ok(ComputeBudgetInstruction::SetComputeUnitPrice(micro_lamports)) => compute_unit_price = micro_lamports,
+            _ => (),
+        }
+    }
+
+    let compute_unit_limit = compute_unit_limit
+        .unwrap_or(non_budget_instructions.saturating_mul(DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT))
+        .min(MAX_COMPUTE_UNIT_LIMIT);
+    let priority_fee = deprecated_fee.unwrap_or_else(|| {
+        let micro_lamports = compute_unit_price as u128 * compute_unit_limit as u128;
+        micro_lamports.div_ceil(MICRO_LAMPORTS_PER_LAMPORT as u128) as u64
+    });
+
+    ComputeBudget {
+        compute_unit_limit,
+        compute_unit_price,
+        priority_fee,
+    }
+}
+
+
+fn _u32(data: &[u8], offset: usize) -> Result<u32, &'static str> {
+    data.get(offset..offset + 4)
+        .and_then(|x| x.try_into().ok())
+        .map(u32::from_le_bytes)
+        .ok_or("Instruction data is too short.")
+}
+
+fn _u64(data: &[u8], offset: usize) -> Result<u64, &'static str> {
+    data.get(offset..offset + 8)
+        .and_then(|x| x.try_into().ok())
+        .map(u64::from_le_bytes)
+        .ok_or("Instruction data is too short.")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set_compute_unit_limit(units: u32) -> Vec<u8> {
+        [&[2u8][..], &units.to_le_bytes()].concat()
+    }
+
+    fn set_compute_unit_price(micro_lamports: u64) -> Vec<u8> {
+        [&[3u8][..], &micro_lamports.to_le_bytes()].concat()
+    }
+
+    #[test]
+    fn test_default_limit() {
+        let budget = compute_budget(Vec::<&[u8]>::new(), 3);
+        assert_eq!(budget, ComputeBudget { compute_unit_limit: 600_000, compute_unit_price: 0, priority_fee: 0 });
+    }
+
+    #[test]
+    fn test_default_limit_is_capped() {
+        let price = set_compute_unit_price(1_000_000);
+        let budget = compute_budget([price.as_slice()], 10);
+        assert_eq!(budget.compute_unit_limit, MAX_COMPUTE_UNIT_LIMIT);
+        assert_eq!(budget.priority_fee, MAX_COMPUTE_UNIT_LIMIT as u64);
+    }
+
+    #[test]
+    fn test_priority_fee_rounds_up() {
+        let limit = set_compute_unit_limit(200_000);
+        let price = set_compute_unit_price(1);
+        let budget = compute_budget([limit.as_slice(), price.as_slice()], 2);
+        assert_eq!(budget.compute_unit_limit, 200_000);
+        assert_eq!(budget.compute_unit_price, 1);
+        assert_eq!(budget.priority_fee, 1);
+
+        let price = set_compute_unit_price(25_000);
+        let budget = compute_budget([limit.as_slice(), price.as_slice()], 2);
+        assert_eq!(budget.priority_fee, 5_000);
+    }
+
+    #[test]
+    fn test_request_units_additional_fee() {
+        let data = [&[0u8][..], &300_000u32.to_le_bytes(), &12_345u32.to_le_bytes()].concat();
+        let budget = compute_budget([data.as_slice()], 1);
+        assert_eq!(budget.compute_unit_limit, 300_000);
+        assert_eq!(budget.priority_fee, 12_345);
+    }
+
+    #[test]
+    fn test_invalid_instructions_are_ignored() {
+        let budget = compute_budget([&[][..], &[9u8][..], &[2u8, 1][..]], 1);
+        assert_eq!(budget, ComputeBudget { compute_unit_limit: DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT, compute_unit_price: 0, priority_fee: 0 });
+    }
+
+    #[test]
+    fn test_unpack() {
+        assert_eq!(ComputeBudgetInstruction::unpack(&set_compute_unit_limit(42)), Ok(ComputeBudgetInstruction::SetComputeUnitLimit(42)));
+        assert_eq!(ComputeBudgetInstruction::unpack(&set_compute_unit_price(7)), Ok(ComputeBudgetInstruction::SetComputeUnitPrice(7)));
+        assert!(ComputeBudgetInstruction::unpack(&[3, 0, 0]).is_err());
+    }
+}
@@ -0,0 +1,73 @@
+use std::rc::Rc;
+
+use substreams_solana::pb::sf::solana::r#type::v1::ConfirmedTransaction;
+use substreams_solana_utils::instruction::StructuredInstruction;
+
+use base64::prelude::{Engine, BASE64_STANDARD};
+
+use crate::pb::common::UnknownInstruction;
+
+/// Depth of the instruction in the invocation stack, 0 for top level instructions.
+pub fn stack_depth(instruction: &StructuredInstruction) -> u32 {
+    let mut depth = 0;
+    let mut parent = instruction.parent_instruction();
+    while let Some(instruction) = parent {
+        depth += 1;
+        parent = instruction.parent_instruction();
+    }
+    depth
+}
+
+/// Index of a top level instruction among the flattened instructions of the transaction.
+pub fn flattened_index(transaction: &ConfirmedTransaction, top_level_index: u32) -> u32 {
+    transaction.meta.iter()
+        .flat_map(|x| x.inner_instructions.iter())
+        .filter(|x| x.index < top_level_index)
+        .map(|x| x.instructions.len() as u32)
+        .sum::<u32>() + top_level_index
+}
+
+/// Top level and parent instructions of an inner instruction, unset for top level instructions.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CallPath {
+    pub top_instruction_program_id: Option<String>,
+    pub parent_instruction_program_id: Option<String>,
+    pub top_instruction_index: Option<u32>,
+    pub parent_instruction_index: Option<u32>,
+}
+
+/// Builds the call path of an instruction, with indexes among the flattened instructions of its transaction.
+pub fn get_call_path<'a>(instruction: &StructuredInstruction<'a>, flattened: &[Rc<StructuredInstruction<'a>>]) -> CallPath {
+    let parent = match instruction.parent_instruction() {
+        Some(parent) => parent,
+        None => return CallPath::default(),
+    };
+    let mut top = parent.clone();
+    while let Some(instruction) = top.parent_instruction() {
+        top = instruction;
+    }
+
+    let index = |instruction: &Rc<StructuredInstruction<'a>>| {
+        flattened.iter().position(|x| Rc::ptr_eq(x, instruction)).map(|x| x as u32)
+    };
+
+    CallPath {
+        top_instruction_program_id: Some(top.program_id().to_string()),
+        parent_instruction_program_id: Some(parent.program_id().to_string()),
+        top_instruction_index: index(&top),
+        parent_instruction_index: index(&parent),
+    }
+}
+
+/// Builds the `UnknownInstruction` of an instruction its program crate couldn't decode, taking
+/// the first `discriminator_len` bytes of the data as its discriminator.
+pub fn get_unknown_instruction(instruction: &StructuredInstruction, discriminator_len: usize) -> UnknownInstruction {
+    let data = instruction.data();
+    UnknownInstruction {
+        program_id: instruction.program_id().to_string(),
+        discriminator: data[..discriminator_len.min(data.len())].to_vec(),
+        data: BASE64_STANDARD.encode(data),
+        accounts: instruction.accounts().iter().map(|x| x.to_string()).collect(),
+        stack_depth: stack_depth(instruction),
+    }
+}
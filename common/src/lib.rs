@@ -0,0 +1,162 @@
+use substreams_solana::b58;
+use substreams_solana::pb::sf::solana::r#type::v1::ConfirmedTransaction;
+use substreams_solana_utils::pubkey::Pubkey;
+
+pub mod pb;
+use pb::common::{FailedInstruction, TransactionEnvelope};
+
+pub mod address;
+pub mod anchor;
+pub mod compute_budget;
+use compute_budget::get_compute_budget;
+pub mod instruction;
+pub mod params;
+pub mod programs;
+pub mod query;
+pub mod transaction_error;
+use transaction_error::{InstructionError, TransactionError};
+
+/// Fee charged for each signature of a transaction.
+pub const LAMPORTS_PER_SIGNATURE: u64 = 5000;
+
+/// Precompiled programs verifying signatures, which are charged like the transaction signatures.
+pub const SIGNATURE_PRECOMPILE_PROGRAM_IDS: [Pubkey; 3] = [
+    Pubkey(b58!("Ed25519SigVerify111111111111111111111111111")),
+    Pubkey(b58!("KeccakSecp256k11111111111111111111111111111")),
+    Pubkey(b58!("Secp256r1SigVerify1111111111111111111111111")),
+];
+
+/// Builds the envelope of a transaction: its signers, fees and compute units consumed.
+pub fn get_transaction_envelope(transaction: &ConfirmedTransaction) -> TransactionEnvelope {
+    let mut envelope = TransactionEnvelope::default();
+
+    if let Some(meta) = transaction.meta.as_ref() {
+        envelope.fee = meta.fee;
+        envelope.base_fee = get_signature_count(transaction).saturating_mul(LAMPORTS_PER_SIGNATURE).min(meta.fee);
+        envelope.priority_fee = get_compute_budget(transaction).priority_fee;
+        envelope.non_base_fee = meta.fee.saturating_sub(envelope.base_fee);
+        envelope.compute_units_consumed = meta.compute_units_consumed;
+    }
+
+    if let Some(message) = transaction.transaction.as_ref().and_then(|x| x.message.as_ref()) {
+        let num_signers = message.header.as_ref().map(|x| x.num_required_signatures as usize).unwrap_or(0);
+        envelope.signers = message.account_keys.iter().take(num_signers).map(|x| bs58::encode(x).into_string()).collect();
+        envelope.fee_payer = envelope.signers.first().cloned().unwrap_or_default();
+        envelope.recent_blockhash = bs58::encode(&message.recent_blockhash).into_string();
+        envelope.versioned = message.versioned;
+        envelope.address_lookup_tables = message.address_table_lookups.iter().map(|x| bs58::encode(&x.account_key).into_string()).collect();
+    }
+
+    envelope
+}
+
+/// Number of signatures charged to a transaction: its own signatures, and those verified by its
+/// top level precompile instructions, whose count is the first byte of their data.
+pub fn get_signature_count(transaction: &ConfirmedTransaction) -> u64 {
+    let accounts = transaction.resolved_accounts();
+    let message = transaction.transaction.as_ref().and_then(|x| x.message.as_ref());
+    let precompile_signatures: u64 = message.iter()
+        .flat_map(|x| x.instructions.iter())
+        .filter(|x| {
+            accounts.get(x.program_id_index as usize)
+                .map_or(false, |program_id| SIGNATURE_PRECOMPILE_PROGRAM_IDS.iter().any(|x| program_id.as_slice() == x.0))
+        })
+        .map(|x| x.data.first().cloned().unwrap_or(0) as u64)
+        .sum();
+    let signatures = transaction.transaction.as_ref().map_or(0, |x| x.signatures.len() as u64);
+    signatures + precompile_signatures
+}
+
+/// Builds the `FailedInstruction` of a transaction that failed on one of its instructions.
+///
+/// `custom_error_name` maps the program that raised the error and its custom error code to the
+/// error name, and is only called for `Custom` instruction errors.
+pub fn get_failed_instruction<F>(transaction: &ConfirmedTransaction, custom_error_name: F) -> Option<FailedInstruction>
+where
+    F: Fn(&str, u32) -> Option<String>,
+{
+    let meta = transaction.meta.as_ref()?;
+    let (index, error) = match TransactionError::unpack(&meta.err.as_ref()?.err)? {
+        TransactionError::InstructionError(index, error) => (index, error),
+        TransactionError::Other(..) => return None,
+    };
+
+    let message = transaction.transaction.as_ref()?.message.as_ref()?;
+    let program_id_index = message.instructions.get(index as usize)?.program_id_index;
+    let program_id = bs58::encode(transaction.resolved_accounts().get(program_id_index as usize)?).into_string();
+    let failing_program_id = meta.log_messages.iter().find_map(|x| _failed_program(x));
+
+    let custom_error_code = match error {
+        InstructionError::Custom(code) => Some(code),
+        _ => None,
+    };
+    let custom_error_name = custom_error_code.and_then(|code| {
+        custom_error_name(failing_program_id.as_ref().unwrap_or(&program_id), code)
+    });
+
+    Some(FailedInstruction {
+        instruction_index: index as u32,
+        program_id,
+        failing_program_id,
+        error: error.to_string(),
+        custom_error_code,
+        custom_error_name,
+    })
+}
+
+/// Whether the program is invoked by the transaction, either directly or through CPI.
+pub fn is_program_invoked(transaction: &ConfirmedTransaction, program_id: &str) -> bool {
+    let accounts = transaction.resolved_accounts();
+    let top_level = transaction.transaction.iter()
+        .flat_map(|x| x.message.iter())
+        .flat_map(|x| x.instructions.iter())
+        .filter_map(|x| accounts.get(x.program_id_index as usize))
+        .any(|x| bs58::encode(x).into_string() == program_id);
+
+    let invoke = format!("Program {} invoke [", program_id);
+    top_level || transaction.meta.iter().flat_map(|x| x.log_messages.iter()).any(|x| x.starts_with(&invoke))
+}
+
+fn _failed_program(log: &str) -> Option<String> {
+    let rest = log.strip_prefix("Program ")?;
+    let (program_id, rest) = rest.split_once(' ')?;
+    rest.starts_with("failed: ").then(|| program_id.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use substreams_solana::pb::sf::solana::r#type::v1::{CompiledInstruction, Message, MessageHeader, Transaction, TransactionStatusMeta};
+
+    use super::*;
+    use compute_budget::COMPUTE_BUDGET_PROGRAM_ID;
+
+    #[test]
+    fn test_transaction_envelope_fees() {
+        let account_keys = vec![vec![1; 32], SIGNATURE_PRECOMPILE_PROGRAM_IDS[0].0.to_vec(), COMPUTE_BUDGET_PROGRAM_ID.0.to_vec()];
+        let instructions = vec![
+            // Two signatures verified by the Ed25519 program.
+            CompiledInstruction { program_id_index: 1, data: vec![2, 0], ..Default::default() },
+            CompiledInstruction { program_id_index: 2, data: [&[2u8][..], &200_000u32.to_le_bytes()].concat(), ..Default::default() },
+            CompiledInstruction { program_id_index: 2, data: [&[3u8][..], &10_000u64.to_le_bytes()].concat(), ..Default::default() },
+        ];
+        let transaction = ConfirmedTransaction {
+            transaction: Some(Transaction {
+                signatures: vec![vec![0; 64]],
+                message: Some(Message {
+                    header: Some(MessageHeader { num_required_signatures: 1, ..Default::default() }),
+                    account_keys,
+                    instructions,
+                    ..Default::default()
+                }),
+            }),
+            meta: Some(TransactionStatusMeta { fee: 17_000, ..Default::default() }),
+        };
+
+        let envelope = get_transaction_envelope(&transaction);
+        assert_eq!(get_signature_count(&transaction), 3);
+        assert_eq!(envelope.base_fee, 15_000);
+        assert_eq!(envelope.priority_fee, 2_000);
+        assert_eq!(envelope.non_base_fee, 2_000);
+        assert_eq!(envelope.fee_payer, Pubkey([1; 32]).to_string());
+    }
+}
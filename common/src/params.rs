@@ -0,0 +1,80 @@
+use std::collections::{HashMap, HashSet};
+
+/// Module params, given as `key=value` pairs separated by `&`, with comma separated values.
+///
+/// For instance `include_failed=true&mints=<mint>,<mint>`.
+#[derive(Clone, Debug, Default)]
+pub struct Params {
+    values: HashMap<String, Vec<String>>,
+}
+
+impl Params {
+    pub fn parse(params: &str) -> Self {
+        let values = params.split('&')
+            .filter_map(|x| x.split_once('='))
+            .map(|(key, value)| {
+                let values = value.split(',').map(|x| x.trim()).filter(|x| !x.is_empty()).map(String::from).collect();
+                (key.trim().to_string(), values)
+            })
+            .collect();
+
+        Self { values }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Vec<String>> {
+        self.values.get(key)
+    }
+
+    /// Values of a filter param, `None` if the param is unset or empty.
+    pub fn get_set(&self, key: &str) -> Option<HashSet<String>> {
+        self.get(key).filter(|x| !x.is_empty()).map(|x| x.iter().cloned().collect())
+    }
+
+    pub fn get_bool(&self, key: &str) -> bool {
+        self.get(key).and_then(|x| x.first()).map(|x| x == "true").unwrap_or(false)
+    }
+}
+
+/// Whether any of the values is in the filter, an unset filter matching everything.
+pub fn matches<'a, I>(filter: &Option<HashSet<String>>, values: I) -> bool
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    match filter {
+        Some(filter) => values.into_iter().any(|x| filter.contains(x)),
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let params = Params::parse("include_failed=true&mints= a, b ,,&owners=");
+        assert!(params.get_bool("include_failed"));
+        assert!(!params.get_bool("include_memos"));
+        assert_eq!(params.get("mints"), Some(&vec!["a".to_string(), "b".to_string()]));
+        assert_eq!(params.get_set("mints").map(|x| x.len()), Some(2));
+        assert!(params.get_set("owners").is_none());
+        assert!(params.get_set("users").is_none());
+    }
+
+    #[test]
+    fn test_parse_empty() {
+        let params = Params::parse("");
+        assert!(params.get("mints").is_none());
+        assert!(!params.get_bool("include_failed"));
+    }
+
+    #[test]
+    fn test_matches() {
+        let filter = Params::parse("mints=a,b").get_set("mints");
+        assert!(matches(&filter, ["c", "a"]));
+        assert!(!matches(&filter, ["c"]));
+        assert!(!matches(&filter, None));
+        assert!(matches(&None, None));
+        assert!(matches(&None, ["c"]));
+    }
+}
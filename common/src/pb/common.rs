@@ -0,0 +1,67 @@
+// @generated
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FailedInstruction {
+    /// Index of the top level instruction that failed.
+    #[prost(uint32, tag="1")]
+    pub instruction_index: u32,
+    /// Program of the top level instruction that failed.
+    #[prost(string, tag="2")]
+    pub program_id: ::prost::alloc::string::String,
+    /// Program that raised the error, which may be invoked through CPI. Unset when the logs are truncated.
+    #[prost(string, optional, tag="3")]
+    pub failing_program_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, tag="4")]
+    pub error: ::prost::alloc::string::String,
+    #[prost(uint32, optional, tag="5")]
+    pub custom_error_code: ::core::option::Option<u32>,
+    #[prost(string, optional, tag="6")]
+    pub custom_error_name: ::core::option::Option<::prost::alloc::string::String>,
+}
+/// Instruction of the program that couldn't be decoded, such as one added by a new program version.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UnknownInstruction {
+    #[prost(string, tag="1")]
+    pub program_id: ::prost::alloc::string::String,
+    /// Leading bytes of the data identifying the instruction, empty when the program has none.
+    #[prost(bytes="vec", tag="2")]
+    pub discriminator: ::prost::alloc::vec::Vec<u8>,
+    /// Base64 encoded instruction data.
+    #[prost(string, tag="3")]
+    pub data: ::prost::alloc::string::String,
+    #[prost(string, repeated, tag="4")]
+    pub accounts: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Depth in the invocation stack, 0 for top level instructions.
+    #[prost(uint32, tag="5")]
+    pub stack_depth: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TransactionEnvelope {
+    #[prost(string, tag="1")]
+    pub fee_payer: ::prost::alloc::string::String,
+    #[prost(string, repeated, tag="2")]
+    pub signers: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Total fee paid.
+    #[prost(uint64, tag="3")]
+    pub fee: u64,
+    /// Fee of the transaction signatures, including those verified by precompiled programs.
+    #[prost(uint64, tag="4")]
+    pub base_fee: u64,
+    /// Priority fee in lamports, derived from the Compute Budget instructions.
+    #[prost(uint64, tag="5")]
+    pub priority_fee: u64,
+    #[prost(uint64, optional, tag="6")]
+    pub compute_units_consumed: ::core::option::Option<u64>,
+    #[prost(string, tag="7")]
+    pub recent_blockhash: ::prost::alloc::string::String,
+    #[prost(bool, tag="8")]
+    pub versioned: bool,
+    #[prost(string, repeated, tag="9")]
+    pub address_lookup_tables: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Fee paid above the base fee, the priority fee included.
+    #[prost(uint64, tag="10")]
+    pub non_base_fee: u64,
+}
+// @@protoc_insertion_point(module)
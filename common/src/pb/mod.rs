@@ -0,0 +1,6 @@
+// @generated
+// @@protoc_insertion_point(attribute:common)
+pub mod common {
+    include!("common.rs");
+    // @@protoc_insertion_point(common)
+}
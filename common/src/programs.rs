@@ -0,0 +1,27 @@
+use substreams_solana::b58;
+use substreams_solana_utils::pubkey::Pubkey;
+use substreams_solana_utils::spl_token::TOKEN_PROGRAM_ID;
+
+pub const TOKEN_2022_PROGRAM_ID: Pubkey = Pubkey(b58!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb"));
+pub const PUMPFUN_PROGRAM_ID: Pubkey = Pubkey(b58!("6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P"));
+pub const RAYDIUM_AMM_PROGRAM_ID: Pubkey = Pubkey(b58!("675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8"));
+pub const MPL_TOKEN_METADATA_PROGRAM_ID: Pubkey = Pubkey(b58!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"));
+
+pub const PUMPFUN_CREATE_DISCRIMINATOR: [u8; 8] = [24, 30, 200, 40, 5, 28, 7, 119];
+
+pub const RAYDIUM_AMM_INITIALIZE2_TAG: u8 = 1;
+
+/// Borsh variant indices of the Token Metadata instructions creating metadata accounts.
+pub mod metadata_instruction {
+    pub const CREATE_METADATA_ACCOUNT: u8 = 0;
+    pub const CREATE_MASTER_EDITION: u8 = 10;
+    pub const CREATE_METADATA_ACCOUNT_V2: u8 = 16;
+    pub const CREATE_MASTER_EDITION_V3: u8 = 17;
+    pub const CREATE_METADATA_ACCOUNT_V3: u8 = 33;
+    pub const CREATE: u8 = 42;
+}
+
+/// Whether the program is the Token or the Token-2022 program.
+pub fn is_token_program(program_id: &Pubkey) -> bool {
+    *program_id == TOKEN_PROGRAM_ID || *program_id == TOKEN_2022_PROGRAM_ID
+}
@@ -0,0 +1,170 @@
+use std::collections::BTreeSet;
+
+/// Block filter query over index keys, such as `mint:<mint> || (owner:<owner> && -program:<program>)`.
+///
+/// Terms are combined with `&&`, `||` and parentheses, and negated with a leading `-`. Terms only
+/// separated by whitespace are combined with `&&`, and an empty query matches everything.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Query {
+    Key(String),
+    Not(Box<Query>),
+    And(Vec<Query>),
+    Or(Vec<Query>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Key(String),
+    Not,
+    And,
+    Or,
+    Open,
+    Close,
+}
+
+impl Query {
+    pub fn parse(query: &str) -> Result<Self, String> {
+        let tokens = _tokenize(query)?;
+        if tokens.is_empty() {
+            return Ok(Query::And(vec![]));
+        }
+        let mut position = 0;
+        let parsed = _parse_or(&tokens, &mut position)?;
+        match tokens.get(position) {
+            Some(token) => Err(format!("Unexpected {:?} in query `{}`", token, query)),
+            None => Ok(parsed),
+        }
+    }
+
+    /// Whether the index keys satisfy the query.
+    pub fn matches(&self, keys: &BTreeSet<String>) -> bool {
+        match self {
+            Query::Key(key) => keys.contains(key),
+            Query::Not(query) => !query.matches(keys),
+            Query::And(queries) => queries.iter().all(|x| x.matches(keys)),
+            Query::Or(queries) => queries.iter().any(|x| x.matches(keys)),
+        }
+    }
+}
+
+fn _tokenize(query: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = query.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => (),
+            '(' => tokens.push(Token::Open),
+            ')' => tokens.push(Token::Close),
+            '-' => tokens.push(Token::Not),
+            '&' | '|' => {
+                if chars.next() != Some(c) {
+                    return Err(format!("Expected `{}{}` in query `{}`", c, c, query));
+                }
+                tokens.push(if c == '&' { Token::And } else { Token::Or });
+            }
+            c => {
+                let mut key = c.to_string();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || "()&|".contains(c) {
+                        break;
+                    }
+                    key.push(c);
+                    chars.next();
+                }
+                tokens.push(Token::Key(key));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+fn _parse_or(tokens: &[Token], position: &mut usize) -> Result<Query, String> {
+    let mut queries = vec![_parse_and(tokens, position)?];
+    while tokens.get(*position) == Some(&Token::Or) {
+        *position += 1;
+        queries.push(_parse_and(tokens, position)?);
+    }
+    Ok(if queries.len() == 1 { queries.remove(0) } else { Query::Or(queries) })
+}
+
+fn _parse_and(tokens: &[Token], position: &mut usize) -> Result<Query, String> {
+    let mut queries = Vec::new();
+    loop {
+        match tokens.get(*position) {
+            None | Some(Token::Or) | Some(Token::Close) => break,
+            Some(Token::And) if !queries.is_empty() => *position += 1,
+            _ => (),
+        }
+        queries.push(_parse_unary(tokens, position)?);
+    }
+    if queries.is_empty() {
+        return Err("Expected a key in query".to_string());
+    }
+    Ok(if queries.len() == 1 { queries.remove(0) } else { Query::And(queries) })
+}
+
+fn _parse_unary(tokens: &[Token], position: &mut usize) -> Result<Query, String> {
+    let token = tokens.get(*position).ok_or("Unexpected end of query")?;
+    *position += 1;
+    match token {
+        Token::Key(key) => Ok(Query::Key(key.clone())),
+        Token::Not => Ok(Query::Not(Box::new(_parse_unary(tokens, position)?))),
+        Token::Open => {
+            let query = _parse_or(tokens, position)?;
+            match tokens.get(*position) {
+                Some(Token::Close) => {
+                    *position += 1;
+                    Ok(query)
+                }
+                _ => Err("Expected `)` in query".to_string()),
+            }
+        }
+        token => Err(format!("Unexpected {:?} in query", token)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(keys: &[&str]) -> BTreeSet<String> {
+        keys.iter().map(|x| x.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        let key = |x: &str| Query::Key(x.to_string());
+        assert_eq!(Query::parse("mint:a").unwrap(), key("mint:a"));
+        assert_eq!(
+            Query::parse("mint:a || owner:b && -program:c").unwrap(),
+            Query::Or(vec![key("mint:a"), Query::And(vec![key("owner:b"), Query::Not(Box::new(key("program:c")))])]),
+        );
+        assert_eq!(
+            Query::parse("(mint:a || mint:b) owner:c").unwrap(),
+            Query::And(vec![Query::Or(vec![key("mint:a"), key("mint:b")]), key("owner:c")]),
+        );
+        assert_eq!(Query::parse(" ").unwrap(), Query::And(vec![]));
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(Query::parse("mint:a & owner:b").is_err());
+        assert!(Query::parse("(mint:a").is_err());
+        assert!(Query::parse("mint:a)").is_err());
+        assert!(Query::parse("mint:a ||").is_err());
+        assert!(Query::parse("&& mint:a").is_err());
+        assert!(Query::parse("mint:a -").is_err());
+        assert!(Query::parse("()").is_err());
+    }
+
+    #[test]
+    fn test_matches() {
+        let query = Query::parse("program:p && (mint:a || owner:b) && -owner:c").unwrap();
+        assert!(query.matches(&keys(&["program:p", "mint:a"])));
+        assert!(query.matches(&keys(&["program:p", "owner:b"])));
+        assert!(!query.matches(&keys(&["program:p", "mint:b"])));
+        assert!(!query.matches(&keys(&["mint:a"])));
+        assert!(!query.matches(&keys(&["program:p", "mint:a", "owner:c"])));
+        assert!(Query::parse("").unwrap().matches(&keys(&[])));
+    }
+}
@@ -0,0 +1,178 @@
+use std::fmt;
+
+const TRANSACTION_ERRORS: [&str; 37] = [
+    "AccountInUse",
+    "AccountLoadedTwice",
+    "AccountNotFound",
+    "ProgramAccountNotFound",
+    "InsufficientFundsForFee",
+    "InvalidAccountForFee",
+    "AlreadyProcessed",
+    "BlockhashNotFound",
+    "InstructionError",
+    "CallChainTooDeep",
+    "MissingSignatureForFee",
+    "InvalidAccountIndex",
+    "SignatureFailure",
+    "InvalidProgramForExecution",
+    "SanitizeFailure",
+    "ClusterMaintenance",
+    "AccountBorrowOutstanding",
+    "WouldExceedMaxBlockCostLimit",
+    "UnsupportedVersion",
+    "InvalidWritableAccount",
+    "WouldExceedMaxAccountCostLimit",
+    "WouldExceedAccountDataBlockLimit",
+    "TooManyAccountLocks",
+    "AddressLookupTableNotFound",
+    "InvalidAddressLookupTableOwner",
+    "InvalidAddressLookupTableData",
+    "InvalidAddressLookupTableIndex",
+    "InvalidRentPayingAccount",
+    "WouldExceedMaxVoteCostLimit",
+    "WouldExceedAccountDataTotalLimit",
+    "DuplicateInstruction",
+    "InsufficientFundsForRent",
+    "MaxLoadedAccountsDataSizeExceeded",
+    "InvalidLoadedAccountsDataSizeLimit",
+    "ResanityCheckFailed",
+    "ProgramExecutionTemporarilyRestricted",
+    "UnbalancedTransaction",
+];
+
+const INSTRUCTION_ERRORS: [&str; 54] = [
+    "GenericError",
+    "InvalidArgument",
+    "InvalidInstructionData",
+    "InvalidAccountData",
+    "AccountDataTooSmall",
+    "InsufficientFunds",
+    "IncorrectProgramId",
+    "MissingRequiredSignature",
+    "AccountAlreadyInitialized",
+    "UninitializedAccount",
+    "UnbalancedInstruction",
+    "ModifiedProgramId",
+    "ExternalAccountLamportSpend",
+    "ExternalAccountDataModified",
+    "ReadonlyLamportChange",
+    "ReadonlyDataModified",
+    "DuplicateAccountIndex",
+    "ExecutableModified",
+    "RentEpochModified",
+    "NotEnoughAccountKeys",
+    "AccountDataSizeChanged",
+    "AccountNotExecutable",
+    "AccountBorrowFailed",
+    "AccountBorrowOutstanding",
+    "DuplicateAccountOutOfSync",
+    "Custom",
+    "InvalidError",
+    "ExecutableDataModified",
+    "ExecutableLamportChange",
+    "ExecutableAccountNotRentExempt",
+    "UnsupportedProgramId",
+    "CallDepth",
+    "MissingAccount",
+    "ReentrancyNotAllowed",
+    "MaxSeedLengthExceeded",
+    "InvalidSeeds",
+    "InvalidRealloc",
+    "ComputationalBudgetExceeded",
+    "PrivilegeEscalation",
+    "ProgramEnvironmentSetupFailure",
+    "ProgramFailedToComplete",
+    "ProgramFailedToCompile",
+    "Immutable",
+    "IncorrectAuthority",
+    "BorshIoError",
+    "AccountNotRentExempt",
+    "InvalidAccountOwner",
+    "ArithmeticOverflow",
+    "UnsupportedSysvar",
+    "IllegalOwner",
+    "MaxAccountsDataAllocationsExceeded",
+    "MaxAccountsResizeExceeded",
+    "MaxInstructionTraceLengthExceeded",
+    "BuiltinProgramsMustConsumeComputeUnits",
+];
+
+const INSTRUCTION_ERROR_TAG: u32 = 8;
+const CUSTOM_TAG: u32 = 25;
+const BORSH_IO_ERROR_TAG: u32 = 44;
+
+/// Error of a failed transaction, as bincode serialized in the transaction meta.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TransactionError {
+    InstructionError(u8, InstructionError),
+    /// Transaction level error, along with the index carried by some of them.
+    Other(&'static str, Option<u8>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum InstructionError {
+    Custom(u32),
+    BorshIoError(String),
+    Other(&'static str),
+}
+
+impl TransactionError {
+    pub fn unpack(data: &[u8]) -> Option<Self> {
+        let (tag, rest) = _unpack_u32(data)?;
+        match tag {
+            INSTRUCTION_ERROR_TAG => {
+                let (index, rest) = rest.split_first()?;
+                Some(Self::InstructionError(*index, InstructionError::unpack(rest)?))
+            }
+            _ => {
+                let name = TRANSACTION_ERRORS.get(tag as usize)?;
+                Some(Self::Other(name, rest.first().cloned()))
+            }
+        }
+    }
+}
+
+impl InstructionError {
+    pub fn unpack(data: &[u8]) -> Option<Self> {
+        let (tag, rest) = _unpack_u32(data)?;
+        match tag {
+            CUSTOM_TAG => Some(Self::Custom(_unpack_u32(rest)?.0)),
+            BORSH_IO_ERROR_TAG => {
+                let (length, rest) = _unpack_u64(rest)?;
+                let message = rest.get(..length as usize)?;
+                Some(Self::BorshIoError(String::from_utf8_lossy(message).to_string()))
+            }
+            _ => INSTRUCTION_ERRORS.get(tag as usize).map(|x| Self::Other(x)),
+        }
+    }
+}
+
+impl fmt::Display for TransactionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InstructionError(index, error) => write!(f, "InstructionError({}, {})", index, error),
+            Self::Other(name, Some(index)) => write!(f, "{}({})", name, index),
+            Self::Other(name, None) => write!(f, "{}", name),
+        }
+    }
+}
+
+impl fmt::Display for InstructionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Custom(code) => write!(f, "Custom({})", code),
+            Self::BorshIoError(message) => write!(f, "BorshIoError({})", message),
+            Self::Other(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+fn _unpack_u32(data: &[u8]) -> Option<(u32, &[u8])> {
+    let (value, rest) = data.split_first_chunk::<4>()?;
+    Some((u32::from_le_bytes(*value), rest))
+}
+
+fn _unpack_u64(data: &[u8]) -> Option<(u64, &[u8])> {
+    let (value, rest) = data.split_first_chunk::<8>()?;
+    Some((u64::from_le_bytes(*value), rest))
+}
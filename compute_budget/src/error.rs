@@ -0,0 +1,13 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ParseError {
+    #[error("Transaction meta is missing")]
+    MissingTransactionMeta,
+    #[error("Failed to build transaction context: {0}")]
+    TransactionContext(String),
+    #[error("Instruction does not originate from the Compute Budget program: {0}")]
+    UnexpectedProgram(String),
+    #[error("Invalid instruction data: {0}")]
+    InvalidInstructionData(String),
+}
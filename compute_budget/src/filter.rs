@@ -0,0 +1,24 @@
+use std::collections::HashSet;
+
+use solana_substreams_common::params::{matches, Params};
+use solana_substreams_common::pb::common::TransactionEnvelope;
+
+/// Filters transactions by the `fee_payers` module param.
+///
+/// An unset filter matches every transaction.
+#[derive(Clone, Debug, Default)]
+pub struct Filter {
+    fee_payers: Option<HashSet<String>>,
+}
+
+impl Filter {
+    pub fn new(params: &Params) -> Self {
+        Self {
+            fee_payers: params.get_set("fee_payers"),
+        }
+    }
+
+    pub fn matches(&self, envelope: &TransactionEnvelope) -> bool {
+        matches(&self.fee_payers, Some(envelope.fee_payer.as_str()))
+    }
+}
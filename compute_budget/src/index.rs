@@ -0,0 +1,20 @@
+use std::collections::BTreeSet;
+
+use substreams::pb::sf::substreams::index::v1::Keys;
+
+use crate::instruction::COMPUTE_BUDGET_PROGRAM_ID;
+use crate::pb::compute_budget::ComputeBudgetTransactionEvents;
+
+/// Block index keys of the transactions: `program:` and `fee_payer:`.
+pub fn index_keys(transactions: &[ComputeBudgetTransactionEvents]) -> Keys {
+    let keys: BTreeSet<String> = transactions.iter().flat_map(transaction_keys).collect();
+    Keys { keys: keys.into_iter().collect() }
+}
+
+/// Index keys of a single transaction, which `filtered_compute_budget_events` matches its query against.
+pub fn transaction_keys(transaction: &ComputeBudgetTransactionEvents) -> BTreeSet<String> {
+    let mut keys = BTreeSet::new();
+    keys.insert(format!("program:{}", COMPUTE_BUDGET_PROGRAM_ID));
+    keys.extend(transaction.envelope.as_ref().map(|x| format!("fee_payer:{}", x.fee_payer)));
+    keys
+}
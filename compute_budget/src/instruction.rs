@@ -0,0 +1 @@
+pub use solana_substreams_common::compute_budget::{ComputeBudgetInstruction, COMPUTE_BUDGET_PROGRAM_ID};
@@ -0,0 +1,188 @@
+use substreams::errors::Error;
+use substreams::pb::sf::substreams::index::v1::Keys;
+use substreams_solana::pb::sf::solana::r#type::v1::ConfirmedTransaction;
+use substreams_solana::pb::sf::solana::r#type::v1::Block;
+
+use substreams_solana_utils as utils;
+use utils::transaction::{get_context, TransactionContext};
+use utils::instruction::{get_structured_instructions, StructuredInstructions, StructuredInstruction};
+
+use solana_substreams_common::{get_failed_instruction, get_transaction_envelope, is_program_invoked};
+use solana_substreams_common::params::Params;
+use solana_substreams_common::query::Query;
+use solana_substreams_common::instruction::{flattened_index, get_call_path, get_unknown_instruction, stack_depth};
+
+pub mod error;
+use error::ParseError;
+
+pub mod instruction;
+use instruction::{ComputeBudgetInstruction, COMPUTE_BUDGET_PROGRAM_ID};
+
+pub use solana_substreams_common::compute_budget::{get_compute_budget, ComputeBudget};
+
+pub mod filter;
+use filter::Filter;
+
+pub mod index;
+use index::{index_keys, transaction_keys};
+
+pub mod pb;
+use pb::compute_budget::*;
+use pb::compute_budget::compute_budget_event::Event;
+
+#[substreams::handlers::map]
+fn compute_budget_events(params: String, block: Block) -> Result<ComputeBudgetBlockEvents, Error> {
+    Ok(_block_events(&block, &Params::parse(&params)))
+}
+
+#[substreams::handlers::map]
+fn compute_budget_index(block: Block) -> Result<Keys, Error> {
+    let transactions = parse_block(&block, &Params::default());
+    Ok(index_keys(&transactions))
+}
+
+/// Events matching the block filter query given as params, such as `fee_payer:<fee_payer>`, on their own index keys.
+#[substreams::handlers::map]
+fn filtered_compute_budget_events(query: String, block: Block) -> Result<ComputeBudgetBlockEvents, Error> {
+    let query = Query::parse(&query).map_err(Error::msg)?;
+    let mut block_events = _block_events(&block, &Params::default());
+    block_events.transactions.retain(|x| query.matches(&transaction_keys(x)));
+    Ok(block_events)
+}
+
+fn _block_events(block: &Block, params: &Params) -> ComputeBudgetBlockEvents {
+    let transactions = parse_block(block, params);
+    ComputeBudgetBlockEvents {
+        slot: block.slot,
+        block_time: block.block_time.as_ref().map(|x| x.timestamp).unwrap_or_default(),
+        block_hash: block.blockhash.clone(),
+        transactions,
+    }
+}
+
+/// Parses the Compute Budget instructions of the block transactions.
+///
+/// The compute budget of a failed transaction is still applied, and its priority fee charged, so
+/// with `include_failed` their instructions are emitted along with the `FailedInstruction` event.
+pub fn parse_block(block: &Block, params: &Params) -> Vec<ComputeBudgetTransactionEvents> {
+    let include_failed = params.get_bool("include_failed");
+    let filter = Filter::new(params);
+    let mut block_events: Vec<ComputeBudgetTransactionEvents> = Vec::new();
+    for (i, transaction) in block.transactions.iter().enumerate() {
+        let failed = transaction.meta.as_ref().map_or(false, |x| x.err.is_some());
+        if failed && !include_failed {
+            continue;
+        }
+        let envelope = get_transaction_envelope(transaction);
+        if !filter.matches(&envelope) {
+            continue;
+        }
+        let signature = utils::transaction::get_signature(&transaction);
+        let result = parse_transaction(transaction).map(|mut events| {
+            if failed {
+                events.extend(parse_failed_transaction(transaction));
+            }
+            events
+        });
+        match result {
+            Ok(events) if !events.is_empty() => {
+                let compute_budget = get_compute_budget(transaction);
+                block_events.push(ComputeBudgetTransactionEvents {
+                    signature,
+                    transaction_index: i as u32,
+                    envelope: Some(envelope),
+                    events,
+                    compute_unit_limit: compute_budget.compute_unit_limit,
+                    compute_unit_price: compute_budget.compute_unit_price,
+                    priority_fee: compute_budget.priority_fee,
+                });
+            },
+            Ok(_) => (),
+            Err(error) => substreams::log::println(format!("Failed to process transaction {}: {}", signature, error)),
+        }
+    }
+    block_events
+}
+
+pub fn parse_transaction(transaction: &ConfirmedTransaction) -> Result<Vec<ComputeBudgetEvent>, ParseError> {
+    transaction.meta.as_ref().ok_or(ParseError::MissingTransactionMeta)?;
+
+    let mut events: Vec<ComputeBudgetEvent> = Vec::new();
+
+    let context = get_context(transaction).map_err(|x| ParseError::TransactionContext(x.to_string()))?;
+    let instructions = get_structured_instructions(transaction).map_err(|x| ParseError::TransactionContext(x.to_string()))?;
+
+    let flattened = instructions.flattened();
+    for (i, instruction) in flattened.iter().enumerate() {
+        if instruction.program_id() != COMPUTE_BUDGET_PROGRAM_ID {
+            continue;
+        }
+
+        let call_path = get_call_path(instruction, &flattened);
+        match parse_instruction(instruction, &context) {
+            Ok(event) => {
+                events.push(ComputeBudgetEvent {
+                    instruction_index: i as u32,
+                    stack_depth: stack_depth(instruction),
+                    top_instruction_program_id: call_path.top_instruction_program_id,
+                    parent_instruction_program_id: call_path.parent_instruction_program_id,
+                    top_instruction_index: call_path.top_instruction_index,
+                    parent_instruction_index: call_path.parent_instruction_index,
+                    event: Some(event),
+                });
+            },
+            Err(error) => substreams::log::println(format!("Failed to process instruction of transaction {}: {}", &context.signature, error)),
+        }
+    }
+
+    Ok(events)
+}
+
+/// Parses a failed transaction into a `FailedInstruction` event, if it invoked the Compute Budget program.
+pub fn parse_failed_transaction(transaction: &ConfirmedTransaction) -> Vec<ComputeBudgetEvent> {
+    if !is_program_invoked(transaction, &COMPUTE_BUDGET_PROGRAM_ID.to_string()) {
+        return Vec::new();
+    }
+    // The Compute Budget program doesn't define custom errors.
+    get_failed_instruction(transaction, |_, _| None)
+        .map(|x| vec![ComputeBudgetEvent {
+            instruction_index: flattened_index(transaction, x.instruction_index),
+            stack_depth: 0,
+            top_instruction_program_id: None,
+            parent_instruction_program_id: None,
+            top_instruction_index: None,
+            parent_instruction_index: None,
+            event: Some(Event::FailedInstruction(x)),
+        }])
+        .unwrap_or_default()
+}
+
+pub fn parse_instruction<'a>(
+    instruction: &StructuredInstruction<'a>,
+    _context: &TransactionContext
+) -> Result<Event, ParseError> {
+    if instruction.program_id() != COMPUTE_BUDGET_PROGRAM_ID {
+        return Err(ParseError::UnexpectedProgram(instruction.program_id().to_string()));
+    }
+    let unpacked = match ComputeBudgetInstruction::unpack(instruction.data()) {
+        Ok(unpacked) => unpacked,
+        Err(_) => return Ok(Event::UnknownInstruction(get_unknown_instruction(instruction, 1))),
+    };
+    match unpacked {
+        ComputeBudgetInstruction::RequestUnits { units, additional_fee } => {
+            Ok(Event::RequestUnits(RequestUnitsEvent { units, additional_fee }))
+        },
+        ComputeBudgetInstruction::RequestHeapFrame(bytes) => {
+            Ok(Event::RequestHeapFrame(RequestHeapFrameEvent { bytes }))
+        },
+        ComputeBudgetInstruction::SetComputeUnitLimit(units) => {
+            Ok(Event::SetComputeUnitLimit(SetComputeUnitLimitEvent { units }))
+        },
+        ComputeBudgetInstruction::SetComputeUnitPrice(micro_lamports) => {
+            Ok(Event::SetComputeUnitPrice(SetComputeUnitPriceEvent { micro_lamports }))
+        },
+        ComputeBudgetInstruction::SetLoadedAccountsDataSizeLimit(bytes) => {
+            Ok(Event::SetLoadedAccountsDataSizeLimit(SetLoadedAccountsDataSizeLimitEvent { bytes }))
+        },
+    }
+}
@@ -0,0 +1,108 @@
+// @generated
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ComputeBudgetBlockEvents {
+    #[prost(uint64, tag="1")]
+    pub slot: u64,
+    #[prost(message, repeated, tag="2")]
+    pub transactions: ::prost::alloc::vec::Vec<ComputeBudgetTransactionEvents>,
+    #[prost(int64, tag="3")]
+    pub block_time: i64,
+    #[prost(string, tag="4")]
+    pub block_hash: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ComputeBudgetTransactionEvents {
+    #[prost(string, tag="1")]
+    pub signature: ::prost::alloc::string::String,
+    #[prost(uint32, tag="2")]
+    pub transaction_index: u32,
+    #[prost(message, repeated, tag="3")]
+    pub events: ::prost::alloc::vec::Vec<ComputeBudgetEvent>,
+    #[prost(message, optional, tag="4")]
+    pub envelope: ::core::option::Option<::solana_substreams_common::pb::common::TransactionEnvelope>,
+    /// Compute unit limit requested by the transaction, or the default limit of its instructions.
+    #[prost(uint32, tag="5")]
+    pub compute_unit_limit: u32,
+    /// Compute unit price in micro-lamports.
+    #[prost(uint64, tag="6")]
+    pub compute_unit_price: u64,
+    /// Priority fee in lamports, derived from the compute unit limit and price.
+    #[prost(uint64, tag="7")]
+    pub priority_fee: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ComputeBudgetEvent {
+    #[prost(uint32, tag="1")]
+    pub instruction_index: u32,
+    /// Depth in the invocation stack, 0 for top level instructions.
+    #[prost(uint32, tag="8")]
+    pub stack_depth: u32,
+    #[prost(string, optional, tag="9")]
+    pub top_instruction_program_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag="10")]
+    pub parent_instruction_program_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(uint32, optional, tag="11")]
+    pub top_instruction_index: ::core::option::Option<u32>,
+    #[prost(uint32, optional, tag="12")]
+    pub parent_instruction_index: ::core::option::Option<u32>,
+    #[prost(oneof="compute_budget_event::Event", tags="2, 3, 4, 5, 6, 7, 13")]
+    pub event: ::core::option::Option<compute_budget_event::Event>,
+}
+/// Nested message and enum types in `ComputeBudgetEvent`.
+pub mod compute_budget_event {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Event {
+        #[prost(message, tag="2")]
+        RequestUnits(super::RequestUnitsEvent),
+        #[prost(message, tag="3")]
+        RequestHeapFrame(super::RequestHeapFrameEvent),
+        #[prost(message, tag="4")]
+        SetComputeUnitLimit(super::SetComputeUnitLimitEvent),
+        #[prost(message, tag="5")]
+        SetComputeUnitPrice(super::SetComputeUnitPriceEvent),
+        #[prost(message, tag="6")]
+        SetLoadedAccountsDataSizeLimit(super::SetLoadedAccountsDataSizeLimitEvent),
+        #[prost(message, tag="7")]
+        FailedInstruction(::solana_substreams_common::pb::common::FailedInstruction),
+        #[prost(message, tag="13")]
+        UnknownInstruction(::solana_substreams_common::pb::common::UnknownInstruction),
+    }
+}
+/// Deprecated instruction, superseded by SetComputeUnitLimit and SetComputeUnitPrice.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RequestUnitsEvent {
+    #[prost(uint32, tag="1")]
+    pub units: u32,
+    #[prost(uint32, tag="2")]
+    pub additional_fee: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RequestHeapFrameEvent {
+    #[prost(uint32, tag="1")]
+    pub bytes: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetComputeUnitLimitEvent {
+    #[prost(uint32, tag="1")]
+    pub units: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetComputeUnitPriceEvent {
+    #[prost(uint64, tag="1")]
+    pub micro_lamports: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetLoadedAccountsDataSizeLimitEvent {
+    #[prost(uint32, tag="1")]
+    pub bytes: u32,
+}
+// @@protoc_insertion_point(module)
@@ -0,0 +1,6 @@
+// @generated
+// @@protoc_insertion_point(attribute:compute_budget)
+pub mod compute_budget {
+    include!("compute_budget.rs");
+    // @@protoc_insertion_point(compute_budget)
+}
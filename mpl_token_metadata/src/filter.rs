@@ -0,0 +1,68 @@
+use std::collections::HashSet;
+
+use solana_substreams_common::params::{matches, Params};
+
+use crate::pb::mpl_token_metadata::MplTokenMetadataEvent;
+
+/// Filters events by the `mints` and `metadata_update_authorities` module params.
+///
+/// Only events of instructions taking a mint, or the update authority of the metadata, can match
+/// the respective filter when set. Unset filters match every event.
+#[derive(Clone, Debug, Default)]
+pub struct Filter {
+    mints: Option<HashSet<String>>,
+    update_authorities: Option<HashSet<String>>,
+}
+
+impl Filter {
+    pub fn new(params: &Params) -> Self {
+        Self {
+            mints: params.get_set("mints"),
+            update_authorities: params.get_set("metadata_update_authorities"),
+        }
+    }
+
+    pub fn matches(&self, event: &MplTokenMetadataEvent) -> bool {
+        matches(&self.mints, event_mint(event))
+            && matches(&self.update_authorities, event_update_authority(event))
+    }
+}
+
+pub fn event_mint(event: &MplTokenMetadataEvent) -> Option<&str> {
+    event.mint.as_deref()
+}
+
+pub fn event_update_authority(event: &MplTokenMetadataEvent) -> Option<&str> {
+    event.update_authority.as_deref()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pb::mpl_token_metadata::mpl_token_metadata_event::Event;
+
+    #[test]
+    fn test_matches_update_authority() {
+        let event = MplTokenMetadataEvent {
+            mint: Some("mint".to_string()),
+            update_authority: Some("authority".to_string()),
+            event: Some(Event::UpdateMetadataAccountV2(Default::default())),
+            ..Default::default()
+        };
+        assert!(Filter::new(&Params::parse("metadata_update_authorities=authority")).matches(&event));
+        assert!(Filter::new(&Params::parse("metadata_update_authorities=authority&mints=mint")).matches(&event));
+        assert!(!Filter::new(&Params::parse("metadata_update_authorities=other")).matches(&event));
+        assert!(Filter::new(&Params::default()).matches(&event));
+    }
+
+    #[test]
+    fn test_unset_update_authority_is_dropped() {
+        let event = MplTokenMetadataEvent {
+            mint: Some("mint".to_string()),
+            event: Some(Event::Burn(Default::default())),
+            ..Default::default()
+        };
+        assert!(!Filter::new(&Params::parse("metadata_update_authorities=authority")).matches(&event));
+        assert!(Filter::new(&Params::parse("mints=mint")).matches(&event));
+    }
+}
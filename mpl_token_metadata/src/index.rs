@@ -0,0 +1,22 @@
+use std::collections::BTreeSet;
+
+use substreams::pb::sf::substreams::index::v1::Keys;
+
+use crate::mpl_token_metadata::constants::MPL_TOKEN_METADATA_PROGRAM_ID;
+use crate::filter::{event_mint, event_update_authority};
+use crate::pb::mpl_token_metadata::{MplTokenMetadataEvent, MplTokenMetadataTransactionEvents};
+
+/// Block index keys of the events: `program:`, `mint:` and `update_authority:`.
+pub fn index_keys(transactions: &[MplTokenMetadataTransactionEvents]) -> Keys {
+    let keys: BTreeSet<String> = transactions.iter().flat_map(|x| x.events.iter()).flat_map(event_keys).collect();
+    Keys { keys: keys.into_iter().collect() }
+}
+
+/// Index keys of a single event, which `filtered_mpl_token_metadata_events` matches its query against.
+pub fn event_keys(event: &MplTokenMetadataEvent) -> BTreeSet<String> {
+    let mut keys = BTreeSet::new();
+    keys.insert(format!("program:{}", MPL_TOKEN_METADATA_PROGRAM_ID));
+    keys.extend(event_mint(event).map(|x| format!("mint:{}", x)));
+    keys.extend(event_update_authority(event).map(|x| format!("update_authority:{}", x)));
+    keys
+}
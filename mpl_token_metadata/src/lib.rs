@@ -1,7 +1,9 @@
 #![allow(deprecated)]
 
 use borsh::BorshDeserialize;
+use num_traits::FromPrimitive;
 use substreams::errors::Error;
+use substreams::pb::sf::substreams::index::v1::Keys;
 
 use substreams_solana::pb::sf::solana::r#type::v1::ConfirmedTransaction;
 use substreams_solana::pb::sf::solana::r#type::v1::Block;
@@ -10,61 +12,175 @@ use substreams_solana_utils as utils;
 use utils::instruction::{get_structured_instructions, StructuredInstruction, StructuredInstructions};
 use utils::transaction::{get_context, TransactionContext};
 
+use solana_substreams_common::{get_failed_instruction, get_transaction_envelope, is_program_invoked};
+use solana_substreams_common::params::Params;
+use solana_substreams_common::query::Query;
+use solana_substreams_common::instruction::{flattened_index, get_call_path, get_unknown_instruction, stack_depth};
+
 pub mod mpl_token_metadata;
 use mpl_token_metadata::constants::MPL_TOKEN_METADATA_PROGRAM_ID;
 use mpl_token_metadata::instruction::MetadataInstruction;
+use mpl_token_metadata::error::MetadataError;
+
+pub mod error;
+use error::ParseError;
+
+pub mod filter;
+use filter::Filter;
+
+pub mod index;
+use index::{event_keys, index_keys};
 
 pub mod pb;
 use pb::mpl_token_metadata::*;
 use pb::mpl_token_metadata::mpl_token_metadata_event::Event;
 
-fn mpl_token_metadata_events(block: Block) -> Result<MplTokenMetadataBlockEvents, Error> {
-    let transactions = parse_block(&block);
-    Ok(MplTokenMetadataBlockEvents { transactions })
+#[substreams::handlers::map]
+fn mpl_token_metadata_events(params: String, block: Block) -> Result<MplTokenMetadataBlockEvents, Error> {
+    Ok(_block_events(&block, &Params::parse(&params)))
+}
+
+#[substreams::handlers::map]
+fn mpl_token_metadata_index(block: Block) -> Result<Keys, Error> {
+    let transactions = parse_block(&block, &Params::default());
+    Ok(index_keys(&transactions))
 }
 
-pub fn parse_block(block: &Block) -> Vec<MplTokenMetadataTransactionEvents> {
+/// Events matching the block filter query given as params, such as `mint:<mint>`, on their own index keys.
+#[substreams::handlers::map]
+fn filtered_mpl_token_metadata_events(query: String, block: Block) -> Result<MplTokenMetadataBlockEvents, Error> {
+    let query = Query::parse(&query).map_err(Error::msg)?;
+    let mut block_events = _block_events(&block, &Params::default());
+    for transaction in block_events.transactions.iter_mut() {
+        transaction.events.retain(|x| query.matches(&event_keys(x)));
+    }
+    block_events.transactions.retain(|x| !x.events.is_empty());
+    Ok(block_events)
+}
+
+fn _block_events(block: &Block, params: &Params) -> MplTokenMetadataBlockEvents {
+    let transactions = parse_block(block, params);
+    MplTokenMetadataBlockEvents {
+        slot: block.slot,
+        block_time: block.block_time.as_ref().map(|x| x.timestamp).unwrap_or_default(),
+        block_hash: block.blockhash.clone(),
+        transactions,
+    }
+}
+
+pub fn parse_block(block: &Block, params: &Params) -> Vec<MplTokenMetadataTransactionEvents> {
+    let include_failed = params.get_bool("include_failed");
+    let filter = Filter::new(params);
     let mut block_events: Vec<MplTokenMetadataTransactionEvents> = Vec::new();
 
-    for transaction in block.transactions() {
-        if let Ok(events) = parse_transaction(transaction) {
-            if !events.is_empty() {
+    for (i, transaction) in block.transactions.iter().enumerate() {
+        let failed = transaction.meta.as_ref().map_or(false, |x| x.err.is_some());
+        if failed && !include_failed {
+            continue;
+        }
+        let signature = utils::transaction::get_signature(&transaction);
+        let result = if failed {
+            Ok(parse_failed_transaction(transaction, &filter))
+        } else {
+            parse_transaction(transaction, &filter)
+        };
+        match result {
+            Ok(events) if !events.is_empty() => {
                 block_events.push(MplTokenMetadataTransactionEvents {
-                    signature: utils::transaction::get_signature(&transaction),
+                    signature,
+                    transaction_index: i as u32,
+                    envelope: Some(get_transaction_envelope(transaction)),
                     events,
                 });
-            }
+            },
+            Ok(_) => (),
+            Err(error) => substreams::log::println(format!("Failed to process transaction {}: {}", signature, error)),
         }
     }
     block_events
 }
 
-pub fn parse_transaction(transaction: &ConfirmedTransaction) -> Result<Vec<MplTokenMetadataEvent>, String> {
+pub fn parse_transaction(transaction: &ConfirmedTransaction, filter: &Filter) -> Result<Vec<MplTokenMetadataEvent>, ParseError> {
+    if let Some(_) = transaction.meta.as_ref().ok_or(ParseError::MissingTransactionMeta)?.err {
+        return Ok(Vec::new())
+    }
+
     let mut events: Vec<MplTokenMetadataEvent> = Vec::new();
 
-    let context = get_context(transaction).unwrap();
-    let instructions = get_structured_instructions(transaction).unwrap();
+    let context = get_context(transaction).map_err(|x| ParseError::TransactionContext(x.to_string()))?;
+    let instructions = get_structured_instructions(transaction).map_err(|x| ParseError::TransactionContext(x.to_string()))?;
 
-    for instruction in instructions.flattened().iter() {
+    let flattened = instructions.flattened();
+    for (i, instruction) in flattened.iter().enumerate() {
         if instruction.program_id() != MPL_TOKEN_METADATA_PROGRAM_ID {
             continue;
         }
+        let call_path = get_call_path(instruction, &flattened);
         match parse_instruction(instruction, &context) {
-            Ok(event) => events.push(MplTokenMetadataEvent { event } ),
-            _ => (),
+            Ok(event) => {
+                let event = MplTokenMetadataEvent {
+                    instruction_index: i as u32,
+                    stack_depth: stack_depth(instruction),
+                    top_instruction_program_id: call_path.top_instruction_program_id,
+                    parent_instruction_program_id: call_path.parent_instruction_program_id,
+                    top_instruction_index: call_path.top_instruction_index,
+                    parent_instruction_index: call_path.parent_instruction_index,
+                    mint: event.as_ref().and_then(|x| instruction_mint(instruction, x)),
+                    update_authority: event.as_ref().and_then(|x| instruction_update_authority(instruction, x)),
+                    event,
+                };
+                if filter.matches(&event) {
+                    events.push(event);
+                }
+            },
+            Err(error) => substreams::log::println(format!("Failed to process instruction of transaction {}: {}", &context.signature, error)),
         }
     }
     Ok(events)
 }
 
+/// Parses a failed transaction into a `FailedInstruction` event, if it invoked the Token Metadata program.
+pub fn parse_failed_transaction(transaction: &ConfirmedTransaction, filter: &Filter) -> Vec<MplTokenMetadataEvent> {
+    if !is_program_invoked(transaction, &MPL_TOKEN_METADATA_PROGRAM_ID.to_string()) {
+        return Vec::new();
+    }
+    get_failed_instruction(transaction, custom_error_name)
+        .map(|x| MplTokenMetadataEvent {
+            instruction_index: flattened_index(transaction, x.instruction_index),
+            stack_depth: 0,
+            top_instruction_program_id: None,
+            parent_instruction_program_id: None,
+            top_instruction_index: None,
+            parent_instruction_index: None,
+            mint: None,
+            update_authority: None,
+            event: Some(Event::FailedInstruction(x)),
+        })
+        .filter(|x| filter.matches(x))
+        .into_iter()
+        .collect()
+}
+
+/// Name of a custom error code raised by the Token Metadata program.
+pub fn custom_error_name(program_id: &str, code: u32) -> Option<String> {
+    if program_id == MPL_TOKEN_METADATA_PROGRAM_ID.to_string() {
+        MetadataError::from_u32(code).map(|x| format!("{:?}", x))
+    } else {
+        None
+    }
+}
+
 pub fn parse_instruction(
     instruction: &StructuredInstruction,
     context: &TransactionContext
-) -> Result<Option<Event>, String> {
+) -> Result<Option<Event>, ParseError> {
     if instruction.program_id() != MPL_TOKEN_METADATA_PROGRAM_ID {
-        return Err("Not a Metaplex Token Metadata instruction.".into());
+        return Err(ParseError::UnexpectedProgram(instruction.program_id().to_string()));
     }
-    let unpacked = MetadataInstruction::try_from_slice(instruction.data()).map_err(|_| "Failed to parse MetadataInstruction.")?;
+    let unpacked = match MetadataInstruction::try_from_slice(instruction.data()) {
+        Ok(unpacked) => unpacked,
+        Err(_) => return Ok(Some(Event::UnknownInstruction(get_unknown_instruction(instruction, 1)))),
+    };
     match unpacked {
         MetadataInstruction::ApproveCollectionAuthority => {
             Ok(Some(Event::ApproveCollectionAuthority(ApproveCollectionAuthorityEvent {})))
@@ -246,14 +362,95 @@ pub fn parse_instruction(
     }
 }
 
+/// Mint account of the instruction, for instructions acting on the metadata of a mint.
+///
+/// Instructions that only take the mint of a collection, such as `Verify`, act on the metadata
+/// of an item whose mint they don't take, and have none.
+pub fn instruction_mint(instruction: &StructuredInstruction, event: &Event) -> Option<String> {
+    let index = match event {
+        Event::CreateMetadataAccount(_) |
+        Event::CreateMetadataAccountV2(_) |
+        Event::CreateMetadataAccountV3(_) |
+        Event::CreateMasterEdition(_) |
+        Event::CreateMasterEditionV3(_) |
+        Event::DeprecatedCreateMasterEdition(_) => 1,
+        Event::Utilize(_) |
+        Event::BurnNft(_) |
+        Event::BurnEditionNft(_) |
+        Event::SetCollectionSize(_) |
+        Event::BubblegumSetCollectionSize(_) |
+        Event::SetTokenStandard(_) |
+        Event::CreateEscrowAccount(_) |
+        Event::CloseEscrowAccount(_) |
+        Event::Create(_) |
+        Event::Print(_) |
+        Event::Resize(_) |
+        Event::CloseAccounts(_) => 2,
+        Event::DeprecatedMintNewEditionFromMasterEditionViaPrintingToken(_) |
+        Event::MintNewEditionFromMasterEditionViaToken(_) |
+        Event::MintNewEditionFromMasterEditionViaVaultProxy(_) |
+        Event::FreezeDelegatedAccount(_) |
+        Event::ThawDelegatedAccount(_) |
+        Event::Lock(_) |
+        Event::Unlock(_) |
+        Event::Update(_) |
+        Event::Use(_) => 3,
+        Event::RevokeUseAuthority(_) |
+        Event::RevokeCollectionAuthority(_) |
+        Event::Burn(_) |
+        Event::Migrate(_) |
+        Event::Transfer(_) => 4,
+        Event::ApproveCollectionAuthority(_) |
+        Event::Mint(_) |
+        Event::Delegate(_) |
+        Event::Revoke(_) => 5,
+        Event::ApproveUseAuthority(_) |
+        Event::TransferOutOfEscrow(_) => 6,
+        _ => return None,
+    };
+    instruction.accounts().get(index).map(|x| x.to_string())
+}
+
+/// Update authority account of the instruction, for instructions taking the update authority of
+/// the metadata they act on. `Update`, `Verify` and `Unverify` take a single authority, which is
+/// the update authority unless it signs as a delegate or creator.
+pub fn instruction_update_authority(instruction: &StructuredInstruction, event: &Event) -> Option<String> {
+    update_authority_index(event).and_then(|index| instruction.accounts().get(index).map(|x| x.to_string()))
+}
+
+fn update_authority_index(event: &Event) -> Option<usize> {
+    match event {
+        Event::Update(_) |
+        Event::Verify(_) |
+        Event::Unverify(_) => Some(0),
+        Event::UpdateMetadataAccount(_) |
+        Event::UpdateMetadataAccountV2(_) |
+        Event::SetTokenStandard(_) => Some(1),
+        Event::CreateMasterEdition(_) |
+        Event::CreateMasterEditionV3(_) |
+        Event::ApproveCollectionAuthority(_) |
+        Event::DeprecatedCreateReservationList(_) |
+        Event::DeprecatedMintPrintingTokens(_) => Some(2),
+        Event::SetAndVerifyCollection(_) |
+        Event::SetAndVerifySizedCollectionItem(_) => Some(3),
+        Event::CreateMetadataAccount(_) |
+        Event::CreateMetadataAccountV2(_) |
+        Event::CreateMetadataAccountV3(_) |
+        Event::DeprecatedCreateMasterEdition(_) => Some(4),
+        Event::Create(_) => Some(5),
+        Event::Print(_) => Some(13),
+        _ => None,
+    }
+}
+
 fn _parse_create_metadata_account_v3_instruction<'a>(
     instruction: &StructuredInstruction<'a>,
     _context: &TransactionContext,
     create_metadata_account_v3: mpl_token_metadata::instruction::CreateMetadataAccountArgsV3,
-) -> Result<CreateMetadataAccountV3Event, String> {
-    let metadata = instruction.accounts()[0].to_string();
-    let mint = instruction.accounts()[1].to_string();
-    let update_authority = instruction.accounts()[4].to_string();
+) -> Result<CreateMetadataAccountV3Event, ParseError> {
+    let metadata = _account(instruction, 0)?;
+    let mint = _account(instruction, 1)?;
+    let update_authority = _account(instruction, 4)?;
     let data = Some(create_metadata_account_v3.data.into());
     let is_mutable = create_metadata_account_v3.is_mutable;
     let collection_details = create_metadata_account_v3.collection_details.map(|x| x.into());
@@ -268,11 +465,15 @@ fn _parse_create_metadata_account_v3_instruction<'a>(
     })
 }
 
+fn _account(instruction: &StructuredInstruction, index: usize) -> Result<String, ParseError> {
+    instruction.accounts().get(index).map(|x| x.to_string()).ok_or(ParseError::MissingAccount(index))
+}
+
 // fn _parse_create_instruction<'a>(
 //     instruction: &StructuredInstruction<'a>,
 //     _context: &TransactionContext,
 //     create: mpl_token_metadata::instruction::CreateArgs,
-// ) -> Result<CreateEvent, String> {
+// ) -> Result<CreateEvent, ParseError> {
 //     unimplemented!()
 // }
 
@@ -280,11 +481,11 @@ fn _parse_create_metadata_account_v3_instruction<'a>(
 //     instruction: &StructuredInstruction,
 //     context: &TransactionContext,
 //     update: mpl_token_metadata::instruction::UpdateArgs,
-// ) -> Result<UpdateEvent, String> {
-//     let metadata = instruction.accounts()[0].to_string();
+// ) -> Result<UpdateEvent, ParseError> {
+//     let metadata = _account(instruction, 0)?;
 //     let delta = (instruction.accounts()[6] == SYSTEM_PROGRAM_ID) as usize;
-//     let mint = instruction.accounts()[1 + delta].to_string();
-//     let authority = instruction.accounts()[2 + delta].to_string();
+//     let mint = _account(instruction, 1 + delta)?;
+//     let authority = _account(instruction, 2 + delta)?;
 
 //     let update_args = match update {
 //         mpl_token_metadata::instruction::UpdateArgs::V1 { new_update_authority, data, primary_sale_happened, is_mutable, collection, collection_details, uses, rule_set, authorization_data } => {
@@ -358,7 +559,7 @@ fn _parse_create_metadata_account_v3_instruction<'a>(
 //     instruction: &StructuredInstruction,
 //     context: &TransactionContext,
 //     update_metadata_account_v2: mpl_token_metadata::instruction::UpdateMetadataAccountArgsV2,
-// ) -> Result<UpdateMetadataAccountV2Event, String> {
+// ) -> Result<UpdateMetadataAccountV2Event, ParseError> {
 //     unimplemented!()
 // }
 
@@ -441,3 +642,19 @@ impl From<mpl_token_metadata::state::CollectionDetails> for CollectionDetails {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_authority_index() {
+        assert_eq!(update_authority_index(&Event::CreateMetadataAccount(Default::default())), Some(4));
+        assert_eq!(update_authority_index(&Event::CreateMetadataAccountV2(Default::default())), Some(4));
+        assert_eq!(update_authority_index(&Event::UpdateMetadataAccountV2(Default::default())), Some(1));
+        assert_eq!(update_authority_index(&Event::CreateMasterEditionV3(Default::default())), Some(2));
+        assert_eq!(update_authority_index(&Event::Create(Default::default())), Some(5));
+        assert_eq!(update_authority_index(&Event::Verify(Default::default())), Some(0));
+        assert_eq!(update_authority_index(&Event::Burn(Default::default())), None);
+    }
+}
@@ -1,4 +1 @@
-use substreams_solana_utils::pubkey::Pubkey;
-use substreams_solana::b58;
-
-pub const MPL_TOKEN_METADATA_PROGRAM_ID: Pubkey = Pubkey(b58!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"));
+pub use solana_substreams_common::programs::MPL_TOKEN_METADATA_PROGRAM_ID;
@@ -4,6 +4,12 @@
 pub struct MplTokenMetadataBlockEvents {
     #[prost(message, repeated, tag="1")]
     pub transactions: ::prost::alloc::vec::Vec<MplTokenMetadataTransactionEvents>,
+    #[prost(uint64, tag="2")]
+    pub slot: u64,
+    #[prost(int64, tag="3")]
+    pub block_time: i64,
+    #[prost(string, tag="4")]
+    pub block_hash: ::prost::alloc::string::String,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -12,11 +18,35 @@ pub struct MplTokenMetadataTransactionEvents {
     pub signature: ::prost::alloc::string::String,
     #[prost(message, repeated, tag="2")]
     pub events: ::prost::alloc::vec::Vec<MplTokenMetadataEvent>,
+    #[prost(uint32, tag="3")]
+    pub transaction_index: u32,
+    #[prost(message, optional, tag="4")]
+    pub envelope: ::core::option::Option<::solana_substreams_common::pb::common::TransactionEnvelope>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct MplTokenMetadataEvent {
-    #[prost(oneof="mpl_token_metadata_event::Event", tags="1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58")]
+    #[prost(uint32, tag="60")]
+    pub instruction_index: u32,
+    /// Depth in the invocation stack, 0 for top level instructions.
+    #[prost(uint32, tag="61")]
+    pub stack_depth: u32,
+    #[prost(string, optional, tag="62")]
+    pub top_instruction_program_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag="63")]
+    pub parent_instruction_program_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(uint32, optional, tag="64")]
+    pub top_instruction_index: ::core::option::Option<u32>,
+    #[prost(uint32, optional, tag="65")]
+    pub parent_instruction_index: ::core::option::Option<u32>,
+    /// Mint of the asset the instruction acts on, for instructions taking one.
+    #[prost(string, optional, tag="67")]
+    pub mint: ::core::option::Option<::prost::alloc::string::String>,
+    /// Update authority of the metadata the instruction acts on, for instructions taking it, or the
+    /// authority signing `Update`, `Verify` and `Unverify`.
+    #[prost(string, optional, tag="68")]
+    pub update_authority: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(oneof="mpl_token_metadata_event::Event", tags="1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 66")]
     pub event: ::core::option::Option<mpl_token_metadata_event::Event>,
 }
 /// Nested message and enum types in `MplTokenMetadataEvent`.
@@ -140,6 +170,10 @@ pub mod mpl_token_metadata_event {
         Resize(super::ResizeEvent),
         #[prost(message, tag="58")]
         CloseAccounts(super::CloseAccountsEvent),
+        #[prost(message, tag="59")]
+        FailedInstruction(::solana_substreams_common::pb::common::FailedInstruction),
+        #[prost(message, tag="66")]
+        UnknownInstruction(::solana_substreams_common::pb::common::UnknownInstruction),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
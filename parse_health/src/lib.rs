@@ -0,0 +1,165 @@
+use std::collections::BTreeMap;
+
+use substreams::errors::Error;
+use substreams_solana::pb::sf::solana::r#type::v1::{Block, ConfirmedTransaction};
+
+use substreams_solana_utils as utils;
+use utils::transaction::get_context;
+use utils::instruction::{get_structured_instructions, StructuredInstructions};
+
+use solana_substreams_common::get_transaction_envelope;
+use solana_substreams_common::params::Params;
+
+pub mod outcome;
+use outcome::{parse_outcome, Outcome};
+
+pub mod pb;
+use pb::parse_health::*;
+
+/// Number of errors kept per program when the `max_errors` param is unset.
+pub const DEFAULT_MAX_ERRORS: usize = 10;
+
+/// Decoding coverage of each program of the workspace in the block, to monitor breaking program
+/// changes. Failed transactions are skipped, as their logs can't be decoded.
+#[substreams::handlers::map]
+fn parse_health(params: String, block: Block) -> Result<ParseHealth, Error> {
+    let params = Params::parse(&params);
+    let max_errors = params.get("max_errors")
+        .and_then(|x| x.first())
+        .and_then(|x| x.parse().ok())
+        .unwrap_or(DEFAULT_MAX_ERRORS);
+
+    let mut programs: BTreeMap<String, ProgramParseHealth> = BTreeMap::new();
+    for (i, transaction) in block.transactions.iter().enumerate() {
+        if transaction.meta.as_ref().map_or(true, |x| x.err.is_some()) {
+            continue;
+        }
+        if let Err(error) = add_transaction(&mut programs, transaction, i as u32, max_errors) {
+            let signature = utils::transaction::get_signature(&transaction);
+            substreams::log::println(format!("Failed to process transaction {}: {}", signature, error));
+        }
+    }
+
+    Ok(ParseHealth {
+        slot: block.slot,
+        block_time: block.block_time.as_ref().map(|x| x.timestamp).unwrap_or_default(),
+        block_hash: block.blockhash.clone(),
+        programs: programs.into_values().collect(),
+    })
+}
+
+/// Counts the outcomes of the instructions of the transaction, by program id.
+pub fn add_transaction(
+    programs: &mut BTreeMap<String, ProgramParseHealth>,
+    transaction: &ConfirmedTransaction,
+    transaction_index: u32,
+    max_errors: usize,
+) -> Result<(), String> {
+    let mut context = get_context(transaction).map_err(|x| x.to_string())?;
+    let instructions = get_structured_instructions(transaction).map_err(|x| x.to_string())?;
+    let signers = get_transaction_envelope(transaction).signers;
+
+    for (i, instruction) in instructions.flattened().iter().enumerate() {
+        context.update_balance(&instruction.instruction);
+        let (substream, outcome) = match parse_outcome(instruction, &context, &signers) {
+            Some(parsed) => parsed,
+            None => continue,
+        };
+
+        let program_id = instruction.program_id().to_string();
+        let health = programs.entry(program_id.clone()).or_insert_with(|| ProgramParseHealth {
+            program_id,
+            substream: substream.to_string(),
+            ..Default::default()
+        });
+        health.instructions += 1;
+        let error = match outcome {
+            Outcome::Decoded => {
+                health.decoded += 1;
+                continue;
+            },
+            Outcome::Unknown => {
+                health.unknown += 1;
+                continue;
+            },
+            Outcome::Failed(error) => {
+                health.failed += 1;
+                error
+            },
+            Outcome::MissingLogs(error) => {
+                health.missing_logs += 1;
+                error
+            },
+        };
+        if health.errors.len() < max_errors {
+            health.errors.push(InstructionParseError {
+                signature: context.signature.clone(),
+                transaction_index,
+                instruction_index: i as u32,
+                error,
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use substreams_solana::pb::sf::solana::r#type::v1::{CompiledInstruction, Message, MessageHeader, Transaction, TransactionStatusMeta};
+
+    use associated_token_account_substream::instruction::ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID;
+    use compute_budget_substream::instruction::COMPUTE_BUDGET_PROGRAM_ID;
+
+    use super::*;
+
+    fn instruction(program_id_index: u32, data: Vec<u8>) -> CompiledInstruction {
+        CompiledInstruction { program_id_index, accounts: vec![], data }
+    }
+
+    #[test]
+    fn test_add_transaction() {
+        let account_keys = vec![vec![1; 32], COMPUTE_BUDGET_PROGRAM_ID.0.to_vec(), ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID.0.to_vec(), vec![2; 32]];
+        let instructions = vec![
+            instruction(1, [&[2u8][..], &200_000u32.to_le_bytes()].concat()),
+            instruction(1, vec![9]),
+            // CreateIdempotent without its accounts.
+            instruction(2, vec![1]),
+            instruction(2, vec![1]),
+            instruction(2, vec![1]),
+            // A program no crate decodes.
+            instruction(3, vec![0]),
+        ];
+        let transaction = ConfirmedTransaction {
+            transaction: Some(Transaction {
+                signatures: vec![vec![0; 64]],
+                message: Some(Message {
+                    header: Some(MessageHeader { num_required_signatures: 1, ..Default::default() }),
+                    account_keys,
+                    instructions,
+                    ..Default::default()
+                }),
+            }),
+            meta: Some(TransactionStatusMeta {
+                pre_balances: vec![0; 4],
+                post_balances: vec![0; 4],
+                ..Default::default()
+            }),
+        };
+
+        let mut programs = BTreeMap::new();
+        add_transaction(&mut programs, &transaction, 7, 2).unwrap();
+        assert_eq!(programs.len(), 2);
+
+        let compute_budget = &programs[&COMPUTE_BUDGET_PROGRAM_ID.to_string()];
+        assert_eq!(compute_budget.substream, "compute_budget");
+        assert_eq!((compute_budget.instructions, compute_budget.decoded, compute_budget.unknown), (2, 1, 1));
+        assert!(compute_budget.errors.is_empty());
+
+        // Every failure is counted, but only the first `max_errors` are kept.
+        let associated_token_account = &programs[&ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID.to_string()];
+        assert_eq!((associated_token_account.instructions, associated_token_account.failed), (3, 3));
+        assert_eq!(associated_token_account.errors.len(), 2);
+        assert_eq!(associated_token_account.errors[0].transaction_index, 7);
+        assert_eq!(associated_token_account.errors.iter().map(|x| x.instruction_index).collect::<Vec<_>>(), vec![2, 3]);
+    }
+}
@@ -0,0 +1,152 @@
+use std::fmt::Display;
+
+use substreams_solana_utils as utils;
+use utils::instruction::StructuredInstruction;
+use utils::system_program::SYSTEM_PROGRAM_ID;
+use utils::transaction::TransactionContext;
+
+use address_lookup_table_substream::instruction::ADDRESS_LOOKUP_TABLE_PROGRAM_ID;
+use address_lookup_table_substream::pb::address_lookup_table::address_lookup_table_event::Event as AddressLookupTableEvent;
+use associated_token_account_substream::instruction::ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID;
+use associated_token_account_substream::pb::associated_token_account::associated_token_account_event::Event as AssociatedTokenAccountEvent;
+use bpf_loader_upgradeable_substream::instruction::BPF_LOADER_UPGRADEABLE_PROGRAM_ID;
+use bpf_loader_upgradeable_substream::pb::bpf_loader_upgradeable::bpf_loader_upgradeable_event::Event as BpfLoaderUpgradeableEvent;
+use compute_budget_substream::instruction::COMPUTE_BUDGET_PROGRAM_ID;
+use compute_budget_substream::pb::compute_budget::compute_budget_event::Event as ComputeBudgetEvent;
+use mpl_token_metadata_substream::mpl_token_metadata::constants::MPL_TOKEN_METADATA_PROGRAM_ID;
+use mpl_token_metadata_substream::pb::mpl_token_metadata::mpl_token_metadata_event::Event as MplTokenMetadataEvent;
+use pumpfun_substream::pumpfun::PUMPFUN_PROGRAM_ID;
+use pumpfun_substream::pb::pumpfun::pumpfun_event::Event as PumpfunEvent;
+use raydium_amm_substream::raydium_amm::constants::RAYDIUM_AMM_PROGRAM_ID;
+use raydium_amm_substream::pb::raydium_amm::raydium_amm_event::Event as RaydiumAmmEvent;
+use spl_memo_substream::memo::is_memo_instruction;
+use spl_memo_substream::pb::spl_memo::spl_memo_event::Event as SplMemoEvent;
+use spl_token_substream::is_token_instruction;
+use spl_token_substream::pb::spl_token::spl_token_event::Event as SplTokenEvent;
+use stake_program_substream::instruction::STAKE_PROGRAM_ID;
+use stake_program_substream::pb::stake_program::stake_program_event::Event as StakeProgramEvent;
+use system_program_substream::pb::system_program::system_program_event::Event as SystemProgramEvent;
+
+/// Result of decoding an instruction with its workspace crate.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Outcome {
+    /// Decoded into an event, or recognized as an instruction the crate doesn't emit.
+    Decoded,
+    /// Emitted as an `UnknownInstruction` event.
+    Unknown,
+    Failed(String),
+    /// Failed because the program logs are truncated.
+    MissingLogs(String),
+}
+
+/// Decodes the instruction with the workspace crate of its program, returning the crate name
+/// along with the outcome. `None` if no crate decodes the program.
+pub fn parse_outcome(
+    instruction: &StructuredInstruction,
+    context: &TransactionContext,
+    signers: &[String],
+) -> Option<(&'static str, Outcome)> {
+    let program_id = instruction.program_id();
+    let parsed = if program_id == SYSTEM_PROGRAM_ID {
+        ("system_program", _outcome(
+            system_program_substream::parse_instruction(instruction, context),
+            |x| matches!(x, Some(SystemProgramEvent::UnknownInstruction(_))),
+            |_| false,
+        ))
+    } else if is_token_instruction(instruction) {
+        ("spl_token", _outcome(
+            spl_token_substream::parse_instruction(instruction, context),
+            |x| matches!(x, Some(SplTokenEvent::UnknownInstruction(_))),
+            |_| false,
+        ))
+    } else if program_id == ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID {
+        ("associated_token_account", _outcome(
+            associated_token_account_substream::parse_instruction(instruction, context),
+            |x| matches!(x, AssociatedTokenAccountEvent::UnknownInstruction(_)),
+            |_| false,
+        ))
+    } else if is_memo_instruction(instruction) {
+        ("spl_memo", _outcome(
+            spl_memo_substream::parse_instruction(instruction, context, signers),
+            |x| matches!(x, SplMemoEvent::UnknownInstruction(_)),
+            |_| false,
+        ))
+    } else if program_id == COMPUTE_BUDGET_PROGRAM_ID {
+        ("compute_budget", _outcome(
+            compute_budget_substream::parse_instruction(instruction, context),
+            |x| matches!(x, ComputeBudgetEvent::UnknownInstruction(_)),
+            |_| false,
+        ))
+    } else if program_id == ADDRESS_LOOKUP_TABLE_PROGRAM_ID {
+        ("address_lookup_table", _outcome(
+            address_lookup_table_substream::parse_instruction(instruction, context),
+            |x| matches!(x, AddressLookupTableEvent::UnknownInstruction(_)),
+            |_| false,
+        ))
+    } else if program_id == STAKE_PROGRAM_ID {
+        ("stake_program", _outcome(
+            stake_program_substream::parse_instruction(instruction, context),
+            |x| matches!(x, Some(StakeProgramEvent::UnknownInstruction(_))),
+            |_| false,
+        ))
+    } else if program_id == BPF_LOADER_UPGRADEABLE_PROGRAM_ID {
+        ("bpf_loader_upgradeable", _outcome(
+            bpf_loader_upgradeable_substream::parse_instruction(instruction, context),
+            |x| matches!(x, Some(BpfLoaderUpgradeableEvent::UnknownInstruction(_))),
+            |_| false,
+        ))
+    } else if program_id == MPL_TOKEN_METADATA_PROGRAM_ID {
+        ("mpl_token_metadata", _outcome(
+            mpl_token_metadata_substream::parse_instruction(instruction, context),
+            |x| matches!(x, Some(MplTokenMetadataEvent::UnknownInstruction(_))),
+            |_| false,
+        ))
+    } else if program_id == PUMPFUN_PROGRAM_ID {
+        ("pumpfun", _outcome(
+            pumpfun_substream::parse_instruction(instruction, context),
+            |x| matches!(x, Some(PumpfunEvent::UnknownInstruction(_))),
+            |x| matches!(x, pumpfun_substream::error::ParseError::LogTruncated),
+        ))
+    } else if program_id == RAYDIUM_AMM_PROGRAM_ID {
+        ("raydium_amm", _outcome(
+            raydium_amm_substream::parse_instruction(instruction, context),
+            |x| matches!(x, Some(RaydiumAmmEvent::UnknownInstruction(_))),
+            |x| matches!(x, raydium_amm_substream::error::ParseError::LogTruncated),
+        ))
+    } else {
+        return None;
+    };
+    Some(parsed)
+}
+
+fn _outcome<T, E: Display>(
+    result: Result<T, E>,
+    is_unknown: impl Fn(&T) -> bool,
+    is_missing_logs: impl Fn(&E) -> bool,
+) -> Outcome {
+    match result {
+        Ok(event) if is_unknown(&event) => Outcome::Unknown,
+        Ok(_) => Outcome::Decoded,
+        Err(error) if is_missing_logs(&error) => Outcome::MissingLogs(error.to_string()),
+        Err(error) => Outcome::Failed(error.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcome(result: Result<Option<&str>, &str>) -> Outcome {
+        _outcome(result, |x| *x == Some("unknown"), |x| *x == "Log truncated")
+    }
+
+    #[test]
+    fn test_outcome() {
+        assert_eq!(outcome(Ok(Some("transfer"))), Outcome::Decoded);
+        // Instructions the crate recognizes but doesn't emit still count as decoded.
+        assert_eq!(outcome(Ok(None)), Outcome::Decoded);
+        assert_eq!(outcome(Ok(Some("unknown"))), Outcome::Unknown);
+        assert_eq!(outcome(Err("Missing account 0")), Outcome::Failed("Missing account 0".to_string()));
+        assert_eq!(outcome(Err("Log truncated")), Outcome::MissingLogs("Log truncated".to_string()));
+    }
+}
@@ -0,0 +1,6 @@
+// @generated
+// @@protoc_insertion_point(attribute:parse_health)
+pub mod parse_health {
+    include!("parse_health.rs");
+    // @@protoc_insertion_point(parse_health)
+}
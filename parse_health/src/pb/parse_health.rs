@@ -0,0 +1,52 @@
+// @generated
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ParseHealth {
+    #[prost(uint64, tag="1")]
+    pub slot: u64,
+    #[prost(message, repeated, tag="2")]
+    pub programs: ::prost::alloc::vec::Vec<ProgramParseHealth>,
+    #[prost(int64, tag="3")]
+    pub block_time: i64,
+    #[prost(string, tag="4")]
+    pub block_hash: ::prost::alloc::string::String,
+}
+/// Decoding coverage of the instructions of a program in the successful transactions of the block.
+/// The instructions are either decoded, unknown, failed to parse or missing log data.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ProgramParseHealth {
+    #[prost(string, tag="1")]
+    pub program_id: ::prost::alloc::string::String,
+    /// Workspace crate decoding the program.
+    #[prost(string, tag="2")]
+    pub substream: ::prost::alloc::string::String,
+    #[prost(uint64, tag="3")]
+    pub instructions: u64,
+    #[prost(uint64, tag="4")]
+    pub decoded: u64,
+    /// Emitted as `UnknownInstruction` events, such as instructions added by a new program version.
+    #[prost(uint64, tag="5")]
+    pub unknown: u64,
+    #[prost(uint64, tag="6")]
+    pub failed: u64,
+    /// Failed to parse because the program logs are truncated.
+    #[prost(uint64, tag="7")]
+    pub missing_logs: u64,
+    /// First errors of the failed and missing log data instructions, up to the `max_errors` param.
+    #[prost(message, repeated, tag="8")]
+    pub errors: ::prost::alloc::vec::Vec<InstructionParseError>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct InstructionParseError {
+    #[prost(string, tag="1")]
+    pub signature: ::prost::alloc::string::String,
+    #[prost(uint32, tag="2")]
+    pub transaction_index: u32,
+    #[prost(uint32, tag="3")]
+    pub instruction_index: u32,
+    #[prost(string, tag="4")]
+    pub error: ::prost::alloc::string::String,
+}
+// @@protoc_insertion_point(module)
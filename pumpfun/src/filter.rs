@@ -0,0 +1,59 @@
+use std::collections::HashSet;
+
+use solana_substreams_common::params::{matches, Params};
+
+use crate::pb::pumpfun::pumpfun_event::Event;
+
+/// Filters events by the `mints`, `bonding_curves` and `users` module params.
+///
+/// An event matches when its mint, bonding curve and user are in the respective filters. Unset
+/// filters match every event.
+#[derive(Clone, Debug, Default)]
+pub struct Filter {
+    mints: Option<HashSet<String>>,
+    bonding_curves: Option<HashSet<String>>,
+    users: Option<HashSet<String>>,
+}
+
+impl Filter {
+    pub fn new(params: &Params) -> Self {
+        Self {
+            mints: params.get_set("mints"),
+            bonding_curves: params.get_set("bonding_curves"),
+            users: params.get_set("users"),
+        }
+    }
+
+    pub fn matches(&self, event: &Event) -> bool {
+        matches(&self.mints, event_mint(event))
+            && matches(&self.bonding_curves, event_bonding_curve(event))
+            && matches(&self.users, event_user(event))
+    }
+}
+
+pub fn event_mint(event: &Event) -> Option<&str> {
+    match event {
+        Event::Create(create) => Some(&create.mint),
+        Event::Swap(swap) => Some(&swap.mint),
+        Event::Withdraw(withdraw) => Some(&withdraw.mint),
+        _ => None,
+    }
+}
+
+pub fn event_bonding_curve(event: &Event) -> Option<&str> {
+    match event {
+        Event::Create(create) => Some(&create.bonding_curve),
+        Event::Swap(swap) => Some(&swap.bonding_curve),
+        _ => None,
+    }
+}
+
+pub fn event_user(event: &Event) -> Option<&str> {
+    match event {
+        Event::Create(create) => Some(&create.user),
+        Event::Swap(swap) => Some(&swap.user),
+        Event::Initialize(initialize) => Some(&initialize.user),
+        Event::SetParams(set_params) => Some(&set_params.user),
+        _ => None,
+    }
+}
@@ -0,0 +1,25 @@
+use std::collections::BTreeSet;
+
+use substreams::pb::sf::substreams::index::v1::Keys;
+
+use crate::pumpfun::PUMPFUN_PROGRAM_ID;
+use crate::filter::{event_bonding_curve, event_mint, event_user};
+use crate::pb::pumpfun::{PumpfunEvent, PumpfunTransactionEvents};
+
+/// Block index keys of the events: `program:`, `mint:`, `bonding_curve:` and `user:`.
+pub fn index_keys(transactions: &[PumpfunTransactionEvents]) -> Keys {
+    let keys: BTreeSet<String> = transactions.iter().flat_map(|x| x.events.iter()).flat_map(event_keys).collect();
+    Keys { keys: keys.into_iter().collect() }
+}
+
+/// Index keys of a single event, which `filtered_pumpfun_events` matches its query against.
+pub fn event_keys(event: &PumpfunEvent) -> BTreeSet<String> {
+    let mut keys = BTreeSet::new();
+    if let Some(event) = event.event.as_ref() {
+        keys.insert(format!("program:{}", PUMPFUN_PROGRAM_ID));
+        keys.extend(event_mint(event).map(|x| format!("mint:{}", x)));
+        keys.extend(event_bonding_curve(event).map(|x| format!("bonding_curve:{}", x)));
+        keys.extend(event_user(event).map(|x| format!("user:{}", x)));
+    }
+    keys
+}
@@ -1,20 +1,36 @@
-use anyhow::Context;
-use anyhow::{anyhow, Error};
+use substreams::errors::Error;
+use substreams::pb::sf::substreams::index::v1::Keys;
 
 use substreams_solana::pb::sf::solana::r#type::v1::ConfirmedTransaction;
 use substreams_solana::pb::sf::solana::r#type::v1::Block;
 
-use substreams_solana_utils::spl_token::TOKEN_PROGRAM_ID;
 use substreams_solana_utils as utils;
 use utils::instruction::{get_structured_instructions, StructuredInstruction, StructuredInstructions};
 use utils::system_program::SYSTEM_PROGRAM_ID;
 use utils::transaction::{get_context, TransactionContext};
 use utils::log::Log;
 
+use solana_substreams_common::{get_failed_instruction, get_transaction_envelope, is_program_invoked};
+use solana_substreams_common::params::Params;
+use solana_substreams_common::query::Query;
+use solana_substreams_common::instruction::{flattened_index, get_call_path, get_unknown_instruction, stack_depth};
+
+use solana_substreams_common::compute_budget::get_compute_budget;
+
 pub mod pumpfun;
 use pumpfun::PUMPFUN_PROGRAM_ID;
 use pumpfun::log::PumpfunLog;
 use pumpfun::instruction::PumpfunInstruction;
+use pumpfun::error::pumpfun_error_name;
+
+pub mod error;
+use error::ParseError;
+
+pub mod filter;
+use filter::Filter;
+
+pub mod index;
+use index::{event_keys, index_keys};
 
 pub mod pb;
 use pb::pumpfun::*;
@@ -22,61 +38,162 @@ use pb::pumpfun::pumpfun_event::Event;
 
 use system_program_substream;
 
-pub fn pumpfun_events(block: Block) -> Result<PumpfunBlockEvents, Error> {
-    let transactions = parse_block(&block)?;
-    Ok(PumpfunBlockEvents { transactions })
+#[substreams::handlers::map]
+fn pumpfun_events(params: String, block: Block) -> Result<PumpfunBlockEvents, Error> {
+    Ok(_block_events(&block, &Params::parse(&params)))
+}
+
+#[substreams::handlers::map]
+fn pumpfun_index(block: Block) -> Result<Keys, Error> {
+    let transactions = parse_block(&block, &Params::default());
+    Ok(index_keys(&transactions))
+}
+
+/// Events matching the block filter query given as params, such as `mint:<mint>`, on their own index keys.
+#[substreams::handlers::map]
+fn filtered_pumpfun_events(query: String, block: Block) -> Result<PumpfunBlockEvents, Error> {
+    let query = Query::parse(&query).map_err(Error::msg)?;
+    let mut block_events = _block_events(&block, &Params::default());
+    for transaction in block_events.transactions.iter_mut() {
+        transaction.events.retain(|x| query.matches(&event_keys(x)));
+    }
+    block_events.transactions.retain(|x| !x.events.is_empty());
+    Ok(block_events)
+}
+
+fn _block_events(block: &Block, params: &Params) -> PumpfunBlockEvents {
+    let transactions = parse_block(block, params);
+    PumpfunBlockEvents {
+        slot: block.slot,
+        block_time: block.block_time.as_ref().map(|x| x.timestamp).unwrap_or_default(),
+        block_hash: block.blockhash.clone(),
+        transactions,
+    }
 }
 
-pub fn parse_block(block: &Block) -> Result<Vec<PumpfunTransactionEvents>, Error> {
+pub fn parse_block(block: &Block, params: &Params) -> Vec<PumpfunTransactionEvents> {
+    let include_failed = params.get_bool("include_failed");
+    let include_priority_fee = params.get_bool("include_priority_fee");
+    let filter = Filter::new(params);
     let mut block_events: Vec<PumpfunTransactionEvents> = Vec::new();
-    for transaction in block.transactions() {
-        let events = parse_transaction(transaction)?;
-        if !events.is_empty() {
-            block_events.push(PumpfunTransactionEvents {
-                signature: utils::transaction::get_signature(&transaction),
-                events,
-            });
+    for (i, transaction) in block.transactions.iter().enumerate() {
+        let failed = transaction.meta.as_ref().map_or(false, |x| x.err.is_some());
+        if failed && !include_failed {
+            continue;
+        }
+        let signature = utils::transaction::get_signature(&transaction);
+        let result = if failed {
+            Ok(parse_failed_transaction(transaction, &filter))
+        } else {
+            parse_transaction(transaction, &filter)
+        };
+        match result {
+            Ok(mut events) if !events.is_empty() => {
+                if include_priority_fee {
+                    attach_priority_fee(&mut events, transaction);
+                }
+                block_events.push(PumpfunTransactionEvents {
+                    signature,
+                    transaction_index: i as u32,
+                    envelope: Some(get_transaction_envelope(transaction)),
+                    events,
+                });
+            },
+            Ok(_) => (),
+            Err(error) => substreams::log::println(format!("Failed to process transaction {}: {}", signature, error)),
         }
     }
-    Ok(block_events)
+    block_events
 }
 
-pub fn parse_transaction(transaction: &ConfirmedTransaction) -> Result<Vec<PumpfunEvent>, Error> {
-    if let Some(_) = transaction.meta.as_ref().unwrap().err {
+pub fn parse_transaction(transaction: &ConfirmedTransaction, filter: &Filter) -> Result<Vec<PumpfunEvent>, ParseError> {
+    if let Some(_) = transaction.meta.as_ref().ok_or(ParseError::MissingTransactionMeta)?.err {
         return Ok(Vec::new())
     }
 
     let mut events: Vec<PumpfunEvent> = Vec::new();
 
-    let context = get_context(transaction).unwrap();
-    let instructions = get_structured_instructions(transaction).unwrap();
+    let context = get_context(transaction).map_err(|x| ParseError::TransactionContext(x.to_string()))?;
+    let instructions = get_structured_instructions(transaction).map_err(|x| ParseError::TransactionContext(x.to_string()))?;
 
-    for instruction in instructions.flattened().iter() {
+    let flattened = instructions.flattened();
+    for (i, instruction) in flattened.iter().enumerate() {
         if instruction.program_id() != PUMPFUN_PROGRAM_ID {
             continue;
         }
 
+        let call_path = get_call_path(instruction, &flattened);
         match parse_instruction(&instruction, &context) {
-            Ok(Some(event)) => {
+            Ok(Some(event)) if filter.matches(&event) => {
                 events.push(PumpfunEvent {
+                    instruction_index: i as u32,
+                    stack_depth: stack_depth(instruction),
+                    top_instruction_program_id: call_path.top_instruction_program_id,
+                    parent_instruction_program_id: call_path.parent_instruction_program_id,
+                    top_instruction_index: call_path.top_instruction_index,
+                    parent_instruction_index: call_path.parent_instruction_index,
                     event: Some(event),
                 })
             }
-            Ok(None) => (),
-            Err(error) => return Err(anyhow!("Transaction {} error: {}", &context.signature, error)),
+            Ok(_) => (),
+            Err(error) => substreams::log::println(format!("Failed to process instruction of transaction {}: {}", &context.signature, error)),
         }
     }
     Ok(events)
 }
 
+/// Sets the priority fee of the transaction on its swap events.
+fn attach_priority_fee(events: &mut [PumpfunEvent], transaction: &ConfirmedTransaction) {
+    let priority_fee = get_compute_budget(transaction).priority_fee;
+    for event in events.iter_mut() {
+        if let Some(Event::Swap(swap)) = event.event.as_mut() {
+            swap.priority_fee = Some(priority_fee);
+        }
+    }
+}
+
+/// Parses a failed transaction into a `FailedInstruction` event, if it invoked the Pumpfun program.
+pub fn parse_failed_transaction(transaction: &ConfirmedTransaction, filter: &Filter) -> Vec<PumpfunEvent> {
+    if !is_program_invoked(transaction, &PUMPFUN_PROGRAM_ID.to_string()) {
+        return Vec::new();
+    }
+    get_failed_instruction(transaction, custom_error_name)
+        .map(|x| PumpfunEvent {
+            instruction_index: flattened_index(transaction, x.instruction_index),
+            stack_depth: 0,
+            top_instruction_program_id: None,
+            parent_instruction_program_id: None,
+            top_instruction_index: None,
+            parent_instruction_index: None,
+            event: Some(Event::FailedInstruction(x)),
+        })
+        .filter(|x| x.event.as_ref().map_or(false, |x| filter.matches(x)))
+        .into_iter()
+        .collect()
+}
+
+/// Name of a custom error code raised by the Pumpfun program or one of the programs it invokes.
+pub fn custom_error_name(program_id: &str, code: u32) -> Option<String> {
+    if program_id == PUMPFUN_PROGRAM_ID.to_string() {
+        pumpfun_error_name(code)
+    } else if program_id == SYSTEM_PROGRAM_ID.to_string() {
+        system_program_substream::custom_error_name(program_id, code)
+    } else {
+        spl_token_substream::custom_error_name(program_id, code)
+    }
+}
+
 pub fn parse_instruction(
     instruction: &StructuredInstruction,
     context: &TransactionContext
-) -> Result<Option<Event>, Error> {
+) -> Result<Option<Event>, ParseError> {
     if instruction.program_id() != PUMPFUN_PROGRAM_ID {
-        return Err(anyhow!("Not a Pumpfun instruction."));
+        return Err(ParseError::UnexpectedProgram(instruction.program_id().to_string()));
     }
-    let unpacked = PumpfunInstruction::unpack(instruction.data()).map_err(|x| anyhow!(x))?;
+    let unpacked = match PumpfunInstruction::unpack(instruction.data()) {
+        Ok(unpacked) => unpacked,
+        Err(_) => return Ok(Some(Event::UnknownInstruction(get_unknown_instruction(instruction, 8)))),
+    };
     match unpacked {
         PumpfunInstruction::Initialize => {
             Ok(Some(Event::Initialize(_parse_initialize_instruction(instruction, context)?)))
@@ -96,15 +213,21 @@ pub fn parse_instruction(
         PumpfunInstruction::Withdraw => {
             Ok(Some(Event::Withdraw(_parse_withdraw_instruction(instruction, context)?)))
         }
-        _ => Ok(None),
+        PumpfunInstruction::Unknown => {
+            Ok(Some(Event::UnknownInstruction(get_unknown_instruction(instruction, 8))))
+        }
     }
 }
 
+fn _account(instruction: &StructuredInstruction, index: usize) -> Result<String, ParseError> {
+    instruction.accounts().get(index).map(|x| x.to_string()).ok_or(ParseError::MissingAccount(index))
+}
+
 fn _parse_initialize_instruction(
     instruction: &StructuredInstruction,
     _context: &TransactionContext,
-) -> Result<InitializeEvent, Error> {
-    let user = instruction.accounts()[0].to_string();
+) -> Result<InitializeEvent, ParseError> {
+    let user = _account(instruction, 0)?;
 
     Ok(InitializeEvent {
         user,
@@ -115,8 +238,8 @@ fn _parse_set_params_instruction(
     instruction: &StructuredInstruction,
     _context: &TransactionContext,
     set_params: pumpfun::instruction::SetParamsInstruction,
-) -> Result<SetParamsEvent, Error> {
-    let user = instruction.accounts()[0].to_string();
+) -> Result<SetParamsEvent, ParseError> {
+    let user = _account(instruction, 0)?;
     let fee_recipient = set_params.fee_recipient.to_string();
     let initial_virtual_token_reserves = set_params.initial_virtual_token_reserves;
     let initial_virtual_sol_reserves = set_params.initial_virtual_sol_reserves;
@@ -139,15 +262,15 @@ fn _parse_create_instruction(
     instruction: &StructuredInstruction,
     _context: &TransactionContext,
     create: pumpfun::instruction::CreateInstruction,
-) -> Result<CreateEvent, Error> {
-    let user = instruction.accounts()[7].to_string();
+) -> Result<CreateEvent, ParseError> {
+    let user = _account(instruction, 7)?;
     let name = create.name;
     let symbol = create.symbol;
     let uri = create.uri;
-    let mint = instruction.accounts()[0].to_string();
-    let bonding_curve = instruction.accounts()[2].to_string();
-    let associated_bonding_curve = instruction.accounts()[2].to_string();
-    let metadata = instruction.accounts()[6].to_string();
+    let mint = _account(instruction, 0)?;
+    let bonding_curve = _account(instruction, 2)?;
+    let associated_bonding_curve = _account(instruction, 2)?;
+    let metadata = _account(instruction, 6)?;
 
     Ok(CreateEvent {
         user,
@@ -165,24 +288,30 @@ fn _parse_buy_instruction<'a>(
     instruction: &StructuredInstruction<'a>,
     context: &TransactionContext,
     buy: pumpfun::instruction::BuyInstruction,
-) -> Result<SwapEvent, Error> {
-    let mint = instruction.accounts()[2].to_string();
-    let bonding_curve = instruction.accounts()[3].to_string();
-    let user = instruction.accounts()[6].to_string();
+) -> Result<SwapEvent, ParseError> {
+    let mint = _account(instruction, 2)?;
+    let bonding_curve = _account(instruction, 3)?;
+    let user = _account(instruction, 6)?;
     let token_amount = buy.amount;
 
     let system_transfer_instruction = instruction.inner_instructions()
         .iter()
         .find(|x| x.program_id() == SYSTEM_PROGRAM_ID)
-        .ok_or(anyhow::anyhow!("No instruction with program_id == SYSTEM_PROGRAM_ID found"))?
+        .ok_or(ParseError::InnerInstructionMismatch("no System Program transfer found"))?
         .clone();
 
     let system_transfer = system_program_substream::parse_transfer_instruction(system_transfer_instruction.as_ref(), context)?;
     let sol_amount = Some(system_transfer.lamports);
 
-    let token_transfer_instruction = instruction.inner_instructions().iter().find(|x| x.program_id() == TOKEN_PROGRAM_ID).unwrap().clone();
-    let token_transfer = spl_token_substream::parse_transfer_instruction(token_transfer_instruction.as_ref(), context).map_err(|e| anyhow!(e))?;
-    let user_token_pre_balance = token_transfer.destination.unwrap().pre_balance;
+    let token_transfer_instruction = instruction.inner_instructions()
+        .iter()
+        .find(|x| spl_token_substream::is_token_instruction(x))
+        .ok_or(ParseError::InnerInstructionMismatch("no Token program transfer found"))?
+        .clone();
+    let token_transfer = spl_token_substream::parse_transfer_instruction(token_transfer_instruction.as_ref(), context)?;
+    let user_token_pre_balance = token_transfer.destination
+        .ok_or(ParseError::InnerInstructionMismatch("token transfer has no destination account"))?
+        .pre_balance;
 
     let trade = match parse_pumpfun_log(instruction) {
         Ok(PumpfunLog::Trade(trade)) => Some(trade),
@@ -207,6 +336,7 @@ fn _parse_buy_instruction<'a>(
         real_sol_reserves,
         real_token_reserves,
         user_token_pre_balance,
+        priority_fee: None,
     })
 }
 
@@ -214,10 +344,10 @@ fn _parse_sell_instruction(
     instruction: &StructuredInstruction,
     context: &TransactionContext,
     sell: pumpfun::instruction::SellInstruction,
-) -> Result<SwapEvent, Error> {
-    let mint = instruction.accounts()[2].to_string();
-    let user = instruction.accounts()[6].to_string();
-    let bonding_curve = instruction.accounts()[3].to_string();
+) -> Result<SwapEvent, ParseError> {
+    let mint = _account(instruction, 2)?;
+    let user = _account(instruction, 6)?;
+    let bonding_curve = _account(instruction, 3)?;
     let token_amount = sell.amount;
 
     let trade = match parse_pumpfun_log(instruction) {
@@ -234,16 +364,15 @@ fn _parse_sell_instruction(
 
     let token_transfer_instruction = instruction.inner_instructions()
         .iter()
-        .find(|x| x.program_id() == TOKEN_PROGRAM_ID)
-        .ok_or_else(|| anyhow!("No instruction with program_id == TOKEN_PROGRAM_ID found"))?
+        .find(|x| spl_token_substream::is_token_instruction(x))
+        .ok_or(ParseError::InnerInstructionMismatch("no Token program transfer found"))?
         .clone();
 
-    let token_transfer = spl_token_substream::parse_transfer_instruction(token_transfer_instruction.as_ref(), context)
-        .map_err(|e| anyhow!(e))?;
+    let token_transfer = spl_token_substream::parse_transfer_instruction(token_transfer_instruction.as_ref(), context)?;
 
     let user_token_pre_balance = token_transfer
         .source
-        .ok_or_else(|| anyhow!("Source account not found in token transfer"))?
+        .ok_or(ParseError::InnerInstructionMismatch("token transfer has no source account"))?
         .pre_balance;
 
 
@@ -259,24 +388,25 @@ fn _parse_sell_instruction(
         real_sol_reserves,
         real_token_reserves,
         user_token_pre_balance,
+        priority_fee: None,
     })
 }
 
 fn _parse_withdraw_instruction(
     instruction: &StructuredInstruction,
     _context: &TransactionContext,
-) -> Result<WithdrawEvent, Error> {
-    let mint = instruction.accounts()[2].to_string();
+) -> Result<WithdrawEvent, ParseError> {
+    let mint = _account(instruction, 2)?;
 
     Ok(WithdrawEvent {
         mint,
     })
 }
 
-fn parse_pumpfun_log(instruction: &StructuredInstruction) -> Result<PumpfunLog, Error> {
-    let data = instruction.logs().as_ref().context("Failed to parse logs due to truncation")?.iter().find_map(|log| match log {
+fn parse_pumpfun_log(instruction: &StructuredInstruction) -> Result<PumpfunLog, ParseError> {
+    let data = instruction.logs().as_ref().ok_or(ParseError::LogTruncated)?.iter().find_map(|log| match log {
         Log::Data(data_log) => data_log.data().ok(),
         _ => None,
-    }).ok_or(anyhow!("Couldn't find data log."))?;
-    PumpfunLog::unpack(data.as_slice()).map_err(|x| anyhow!(x))
+    }).ok_or(ParseError::InvalidLog("couldn't find data log".into()))?;
+    PumpfunLog::unpack(data.as_slice()).map_err(|x| ParseError::InvalidLog(x.to_string()))
 }
@@ -4,6 +4,12 @@
 pub struct PumpfunBlockEvents {
     #[prost(message, repeated, tag="1")]
     pub transactions: ::prost::alloc::vec::Vec<PumpfunTransactionEvents>,
+    #[prost(uint64, tag="2")]
+    pub slot: u64,
+    #[prost(int64, tag="3")]
+    pub block_time: i64,
+    #[prost(string, tag="4")]
+    pub block_hash: ::prost::alloc::string::String,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -12,11 +18,28 @@ pub struct PumpfunTransactionEvents {
     pub signature: ::prost::alloc::string::String,
     #[prost(message, repeated, tag="2")]
     pub events: ::prost::alloc::vec::Vec<PumpfunEvent>,
+    #[prost(uint32, tag="3")]
+    pub transaction_index: u32,
+    #[prost(message, optional, tag="4")]
+    pub envelope: ::core::option::Option<::solana_substreams_common::pb::common::TransactionEnvelope>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct PumpfunEvent {
-    #[prost(oneof="pumpfun_event::Event", tags="1, 2, 3, 4, 5")]
+    #[prost(uint32, tag="7")]
+    pub instruction_index: u32,
+    /// Depth in the invocation stack, 0 for top level instructions.
+    #[prost(uint32, tag="8")]
+    pub stack_depth: u32,
+    #[prost(string, optional, tag="9")]
+    pub top_instruction_program_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag="10")]
+    pub parent_instruction_program_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(uint32, optional, tag="11")]
+    pub top_instruction_index: ::core::option::Option<u32>,
+    #[prost(uint32, optional, tag="12")]
+    pub parent_instruction_index: ::core::option::Option<u32>,
+    #[prost(oneof="pumpfun_event::Event", tags="1, 2, 3, 4, 5, 6, 13")]
     pub event: ::core::option::Option<pumpfun_event::Event>,
 }
 /// Nested message and enum types in `PumpfunEvent`.
@@ -34,6 +57,10 @@ pub mod pumpfun_event {
         Withdraw(super::WithdrawEvent),
         #[prost(message, tag="5")]
         Create(super::CreateEvent),
+        #[prost(message, tag="6")]
+        FailedInstruction(::solana_substreams_common::pb::common::FailedInstruction),
+        #[prost(message, tag="13")]
+        UnknownInstruction(::solana_substreams_common::pb::common::UnknownInstruction),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -105,6 +132,9 @@ pub struct SwapEvent {
     pub real_token_reserves: ::core::option::Option<u64>,
     #[prost(uint64, optional, tag="11")]
     pub user_token_pre_balance: ::core::option::Option<u64>,
+    /// Priority fee of the transaction in lamports, set with the `include_priority_fee` param.
+    #[prost(uint64, optional, tag="12")]
+    pub priority_fee: ::core::option::Option<u64>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
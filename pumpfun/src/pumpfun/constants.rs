@@ -1,4 +1 @@
-use substreams_solana_utils::pubkey::Pubkey;
-use substreams_solana::b58;
-
-pub const PUMPFUN_PROGRAM_ID: Pubkey = Pubkey(b58!("6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P"));
+pub use solana_substreams_common::programs::PUMPFUN_PROGRAM_ID;
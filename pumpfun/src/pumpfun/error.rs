@@ -0,0 +1,48 @@
+//! Error types
+
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+use thiserror::Error;
+
+use solana_substreams_common::anchor::anchor_error_name;
+
+/// Errors that may be returned by the Pumpfun program.
+#[derive(Clone, Debug, Eq, Error, FromPrimitive, PartialEq)]
+pub enum PumpfunError {
+    /// 6000
+    #[error("The given account is not authorized to execute this instruction.")]
+    NotAuthorized = 6000,
+
+    /// 6001
+    #[error("The program is already initialized.")]
+    AlreadyInitialized,
+
+    /// 6002
+    #[error("slippage: Too much SOL required to buy the given amount of tokens.")]
+    TooMuchSolRequired,
+
+    /// 6003
+    #[error("slippage: Too little SOL received to sell the given amount of tokens.")]
+    TooLittleSolReceived,
+
+    /// 6004
+    #[error("The mint does not match the bonding curve.")]
+    MintDoesNotMatchBondingCurve,
+
+    /// 6005
+    #[error("The bonding curve has completed and liquidity migrated to raydium.")]
+    BondingCurveComplete,
+
+    /// 6006
+    #[error("The bonding curve has not completed.")]
+    BondingCurveNotComplete,
+
+    /// 6007
+    #[error("The program is not initialized.")]
+    NotInitialized,
+}
+
+/// Name of a Pumpfun custom error code, falling back to the Anchor framework errors.
+pub fn pumpfun_error_name(code: u32) -> Option<String> {
+    PumpfunError::from_u32(code).map(|x| format!("{:?}", x)).or_else(|| anchor_error_name(code))
+}
@@ -14,6 +14,9 @@ pub enum PumpfunInstruction {
 
 impl PumpfunInstruction {
     pub fn unpack(data: &[u8]) -> Result<Self, &'static str> {
+        if data.len() < 8 {
+            return Err("Data is shorter than the discriminator.");
+        }
         let (tag, data) = data.split_at(8);
         match tag {
             [175, 175, 109, 31, 13, 152, 155, 237] => Ok(Self::Initialize),
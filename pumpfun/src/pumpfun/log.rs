@@ -28,6 +28,9 @@ pub enum PumpfunLog {
 
 impl PumpfunLog {
     pub fn unpack(data: &[u8]) -> Result<Self, &'static str> {
+        if data.len() < 8 {
+            return Err("Data is shorter than the discriminator.");
+        }
         let (discriminator, data) = data.split_at(8);
         match discriminator {
             [27, 114, 169, 77, 222, 235, 99, 118] => CreateLog::try_from_slice(data).map(Self::Create).map_err(|_| "Failed to unpack CreateEvent."),
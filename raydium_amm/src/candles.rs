@@ -0,0 +1,151 @@
+use substreams_solana::pb::sf::solana::r#type::v1::Block;
+
+use spl_token_substream::decimals::MintDecimals;
+
+use crate::pb::raydium_amm::{RaydiumAmmBlockEvents, SwapEvent, Trade};
+use crate::pb::raydium_amm::raydium_amm_event::Event;
+
+/// Candle intervals, by name and length in seconds.
+pub const CANDLE_INTERVALS: [(&str, i64); 4] = [("1m", 60), ("5m", 300), ("1h", 3_600), ("1d", 86_400)];
+
+/// Swaps of the block priced at their execution price. Swaps of mints with unknown decimals, or
+/// with a zero amount, are kept without a price, so that they still count in the candle volumes.
+pub fn get_trades(block: &Block, events: &RaydiumAmmBlockEvents) -> Vec<Trade> {
+    let mut trades: Vec<Trade> = Vec::new();
+    for transaction in events.transactions.iter() {
+        let mint_decimals = match block.transactions.get(transaction.transaction_index as usize) {
+            Some(confirmed_transaction) => MintDecimals::new(confirmed_transaction),
+            None => continue,
+        };
+        for event in transaction.events.iter() {
+            let swap = match event.event.as_ref() {
+                Some(Event::Swap(swap)) => swap,
+                _ => continue,
+            };
+            let (coin_amount, pc_amount) = swap_amounts(swap);
+            let price = match (mint_decimals.get(&swap.coin_mint), mint_decimals.get(&swap.pc_mint)) {
+                (Some(coin_decimals), Some(pc_decimals)) => execution_price(coin_amount, pc_amount, coin_decimals, pc_decimals),
+                _ => None,
+            };
+            trades.push(Trade {
+                amm: swap.amm.clone(),
+                coin_mint: swap.coin_mint.clone(),
+                pc_mint: swap.pc_mint.clone(),
+                user: swap.user.clone(),
+                coin_amount,
+                pc_amount,
+                price,
+                signature: transaction.signature.clone(),
+                transaction_index: transaction.transaction_index,
+                instruction_index: event.instruction_index,
+            });
+        }
+    }
+    trades
+}
+
+/// Coin and pc amounts exchanged by the swap. The direction is the side the user receives.
+pub fn swap_amounts(swap: &SwapEvent) -> (u64, u64) {
+    if swap.direction == "coin" {
+        (swap.amount_out, swap.amount_in)
+    } else {
+        (swap.amount_in, swap.amount_out)
+    }
+}
+
+/// Price of the coin in pc, adjusted by the mint decimals.
+pub fn execution_price(coin_amount: u64, pc_amount: u64, coin_decimals: u32, pc_decimals: u32) -> Option<f64> {
+    if coin_amount == 0 || pc_amount == 0 {
+        return None;
+    }
+    let coin = coin_amount as f64 / 10f64.powi(coin_decimals as i32);
+    let pc = pc_amount as f64 / 10f64.powi(pc_decimals as i32);
+    Some(pc / coin)
+}
+
+/// Start of the interval containing the timestamp.
+pub fn candle_start(timestamp: i64, seconds: i64) -> i64 {
+    timestamp - timestamp.rem_euclid(seconds)
+}
+
+/// Store key of the candle of the pool, as `<interval>:<start_time>:<amm>`, so that the candles
+/// of a window share a prefix.
+pub fn candle_key(interval: &str, amm: &str, start_time: i64) -> String {
+    format!("{}:{}:{}", interval, start_time, amm)
+}
+
+/// Store keys of the candles of each interval containing the timestamp, along with their interval
+/// name and start time.
+pub fn candle_keys(amm: &str, timestamp: i64) -> Vec<(&'static str, i64, String)> {
+    CANDLE_INTERVALS.iter().map(|(interval, seconds)| {
+        let start_time = candle_start(timestamp, *seconds);
+        (*interval, start_time, candle_key(interval, amm, start_time))
+    }).collect()
+}
+
+/// Key of the time of the last block processed, whose deltas give the time of the previous block.
+pub const LAST_BLOCK_TIME_KEY: &str = "last_block_time";
+
+/// Store key prefixes of the candles closed since the previous block, for each interval: every
+/// window from the one containing the previous block up to the one containing the timestamp,
+/// excluded. Without a previous block time, only the window preceding the current one is closed.
+pub fn closed_candle_prefixes(previous_timestamp: Option<i64>, timestamp: i64) -> Vec<String> {
+    let mut prefixes = Vec::new();
+    for (interval, seconds) in CANDLE_INTERVALS.iter() {
+        let current_start = candle_start(timestamp, *seconds);
+        let mut start = match previous_timestamp {
+            Some(previous_timestamp) => candle_start(previous_timestamp, *seconds),
+            None => current_start - seconds,
+        };
+        while start < current_start {
+            prefixes.push(format!("{}:{}:", interval, start));
+            start += seconds;
+        }
+    }
+    prefixes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_candle_start() {
+        assert_eq!(candle_start(1_700_000_000, 60), 1_699_999_980);
+        assert_eq!(candle_start(1_699_999_980, 60), 1_699_999_980);
+        assert_eq!(candle_start(1_700_000_000, 86_400), 1_699_920_000);
+        assert_eq!(candle_start(-1, 60), -60);
+    }
+
+    #[test]
+    fn test_candle_keys() {
+        let keys = candle_keys("amm", 1_700_000_000);
+        assert_eq!(keys[0], ("1m", 1_699_999_980, "1m:1699999980:amm".to_string()));
+        assert_eq!(keys[2], ("1h", 1_699_999_200, "1h:1699999200:amm".to_string()));
+        assert_eq!(closed_candle_prefixes(None, 1_700_000_000)[0], "1m:1699999920:");
+        assert!(!keys[0].2.starts_with(&closed_candle_prefixes(None, 1_700_000_000)[0]));
+        assert!(keys[0].2.starts_with(&closed_candle_prefixes(None, 1_700_000_040)[0]));
+    }
+
+    #[test]
+    fn test_closed_candle_prefixes() {
+        // Nothing closes within a window.
+        assert!(closed_candle_prefixes(Some(1_699_999_985), 1_700_000_000).is_empty());
+        assert_eq!(closed_candle_prefixes(Some(1_699_999_975), 1_700_000_000), vec!["1m:1699999920:"]);
+        // Every window since the previous block closes, and not only the last one.
+        assert_eq!(
+            closed_candle_prefixes(Some(1_699_999_700), 1_700_000_000),
+            vec!["1m:1699999680:", "1m:1699999740:", "1m:1699999800:", "1m:1699999860:", "1m:1699999920:", "5m:1699999500:"],
+        );
+        assert_eq!(closed_candle_prefixes(None, 1_700_000_000).len(), CANDLE_INTERVALS.len());
+    }
+
+    #[test]
+    fn test_execution_price() {
+        // 2 coins with 9 decimals for 300 pc with 6 decimals.
+        assert_eq!(execution_price(2_000_000_000, 300_000_000, 9, 6), Some(150.0));
+        assert_eq!(execution_price(4, 1, 0, 0), Some(0.25));
+        assert_eq!(execution_price(0, 300_000_000, 9, 6), None);
+        assert_eq!(execution_price(2_000_000_000, 0, 9, 6), None);
+    }
+}
@@ -0,0 +1,25 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ParseError {
+    #[error("Transaction meta is missing")]
+    MissingTransactionMeta,
+    #[error("Failed to build transaction context: {0}")]
+    TransactionContext(String),
+    #[error("Instruction does not originate from Raydium AMM Program: {0}")]
+    UnexpectedProgram(String),
+    #[error("Missing account at index {0}")]
+    MissingAccount(usize),
+    #[error("Token account {0} not found in transaction context")]
+    MissingTokenAccount(String),
+    #[error("Invalid instruction data: {0}")]
+    InvalidInstructionData(String),
+    #[error("Inner instruction mismatch: {0}")]
+    InnerInstructionMismatch(&'static str),
+    #[error("Program logs are truncated")]
+    LogTruncated,
+    #[error("Invalid program log: {0}")]
+    InvalidLog(String),
+    #[error(transparent)]
+    SplToken(#[from] spl_token_substream::error::ParseError),
+}
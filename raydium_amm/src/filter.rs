@@ -0,0 +1,65 @@
+use std::collections::HashSet;
+
+use solana_substreams_common::params::{matches, Params};
+
+use crate::pb::raydium_amm::raydium_amm_event::Event;
+
+/// Filters events by the `amms`, `mints` and `users` module params.
+///
+/// An event matches when its AMM, one of its mints and its user are in the respective filters.
+/// Unset filters match every event.
+#[derive(Clone, Debug, Default)]
+pub struct Filter {
+    amms: Option<HashSet<String>>,
+    mints: Option<HashSet<String>>,
+    users: Option<HashSet<String>>,
+}
+
+impl Filter {
+    pub fn new(params: &Params) -> Self {
+        Self {
+            amms: params.get_set("amms"),
+            mints: params.get_set("mints"),
+            users: params.get_set("users"),
+        }
+    }
+
+    pub fn matches(&self, event: &Event) -> bool {
+        matches(&self.amms, event_amm(event))
+            && matches(&self.mints, event_mints(event))
+            && matches(&self.users, event_user(event))
+    }
+}
+
+pub fn event_amm(event: &Event) -> Option<&str> {
+    match event {
+        Event::Initialize(initialize) => Some(&initialize.amm),
+        Event::Deposit(deposit) => Some(&deposit.amm),
+        Event::Withdraw(withdraw) => Some(&withdraw.amm),
+        Event::WithdrawPnl(withdraw_pnl) => Some(&withdraw_pnl.amm),
+        Event::Swap(swap) => Some(&swap.amm),
+        Event::FailedInstruction(_) | Event::UnknownInstruction(_) => None,
+    }
+}
+
+pub fn event_mints(event: &Event) -> Vec<&str> {
+    match event {
+        Event::Initialize(initialize) => vec![initialize.pc_mint.as_str(), initialize.coin_mint.as_str(), initialize.lp_mint.as_str()],
+        Event::Deposit(deposit) => vec![deposit.pc_mint.as_str(), deposit.coin_mint.as_str(), deposit.lp_mint.as_str()],
+        Event::Withdraw(withdraw) => vec![withdraw.pc_mint.as_str(), withdraw.coin_mint.as_str(), withdraw.lp_mint.as_str()],
+        Event::WithdrawPnl(withdraw_pnl) => withdraw_pnl.pc_mint.iter().chain(withdraw_pnl.coin_mint.iter()).map(|x| x.as_str()).collect(),
+        Event::Swap(swap) => vec![swap.pc_mint.as_str(), swap.coin_mint.as_str()],
+        Event::FailedInstruction(_) | Event::UnknownInstruction(_) => Vec::new(),
+    }
+}
+
+pub fn event_user(event: &Event) -> Option<&str> {
+    match event {
+        Event::Initialize(initialize) => Some(&initialize.user),
+        Event::Deposit(deposit) => Some(&deposit.user),
+        Event::Withdraw(withdraw) => Some(&withdraw.user),
+        Event::WithdrawPnl(withdraw_pnl) => Some(&withdraw_pnl.user),
+        Event::Swap(swap) => Some(&swap.user),
+        Event::FailedInstruction(_) | Event::UnknownInstruction(_) => None,
+    }
+}
@@ -0,0 +1,25 @@
+use std::collections::BTreeSet;
+
+use substreams::pb::sf::substreams::index::v1::Keys;
+
+use crate::raydium_amm::constants::RAYDIUM_AMM_PROGRAM_ID;
+use crate::filter::{event_amm, event_mints, event_user};
+use crate::pb::raydium_amm::{RaydiumAmmEvent, RaydiumAmmTransactionEvents};
+
+/// Block index keys of the events: `program:`, `amm:`, `mint:` and `user:`.
+pub fn index_keys(transactions: &[RaydiumAmmTransactionEvents]) -> Keys {
+    let keys: BTreeSet<String> = transactions.iter().flat_map(|x| x.events.iter()).flat_map(event_keys).collect();
+    Keys { keys: keys.into_iter().collect() }
+}
+
+/// Index keys of a single event, which `filtered_raydium_amm_events` matches its query against.
+pub fn event_keys(event: &RaydiumAmmEvent) -> BTreeSet<String> {
+    let mut keys = BTreeSet::new();
+    if let Some(event) = event.event.as_ref() {
+        keys.insert(format!("program:{}", RAYDIUM_AMM_PROGRAM_ID));
+        keys.extend(event_amm(event).map(|x| format!("amm:{}", x)));
+        keys.extend(event_mints(event).into_iter().map(|x| format!("mint:{}", x)));
+        keys.extend(event_user(event).map(|x| format!("user:{}", x)));
+    }
+    keys
+}
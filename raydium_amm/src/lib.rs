@@ -1,5 +1,13 @@
-use regex;
-use anyhow::{anyhow, Error, Context};
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use substreams::errors::Error;
+use substreams::pb::sf::substreams::index::v1::Keys;
+use substreams::pb::substreams::store_delta::Operation;
+use substreams::scalar::BigInt;
+use substreams::store::{DeltaInt64, Deltas, StoreGet, StoreGetBigInt, StoreGetFloat64, StoreGetProto, StoreNew, StoreSet, StoreSetProto};
+use substreams::store::{StoreAdd, StoreAddBigInt, StoreDelete, StoreMax, StoreMaxFloat64, StoreMin, StoreMinFloat64};
+use substreams::store::{StoreSetFloat64, StoreSetInt64, StoreSetIfNotExists, StoreSetIfNotExistsFloat64, StoreSetIfNotExistsInt64};
 
 use substreams_solana::pb::sf::solana::r#type::v1::ConfirmedTransaction;
 use substreams_solana::pb::sf::solana::r#type::v1::Block;
@@ -8,6 +16,7 @@ pub mod raydium_amm;
 use raydium_amm::instruction::AmmInstruction;
 use raydium_amm::constants::RAYDIUM_AMM_PROGRAM_ID;
 use raydium_amm::log::{decode_ray_log, RayLog};
+use raydium_amm::error::amm_error_name;
 
 use substreams_solana_utils as utils;
 use utils::instruction::{get_structured_instructions, StructuredInstruction, StructuredInstructions};
@@ -15,68 +24,400 @@ use utils::transaction::{get_context, TransactionContext};
 use utils::pubkey::Pubkey;
 use utils::log::Log;
 
+use solana_substreams_common::{get_failed_instruction, get_transaction_envelope, is_program_invoked};
+use solana_substreams_common::params::Params;
+use solana_substreams_common::query::Query;
+use solana_substreams_common::instruction::{flattened_index, get_call_path, get_unknown_instruction, stack_depth};
+
+use solana_substreams_common::compute_budget::get_compute_budget;
+
 use spl_token_substream;
+use spl_token_substream::pb::spl_token::TokenAccount;
+
+pub mod error;
+use error::ParseError;
+
+pub mod filter;
+use filter::Filter;
+
+pub mod index;
+use index::{event_keys, index_keys};
+
+pub mod reserves;
+use reserves::{get_pool_price, get_pool_reserves};
+
+pub mod candles;
+use candles::{candle_keys, closed_candle_prefixes, get_trades, LAST_BLOCK_TIME_KEY};
 
 pub mod pb;
 use pb::raydium_amm::*;
 use pb::raydium_amm::raydium_amm_event::Event;
 
-fn raydium_amm_events(block: Block) -> Result<RaydiumAmmBlockEvents, Error> {
-    let transactions = parse_block(&block);
-    Ok(RaydiumAmmBlockEvents { transactions })
+#[substreams::handlers::map]
+fn raydium_amm_events(params: String, block: Block) -> Result<RaydiumAmmBlockEvents, Error> {
+    Ok(_block_events(&block, &Params::parse(&params)))
+}
+
+#[substreams::handlers::map]
+fn raydium_amm_index(block: Block) -> Result<Keys, Error> {
+    let transactions = parse_block(&block, &Params::default());
+    Ok(index_keys(&transactions))
+}
+
+/// Events matching the block filter query given as params, such as `mint:<mint>`, on their own index keys.
+#[substreams::handlers::map]
+fn filtered_raydium_amm_events(query: String, block: Block) -> Result<RaydiumAmmBlockEvents, Error> {
+    let query = Query::parse(&query).map_err(Error::msg)?;
+    let mut block_events = _block_events(&block, &Params::default());
+    for transaction in block_events.transactions.iter_mut() {
+        transaction.events.retain(|x| query.matches(&event_keys(x)));
+    }
+    block_events.transactions.retain(|x| !x.events.is_empty());
+    Ok(block_events)
+}
+
+/// End of block reserves of the pools with a swap, initialization, deposit or withdrawal.
+#[substreams::handlers::map]
+fn raydium_amm_pool_reserves(block: Block) -> Result<RaydiumAmmPoolReserves, Error> {
+    let transactions = parse_block(&block, &Params::default());
+    Ok(RaydiumAmmPoolReserves {
+        slot: block.slot,
+        block_time: block.block_time.as_ref().map(|x| x.timestamp).unwrap_or_default(),
+        pools: get_pool_reserves(&block, &transactions),
+    })
+}
+
+/// Latest reserves of each pool under `pool:<amm>`, and its latest known LP supply under
+/// `lp_supply:<amm>`, as swaps don't report it.
+#[substreams::handlers::store]
+fn store_raydium_amm_pools(reserves: RaydiumAmmPoolReserves, store: StoreSetProto<PoolReserves>) {
+    for pool in reserves.pools {
+        if pool.lp_supply.is_some() {
+            store.set(0, format!("lp_supply:{}", pool.amm), &pool);
+        }
+        store.set(0, format!("pool:{}", pool.amm), &pool);
+    }
+}
+
+/// End of block reserves and mid price of the pools, with the LP supply carried over from the
+/// store when the block doesn't report it.
+#[substreams::handlers::map]
+fn raydium_amm_pool_prices(
+    reserves: RaydiumAmmPoolReserves,
+    store: StoreGetProto<PoolReserves>,
+) -> Result<RaydiumAmmPoolPrices, Error> {
+    let pools = reserves.pools.iter().map(|pool| {
+        let lp_supply = store.get_last(format!("lp_supply:{}", pool.amm)).and_then(|x| x.lp_supply);
+        get_pool_price(pool, lp_supply)
+    }).collect();
+    Ok(RaydiumAmmPoolPrices {
+        slot: reserves.slot,
+        block_time: reserves.block_time,
+        pools,
+    })
+}
+
+/// Swaps of the block priced at their execution price, which the candle stores aggregate.
+///
+/// The swaps are parsed without params, so that the candles don't depend on the event filters.
+#[substreams::handlers::map]
+fn raydium_amm_trades(block: Block) -> Result<RaydiumAmmTrades, Error> {
+    let events = _block_events(&block, &Params::default());
+    Ok(RaydiumAmmTrades {
+        slot: events.slot,
+        block_time: events.block_time,
+        trades: get_trades(&block, &events),
+    })
+}
+
+/// Time of the last block, under `last_block_time`. Its deltas give the candle stores the time of
+/// the previous block, so that they delete every candle closed since.
+#[substreams::handlers::store]
+fn store_raydium_amm_last_block_time(trades: RaydiumAmmTrades, store: StoreSetInt64) {
+    store.set(0, LAST_BLOCK_TIME_KEY, &trades.block_time);
+}
+
+/// Traders of each candle, under `<candle>:<user>`, so that the first trade of a user in a candle
+/// shows up as a created delta.
+///
+/// Like the other candle stores, it deletes the closed candles at the start of every block.
+#[substreams::handlers::store]
+fn store_raydium_amm_candle_traders(trades: RaydiumAmmTrades, last_block_time: Deltas<DeltaInt64>, store: StoreSetIfNotExistsInt64) {
+    for prefix in _closed_candle_prefixes(&trades, &last_block_time) {
+        store.delete_prefix(0, &prefix);
+    }
+    for (i, trade) in trades.trades.iter().enumerate() {
+        for (_, _, key) in candle_keys(&trade.amm, trades.block_time) {
+            store.set_if_not_exists(i as u64, format!("{}:{}", key, trade.user), &1);
+        }
+    }
+}
+
+/// Volumes, trade count and trader count of each candle, under `volume_coin:<candle>`,
+/// `volume_pc:<candle>`, `trades:<candle>` and `traders:<candle>`. Unpriced trades are counted too.
+#[substreams::handlers::store]
+fn store_raydium_amm_candle_totals(trades: RaydiumAmmTrades, traders: Deltas<DeltaInt64>, last_block_time: Deltas<DeltaInt64>, store: StoreAddBigInt) {
+    for prefix in _closed_candle_prefixes(&trades, &last_block_time) {
+        for total in ["volume_coin", "volume_pc", "trades", "traders"] {
+            store.delete_prefix(0, &format!("{}:{}", total, prefix));
+        }
+    }
+    for (i, trade) in trades.trades.iter().enumerate() {
+        for (_, _, key) in candle_keys(&trade.amm, trades.block_time) {
+            store.add(i as u64, format!("volume_coin:{}", key), BigInt::from(trade.coin_amount));
+            store.add(i as u64, format!("volume_pc:{}", key), BigInt::from(trade.pc_amount));
+            store.add(i as u64, format!("trades:{}", key), BigInt::one());
+        }
+    }
+    for delta in traders.deltas.iter().filter(|x| x.operation == Operation::Create) {
+        if let Some((key, _user)) = delta.key.rsplit_once(':') {
+            store.add(delta.ordinal, format!("traders:{}", key), BigInt::one());
+        }
+    }
+}
+
+#[substreams::handlers::store]
+fn store_raydium_amm_candle_opens(trades: RaydiumAmmTrades, last_block_time: Deltas<DeltaInt64>, store: StoreSetIfNotExistsFloat64) {
+    for prefix in _closed_candle_prefixes(&trades, &last_block_time) {
+        store.delete_prefix(0, &prefix);
+    }
+    for (i, trade) in trades.trades.iter().enumerate() {
+        let price = match trade.price {
+            Some(price) => price,
+            None => continue,
+        };
+        for (_, _, key) in candle_keys(&trade.amm, trades.block_time) {
+            store.set_if_not_exists(i as u64, key, &price);
+        }
+    }
+}
+
+#[substreams::handlers::store]
+fn store_raydium_amm_candle_closes(trades: RaydiumAmmTrades, last_block_time: Deltas<DeltaInt64>, store: StoreSetFloat64) {
+    for prefix in _closed_candle_prefixes(&trades, &last_block_time) {
+        store.delete_prefix(0, &prefix);
+    }
+    for (i, trade) in trades.trades.iter().enumerate() {
+        let price = match trade.price {
+            Some(price) => price,
+            None => continue,
+        };
+        for (_, _, key) in candle_keys(&trade.amm, trades.block_time) {
+            store.set(i as u64, key, &price);
+        }
+    }
+}
+
+#[substreams::handlers::store]
+fn store_raydium_amm_candle_highs(trades: RaydiumAmmTrades, last_block_time: Deltas<DeltaInt64>, store: StoreMaxFloat64) {
+    for prefix in _closed_candle_prefixes(&trades, &last_block_time) {
+        store.delete_prefix(0, &prefix);
+    }
+    for (i, trade) in trades.trades.iter().enumerate() {
+        let price = match trade.price {
+            Some(price) => price,
+            None => continue,
+        };
+        for (_, _, key) in candle_keys(&trade.amm, trades.block_time) {
+            store.max(i as u64, key, price);
+        }
+    }
+}
+
+#[substreams::handlers::store]
+fn store_raydium_amm_candle_lows(trades: RaydiumAmmTrades, last_block_time: Deltas<DeltaInt64>, store: StoreMinFloat64) {
+    for prefix in _closed_candle_prefixes(&trades, &last_block_time) {
+        store.delete_prefix(0, &prefix);
+    }
+    for (i, trade) in trades.trades.iter().enumerate() {
+        let price = match trade.price {
+            Some(price) => price,
+            None => continue,
+        };
+        for (_, _, key) in candle_keys(&trade.amm, trades.block_time) {
+            store.min(i as u64, key, price);
+        }
+    }
+}
+
+fn _closed_candle_prefixes(trades: &RaydiumAmmTrades, last_block_time: &Deltas<DeltaInt64>) -> Vec<String> {
+    let previous_block_time = last_block_time.deltas.iter()
+        .find(|x| x.key == LAST_BLOCK_TIME_KEY && x.operation == Operation::Update)
+        .map(|x| x.old_value);
+    closed_candle_prefixes(previous_block_time, trades.block_time)
 }
 
-pub fn parse_block(block: &Block) -> Vec<RaydiumAmmTransactionEvents> {
+/// 1m, 5m, 1h and 1d candles of the pools traded in the block, as of the end of the block.
+#[substreams::handlers::map]
+fn raydium_amm_candles(
+    trades: RaydiumAmmTrades,
+    opens: StoreGetFloat64,
+    highs: StoreGetFloat64,
+    lows: StoreGetFloat64,
+    closes: StoreGetFloat64,
+    totals: StoreGetBigInt,
+) -> Result<RaydiumAmmCandles, Error> {
+    let mut candles: BTreeMap<String, Candle> = BTreeMap::new();
+    for trade in trades.trades.iter() {
+        for (interval, start_time, key) in candle_keys(&trade.amm, trades.block_time) {
+            candles.entry(key).or_insert_with(|| Candle {
+                amm: trade.amm.clone(),
+                coin_mint: trade.coin_mint.clone(),
+                pc_mint: trade.pc_mint.clone(),
+                interval: interval.to_string(),
+                start_time,
+                ..Default::default()
+            });
+        }
+    }
+    for (key, candle) in candles.iter_mut() {
+        candle.open = opens.get_last(key).unwrap_or_default();
+        candle.high = highs.get_last(key).unwrap_or_default();
+        candle.low = lows.get_last(key).unwrap_or_default();
+        candle.close = closes.get_last(key).unwrap_or_default();
+        candle.volume_coin = totals.get_last(format!("volume_coin:{}", key)).unwrap_or_else(BigInt::zero).to_string();
+        candle.volume_pc = totals.get_last(format!("volume_pc:{}", key)).unwrap_or_else(BigInt::zero).to_string();
+        candle.trades = totals.get_last(format!("trades:{}", key)).map_or(0, |x| x.to_u64());
+        candle.traders = totals.get_last(format!("traders:{}", key)).map_or(0, |x| x.to_u64());
+    }
+    Ok(RaydiumAmmCandles {
+        slot: trades.slot,
+        block_time: trades.block_time,
+        candles: candles.into_values().collect(),
+    })
+}
+
+fn _block_events(block: &Block, params: &Params) -> RaydiumAmmBlockEvents {
+    let transactions = parse_block(block, params);
+    RaydiumAmmBlockEvents {
+        slot: block.slot,
+        block_time: block.block_time.as_ref().map(|x| x.timestamp).unwrap_or_default(),
+        block_hash: block.blockhash.clone(),
+        transactions,
+    }
+}
+
+pub fn parse_block(block: &Block, params: &Params) -> Vec<RaydiumAmmTransactionEvents> {
+    let include_failed = params.get_bool("include_failed");
+    let include_priority_fee = params.get_bool("include_priority_fee");
+    let filter = Filter::new(params);
     let mut block_events: Vec<RaydiumAmmTransactionEvents> = Vec::new();
-    for transaction in block.transactions.iter() {
-        if let Ok(events) = parse_transaction(transaction) {
-            if !events.is_empty() {
+    for (i, transaction) in block.transactions.iter().enumerate() {
+        let failed = transaction.meta.as_ref().map_or(false, |x| x.err.is_some());
+        if failed && !include_failed {
+            continue;
+        }
+        let signature = utils::transaction::get_signature(&transaction);
+        let result = if failed {
+            Ok(parse_failed_transaction(transaction, &filter))
+        } else {
+            parse_transaction(transaction, &filter)
+        };
+        match result {
+            Ok(mut events) if !events.is_empty() => {
+                if include_priority_fee {
+                    attach_priority_fee(&mut events, transaction);
+                }
                 block_events.push(RaydiumAmmTransactionEvents {
-                    signature: utils::transaction::get_signature(&transaction),
+                    signature,
+                    transaction_index: i as u32,
+                    envelope: Some(get_transaction_envelope(transaction)),
                     events,
                 });
-            }
+            },
+            Ok(_) => (),
+            Err(error) => substreams::log::println(format!("Failed to process transaction {}: {}", signature, error)),
         }
     }
     block_events
 }
 
-pub fn parse_transaction(transaction: &ConfirmedTransaction) -> Result<Vec<RaydiumAmmEvent>, Error> {
-    if let Some(_) = transaction.meta.as_ref().unwrap().err {
+pub fn parse_transaction(transaction: &ConfirmedTransaction, filter: &Filter) -> Result<Vec<RaydiumAmmEvent>, ParseError> {
+    if let Some(_) = transaction.meta.as_ref().ok_or(ParseError::MissingTransactionMeta)?.err {
         return Ok(Vec::new());
     }
 
     let mut events: Vec<RaydiumAmmEvent> = Vec::new();
 
-    let mut context = get_context(transaction)?;
-    let instructions = get_structured_instructions(transaction)?;
-    for instruction in instructions.flattened().iter() {
+    let mut context = get_context(transaction).map_err(|x| ParseError::TransactionContext(x.to_string()))?;
+    let instructions = get_structured_instructions(transaction).map_err(|x| ParseError::TransactionContext(x.to_string()))?;
+    let flattened = instructions.flattened();
+    for (i, instruction) in flattened.iter().enumerate() {
         context.update_balance(&instruction.instruction);
         if instruction.program_id() != RAYDIUM_AMM_PROGRAM_ID {
             continue;
         }
 
+        let call_path = get_call_path(instruction, &flattened);
         match parse_instruction(&instruction, &context) {
-            Ok(Some(event)) => {
+            Ok(Some(event)) if filter.matches(&event) => {
                 events.push(RaydiumAmmEvent {
+                    instruction_index: i as u32,
+                    stack_depth: stack_depth(instruction),
+                    top_instruction_program_id: call_path.top_instruction_program_id,
+                    parent_instruction_program_id: call_path.parent_instruction_program_id,
+                    top_instruction_index: call_path.top_instruction_index,
+                    parent_instruction_index: call_path.parent_instruction_index,
                     event: Some(event),
                 })
             }
-            Ok(None) => (),
+            Ok(_) => (),
             Err(error) => substreams::log::println(format!("Failed to process instruction of transaction {}: {}", &context.signature, error))
         }
     }
     Ok(events)
 }
 
+/// Sets the priority fee of the transaction on its swap events.
+fn attach_priority_fee(events: &mut [RaydiumAmmEvent], transaction: &ConfirmedTransaction) {
+    let priority_fee = get_compute_budget(transaction).priority_fee;
+    for event in events.iter_mut() {
+        if let Some(Event::Swap(swap)) = event.event.as_mut() {
+            swap.priority_fee = Some(priority_fee);
+        }
+    }
+}
+
+/// Parses a failed transaction into a `FailedInstruction` event, if it invoked the Raydium AMM program.
+pub fn parse_failed_transaction(transaction: &ConfirmedTransaction, filter: &Filter) -> Vec<RaydiumAmmEvent> {
+    if !is_program_invoked(transaction, &RAYDIUM_AMM_PROGRAM_ID.to_string()) {
+        return Vec::new();
+    }
+    get_failed_instruction(transaction, custom_error_name)
+        .map(|x| RaydiumAmmEvent {
+            instruction_index: flattened_index(transaction, x.instruction_index),
+            stack_depth: 0,
+            top_instruction_program_id: None,
+            parent_instruction_program_id: None,
+            top_instruction_index: None,
+            parent_instruction_index: None,
+            event: Some(Event::FailedInstruction(x)),
+        })
+        .filter(|x| x.event.as_ref().map_or(false, |x| filter.matches(x)))
+        .into_iter()
+        .collect()
+}
+
+/// Name of a custom error code raised by the Raydium AMM program or one of the programs it invokes.
+pub fn custom_error_name(program_id: &str, code: u32) -> Option<String> {
+    if program_id == RAYDIUM_AMM_PROGRAM_ID.to_string() {
+        amm_error_name(code)
+    } else {
+        spl_token_substream::custom_error_name(program_id, code)
+    }
+}
+
 pub fn parse_instruction<'a>(
     instruction: &StructuredInstruction<'a>,
     context: &TransactionContext
-) -> Result<Option<Event>, String> {
+) -> Result<Option<Event>, ParseError> {
     if instruction.program_id() != RAYDIUM_AMM_PROGRAM_ID {
-        return Err("Instruction does not originate from Raydium AMM Program.".into());
+        return Err(ParseError::UnexpectedProgram(instruction.program_id().to_string()));
     }
-    let unpacked = AmmInstruction::unpack(&instruction.data())?;
+    let unpacked = match AmmInstruction::unpack(&instruction.data()) {
+        Ok(unpacked) => unpacked,
+        Err(_) => return Ok(Some(Event::UnknownInstruction(get_unknown_instruction(instruction, 1)))),
+    };
     match unpacked {
         AmmInstruction::SwapBaseIn(_) |
         AmmInstruction::SwapBaseOut(_) => {
@@ -99,32 +440,32 @@ pub fn parse_instruction<'a>(
             let event = _parse_withdraw_pnl_instruction(instruction, context)?;
             Ok(Some(Event::WithdrawPnl(event)))
         }
-        _ => Ok(None),
+        // Admin and deprecated instructions, kept raw.
+        _ => Ok(Some(Event::UnknownInstruction(get_unknown_instruction(instruction, 1)))),
     }
 }
 
 fn _parse_swap_instruction<'a>(
     instruction: &StructuredInstruction<'a>,
     context: &TransactionContext,
-) -> Result<SwapEvent, String> {
-    let amm = instruction.accounts()[1].to_string();
-    let user = instruction.accounts().last().unwrap().to_string();
+) -> Result<SwapEvent, ParseError> {
+    let amm = _account(instruction, 1)?;
+    let user = _account(instruction, instruction.accounts().len().saturating_sub(1))?;
 
-    let instructions_len = instruction.inner_instructions().len();
-    let transfer_in = spl_token_substream::parse_transfer_instruction(&instruction.inner_instructions()[instructions_len - 2], context)?;
-    let transfer_out = spl_token_substream::parse_transfer_instruction(&instruction.inner_instructions()[instructions_len - 1], context)?;
+    let transfer_in = spl_token_substream::parse_transfer_instruction(&_inner_instruction(instruction, 2)?, context)?;
+    let transfer_out = spl_token_substream::parse_transfer_instruction(&_inner_instruction(instruction, 1)?, context)?;
 
     let amount_in = transfer_in.amount;
     let amount_out = transfer_out.amount;
-    let mint_in = transfer_in.source.as_ref().unwrap().mint.clone();
-    let mint_out = transfer_out.source.as_ref().unwrap().mint.clone();
+    let mint_in = _source(&transfer_in.source)?.mint.clone();
+    let mint_out = _source(&transfer_out.source)?.mint.clone();
 
-    let user_pre_balance_in = transfer_in.source.unwrap().pre_balance;
-    let user_pre_balance_out = transfer_out.destination.unwrap().pre_balance;
+    let user_pre_balance_in = _source(&transfer_in.source)?.pre_balance;
+    let user_pre_balance_out = _destination(&transfer_out.destination)?.pre_balance;
 
     let delta = if instruction.accounts().len() == 17 { 0 } else { 1 };
-    let coin_mint = context.get_token_account(&instruction.accounts()[4 + delta]).unwrap().mint.to_string();
-    let pc_mint = context.get_token_account(&instruction.accounts()[5 + delta]).unwrap().mint.to_string();
+    let coin_mint = _token_account_mint(instruction, context, 4 + delta)?;
+    let pc_mint = _token_account_mint(instruction, context, 5 + delta)?;
 
     let direction = (if mint_out == coin_mint { "coin" } else { "pc" }).to_string();
 
@@ -152,6 +493,7 @@ fn _parse_swap_instruction<'a>(
         pc_mint,
         user_pre_balance_in,
         user_pre_balance_out,
+        priority_fee: None,
     })
 }
 
@@ -159,24 +501,23 @@ fn _parse_initialize_instruction<'a>(
     instruction: &StructuredInstruction<'a>,
     context: &TransactionContext,
     nonce: u8,
-) -> Result<InitializeEvent, String> {
-    let amm = instruction.accounts()[4].to_string();
-    let user = instruction.accounts()[17].to_string();
+) -> Result<InitializeEvent, ParseError> {
+    let amm = _account(instruction, 4)?;
+    let user = _account(instruction, 17)?;
 
-    let instructions_len = instruction.inner_instructions().len();
-    let coin_transfer = spl_token_substream::parse_transfer_instruction(&instruction.inner_instructions()[instructions_len - 3], context)?;
-    let pc_transfer = spl_token_substream::parse_transfer_instruction(&instruction.inner_instructions()[instructions_len - 2], context)?;
-    let lp_mint_to = spl_token_substream::parse_mint_to_instruction(&instruction.inner_instructions()[instructions_len - 1], context)?;
+    let coin_transfer = spl_token_substream::parse_transfer_instruction(&_inner_instruction(instruction, 3)?, context)?;
+    let pc_transfer = spl_token_substream::parse_transfer_instruction(&_inner_instruction(instruction, 2)?, context)?;
+    let lp_mint_to = spl_token_substream::parse_mint_to_instruction(&_inner_instruction(instruction, 1)?, context)?;
 
     let pc_init_amount = pc_transfer.amount;
     let coin_init_amount = coin_transfer.amount;
     let lp_init_amount = lp_mint_to.amount;
-    let pc_mint = pc_transfer.source.as_ref().unwrap().mint.clone();
-    let coin_mint = coin_transfer.source.as_ref().unwrap().mint.clone();
+    let pc_mint = _source(&pc_transfer.source)?.mint.clone();
+    let coin_mint = _source(&coin_transfer.source)?.mint.clone();
     let lp_mint = lp_mint_to.mint;
 
-    let user_pc_pre_balance = pc_transfer.source.unwrap().pre_balance;
-    let user_coin_pre_balance = coin_transfer.source.unwrap().pre_balance;
+    let user_pc_pre_balance = _source(&pc_transfer.source)?.pre_balance;
+    let user_coin_pre_balance = _source(&coin_transfer.source)?.pre_balance;
 
     let market = match parse_raydium_log(instruction) {
         Ok(RayLog::Init(init)) => Some(Pubkey(init.market).to_string()),
@@ -202,24 +543,23 @@ fn _parse_initialize_instruction<'a>(
 fn _parse_deposit_instruction<'a>(
     instruction: &StructuredInstruction<'a>,
     context: &TransactionContext
-) -> Result<DepositEvent, String> {
-    let amm = instruction.accounts()[1].to_string();
-    let user = instruction.accounts()[12].to_string();
+) -> Result<DepositEvent, ParseError> {
+    let amm = _account(instruction, 1)?;
+    let user = _account(instruction, 12)?;
 
-    let instructions_len = instruction.inner_instructions().len();
-    let pc_transfer = spl_token_substream::parse_transfer_instruction(&instruction.inner_instructions()[instructions_len - 2], context)?;
-    let coin_transfer = spl_token_substream::parse_transfer_instruction(&instruction.inner_instructions()[instructions_len - 3], context)?;
-    let lp_mint_to = spl_token_substream::parse_mint_to_instruction(&instruction.inner_instructions()[instructions_len - 1], context)?;
+    let pc_transfer = spl_token_substream::parse_transfer_instruction(&_inner_instruction(instruction, 2)?, context)?;
+    let coin_transfer = spl_token_substream::parse_transfer_instruction(&_inner_instruction(instruction, 3)?, context)?;
+    let lp_mint_to = spl_token_substream::parse_mint_to_instruction(&_inner_instruction(instruction, 1)?, context)?;
 
     let pc_amount = pc_transfer.amount;
     let coin_amount = coin_transfer.amount;
     let lp_amount = lp_mint_to.amount;
-    let pc_mint = pc_transfer.source.as_ref().unwrap().mint.clone();
-    let coin_mint = coin_transfer.source.as_ref().unwrap().mint.clone();
+    let pc_mint = _source(&pc_transfer.source)?.mint.clone();
+    let coin_mint = _source(&coin_transfer.source)?.mint.clone();
     let lp_mint = lp_mint_to.mint;
 
-    let user_pc_pre_balance = pc_transfer.source.unwrap().pre_balance;
-    let user_coin_pre_balance = coin_transfer.source.unwrap().pre_balance;
+    let user_pc_pre_balance = _source(&pc_transfer.source)?.pre_balance;
+    let user_coin_pre_balance = _source(&coin_transfer.source)?.pre_balance;
 
     let (pool_pc_amount, pool_coin_amount, pool_lp_amount) = match parse_raydium_log(instruction) {
         Ok(RayLog::Deposit(deposit)) => {
@@ -248,24 +588,23 @@ fn _parse_deposit_instruction<'a>(
 fn _parse_withdraw_instruction<'a>(
     instruction: &StructuredInstruction<'a>,
     context: &TransactionContext,
-) -> Result<WithdrawEvent, String> {
-    let amm = instruction.accounts()[1].to_string();
-    let user = instruction.accounts()[16].to_string();
+) -> Result<WithdrawEvent, ParseError> {
+    let amm = _account(instruction, 1)?;
+    let user = _account(instruction, 16)?;
 
-    let instructions_len = instruction.inner_instructions().len();
-    let pc_transfer = spl_token_substream::parse_transfer_instruction(&instruction.inner_instructions()[instructions_len - 2], context)?;
-    let coin_transfer = spl_token_substream::parse_transfer_instruction(&instruction.inner_instructions()[instructions_len - 3], context)?;
-    let lp_burn = spl_token_substream::parse_burn_instruction(&instruction.inner_instructions()[instructions_len - 1], context)?;
+    let pc_transfer = spl_token_substream::parse_transfer_instruction(&_inner_instruction(instruction, 2)?, context)?;
+    let coin_transfer = spl_token_substream::parse_transfer_instruction(&_inner_instruction(instruction, 3)?, context)?;
+    let lp_burn = spl_token_substream::parse_burn_instruction(&_inner_instruction(instruction, 1)?, context)?;
 
     let pc_amount = pc_transfer.amount;
     let coin_amount = coin_transfer.amount;
     let lp_amount = lp_burn.amount;
-    let pc_mint = pc_transfer.source.unwrap().mint;
-    let coin_mint = coin_transfer.source.unwrap().mint;
-    let lp_mint = lp_burn.source.unwrap().mint;
+    let pc_mint = _source(&pc_transfer.source)?.mint.clone();
+    let coin_mint = _source(&coin_transfer.source)?.mint.clone();
+    let lp_mint = _source(&lp_burn.source)?.mint.clone();
 
-    let user_pc_pre_balance = pc_transfer.destination.unwrap().pre_balance;
-    let user_coin_pre_balance = coin_transfer.destination.unwrap().pre_balance;
+    let user_pc_pre_balance = _destination(&pc_transfer.destination)?.pre_balance;
+    let user_coin_pre_balance = _destination(&coin_transfer.destination)?.pre_balance;
 
     let (pool_pc_amount, pool_coin_amount, pool_lp_amount) = match parse_raydium_log(instruction) {
         Ok(RayLog::Withdraw(withdraw)) => {
@@ -294,19 +633,19 @@ fn _parse_withdraw_instruction<'a>(
 fn _parse_withdraw_pnl_instruction(
     instruction: &StructuredInstruction,
     context: &TransactionContext,
-) -> Result<WithdrawPnlEvent, String> {
-    let amm = instruction.accounts()[1].to_string();
-    let user = instruction.accounts()[9].to_string();
+) -> Result<WithdrawPnlEvent, ParseError> {
+    let amm = _account(instruction, 1)?;
+    let user = _account(instruction, 9)?;
 
     let instructions_len = instruction.inner_instructions().len();
     if instructions_len == 2 || instructions_len == 3 {
-        let pc_transfer = spl_token_substream::parse_transfer_instruction(&instruction.inner_instructions()[instructions_len - 1], context)?;
-        let coin_transfer = spl_token_substream::parse_transfer_instruction(&instruction.inner_instructions()[instructions_len - 2], context)?;
+        let pc_transfer = spl_token_substream::parse_transfer_instruction(&_inner_instruction(instruction, 1)?, context)?;
+        let coin_transfer = spl_token_substream::parse_transfer_instruction(&_inner_instruction(instruction, 2)?, context)?;
 
         let pc_amount = Some(pc_transfer.amount);
         let coin_amount = Some(coin_transfer.amount);
-        let pc_mint = Some(pc_transfer.source.unwrap().mint);
-        let coin_mint = Some(coin_transfer.source.unwrap().mint);
+        let pc_mint = Some(_source(&pc_transfer.source)?.mint.clone());
+        let coin_mint = Some(_source(&coin_transfer.source)?.mint.clone());
 
         return Ok(WithdrawPnlEvent {
             amm,
@@ -328,20 +667,53 @@ fn _parse_withdraw_pnl_instruction(
     }
 }
 
-fn parse_raydium_log(instruction: &StructuredInstruction) -> Result<RayLog, Error> {
-    let re = regex::Regex::new(r"ray_log: (.+)").unwrap();
-    let log_message = instruction.logs().as_ref().context("Failed to parse logs due to truncation")?.iter().rev().find_map(|log| {
+fn parse_raydium_log(instruction: &StructuredInstruction) -> Result<RayLog, ParseError> {
+    let log_message = instruction.logs().as_ref().ok_or(ParseError::LogTruncated)?.iter().rev().find_map(|log| {
         if let Log::Program(program_log) = log {
-            Some(program_log.message().unwrap())
+            program_log.message().ok()
         } else {
             None
         }
     });
     match log_message {
-        Some(message) => match re.captures(message.as_str()) {
-            Some(captures) => Ok(decode_ray_log(&captures[1])),
-            None => return Err(anyhow!("Failed to capture log message")),
+        Some(message) => match message.split_once("ray_log: ").map(|(_, x)| x).filter(|x| !x.is_empty()) {
+            Some(ray_log) => decode_ray_log(ray_log).map_err(|x| ParseError::InvalidLog(x.to_string())),
+            None => return Err(ParseError::InvalidLog("failed to capture log message".into())),
         },
-        None => return Err(anyhow!("Log message not found")),
+        None => return Err(ParseError::InvalidLog("log message not found".into())),
     }
 }
+
+fn _account(instruction: &StructuredInstruction, index: usize) -> Result<String, ParseError> {
+    instruction.accounts().get(index).map(|x| x.to_string()).ok_or(ParseError::MissingAccount(index))
+}
+
+fn _token_account_mint(
+    instruction: &StructuredInstruction,
+    context: &TransactionContext,
+    index: usize,
+) -> Result<String, ParseError> {
+    let accounts = instruction.accounts();
+    let address = accounts.get(index).ok_or(ParseError::MissingAccount(index))?;
+    context.get_token_account(address).map(|x| x.mint.to_string()).ok_or_else(|| ParseError::MissingTokenAccount(address.to_string()))
+}
+
+/// Returns the inner instruction at `position`, counting back from the last one.
+fn _inner_instruction<'a>(
+    instruction: &StructuredInstruction<'a>,
+    position: usize,
+) -> Result<Rc<StructuredInstruction<'a>>, ParseError> {
+    let inner_instructions = instruction.inner_instructions();
+    inner_instructions.len().checked_sub(position)
+        .and_then(|i| inner_instructions.get(i))
+        .cloned()
+        .ok_or(ParseError::InnerInstructionMismatch("not enough inner instructions"))
+}
+
+fn _source(account: &Option<TokenAccount>) -> Result<&TokenAccount, ParseError> {
+    account.as_ref().ok_or(ParseError::InnerInstructionMismatch("token instruction has no source account"))
+}
+
+fn _destination(account: &Option<TokenAccount>) -> Result<&TokenAccount, ParseError> {
+    account.as_ref().ok_or(ParseError::InnerInstructionMismatch("token instruction has no destination account"))
+}
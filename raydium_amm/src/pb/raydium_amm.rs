@@ -2,8 +2,14 @@
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct RaydiumAmmBlockEvents {
+    #[prost(uint64, tag="1")]
+    pub slot: u64,
     #[prost(message, repeated, tag="2")]
     pub transactions: ::prost::alloc::vec::Vec<RaydiumAmmTransactionEvents>,
+    #[prost(int64, tag="3")]
+    pub block_time: i64,
+    #[prost(string, tag="4")]
+    pub block_hash: ::prost::alloc::string::String,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -12,11 +18,28 @@ pub struct RaydiumAmmTransactionEvents {
     pub signature: ::prost::alloc::string::String,
     #[prost(message, repeated, tag="2")]
     pub events: ::prost::alloc::vec::Vec<RaydiumAmmEvent>,
+    #[prost(uint32, tag="3")]
+    pub transaction_index: u32,
+    #[prost(message, optional, tag="4")]
+    pub envelope: ::core::option::Option<::solana_substreams_common::pb::common::TransactionEnvelope>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct RaydiumAmmEvent {
-    #[prost(oneof="raydium_amm_event::Event", tags="1, 2, 3, 4, 5")]
+    #[prost(uint32, tag="7")]
+    pub instruction_index: u32,
+    /// Depth in the invocation stack, 0 for top level instructions.
+    #[prost(uint32, tag="8")]
+    pub stack_depth: u32,
+    #[prost(string, optional, tag="9")]
+    pub top_instruction_program_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag="10")]
+    pub parent_instruction_program_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(uint32, optional, tag="11")]
+    pub top_instruction_index: ::core::option::Option<u32>,
+    #[prost(uint32, optional, tag="12")]
+    pub parent_instruction_index: ::core::option::Option<u32>,
+    #[prost(oneof="raydium_amm_event::Event", tags="1, 2, 3, 4, 5, 6, 13")]
     pub event: ::core::option::Option<raydium_amm_event::Event>,
 }
 /// Nested message and enum types in `RaydiumAmmEvent`.
@@ -34,6 +57,10 @@ pub mod raydium_amm_event {
         WithdrawPnl(super::WithdrawPnlEvent),
         #[prost(message, tag="5")]
         Swap(super::SwapEvent),
+        #[prost(message, tag="6")]
+        FailedInstruction(::solana_substreams_common::pb::common::FailedInstruction),
+        #[prost(message, tag="13")]
+        UnknownInstruction(::solana_substreams_common::pb::common::UnknownInstruction),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -169,5 +196,153 @@ pub struct SwapEvent {
     pub user_pre_balance_in: ::core::option::Option<u64>,
     #[prost(uint64, optional, tag="13")]
     pub user_pre_balance_out: ::core::option::Option<u64>,
+    /// Priority fee of the transaction in lamports, set with the `include_priority_fee` param.
+    #[prost(uint64, optional, tag="14")]
+    pub priority_fee: ::core::option::Option<u64>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RaydiumAmmPoolReserves {
+    #[prost(uint64, tag="1")]
+    pub slot: u64,
+    #[prost(int64, tag="2")]
+    pub block_time: i64,
+    #[prost(message, repeated, tag="3")]
+    pub pools: ::prost::alloc::vec::Vec<PoolReserves>,
+}
+/// Reserves of a pool after the last event of the block touching it.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PoolReserves {
+    #[prost(string, tag="1")]
+    pub amm: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub coin_mint: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub pc_mint: ::prost::alloc::string::String,
+    #[prost(uint64, tag="4")]
+    pub coin_reserve: u64,
+    #[prost(uint64, tag="5")]
+    pub pc_reserve: u64,
+    /// Unset when the events of the block don't reveal it, such as swaps.
+    #[prost(uint64, optional, tag="6")]
+    pub lp_supply: ::core::option::Option<u64>,
+    #[prost(uint32, optional, tag="7")]
+    pub coin_decimals: ::core::option::Option<u32>,
+    #[prost(uint32, optional, tag="8")]
+    pub pc_decimals: ::core::option::Option<u32>,
+    #[prost(uint64, tag="9")]
+    pub slot: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RaydiumAmmPoolPrices {
+    #[prost(uint64, tag="1")]
+    pub slot: u64,
+    #[prost(int64, tag="2")]
+    pub block_time: i64,
+    #[prost(message, repeated, tag="3")]
+    pub pools: ::prost::alloc::vec::Vec<PoolPrice>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PoolPrice {
+    #[prost(string, tag="1")]
+    pub amm: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub coin_mint: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub pc_mint: ::prost::alloc::string::String,
+    #[prost(uint64, tag="4")]
+    pub coin_reserve: u64,
+    #[prost(uint64, tag="5")]
+    pub pc_reserve: u64,
+    /// Latest known LP supply of the pool.
+    #[prost(uint64, optional, tag="6")]
+    pub lp_supply: ::core::option::Option<u64>,
+    /// Price of the coin in pc, adjusted by their decimals. Unset for an empty pool or unknown decimals.
+    #[prost(double, optional, tag="7")]
+    pub mid_price: ::core::option::Option<f64>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RaydiumAmmTrades {
+    #[prost(uint64, tag="1")]
+    pub slot: u64,
+    #[prost(int64, tag="2")]
+    pub block_time: i64,
+    #[prost(message, repeated, tag="3")]
+    pub trades: ::prost::alloc::vec::Vec<Trade>,
+}
+/// Swap priced at its execution price.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Trade {
+    #[prost(string, tag="1")]
+    pub amm: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub coin_mint: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub pc_mint: ::prost::alloc::string::String,
+    #[prost(string, tag="4")]
+    pub user: ::prost::alloc::string::String,
+    #[prost(uint64, tag="5")]
+    pub coin_amount: u64,
+    #[prost(uint64, tag="6")]
+    pub pc_amount: u64,
+    /// Price of the coin in pc, adjusted by their decimals. Unset when a mint's decimals are
+    /// unknown or an amount is zero.
+    #[prost(double, optional, tag="7")]
+    pub price: ::core::option::Option<f64>,
+    #[prost(string, tag="8")]
+    pub signature: ::prost::alloc::string::String,
+    #[prost(uint32, tag="9")]
+    pub transaction_index: u32,
+    #[prost(uint32, tag="10")]
+    pub instruction_index: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RaydiumAmmCandles {
+    #[prost(uint64, tag="1")]
+    pub slot: u64,
+    #[prost(int64, tag="2")]
+    pub block_time: i64,
+    #[prost(message, repeated, tag="3")]
+    pub candles: ::prost::alloc::vec::Vec<Candle>,
+}
+/// Candle of a pool over the interval starting at `start_time`, as of the end of the block.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Candle {
+    #[prost(string, tag="1")]
+    pub amm: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub coin_mint: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub pc_mint: ::prost::alloc::string::String,
+    /// One of `1m`, `5m`, `1h` or `1d`.
+    #[prost(string, tag="4")]
+    pub interval: ::prost::alloc::string::String,
+    #[prost(int64, tag="5")]
+    pub start_time: i64,
+    /// Prices are zero when none of the candle trades is priced.
+    #[prost(double, tag="6")]
+    pub open: f64,
+    #[prost(double, tag="7")]
+    pub high: f64,
+    #[prost(double, tag="8")]
+    pub low: f64,
+    #[prost(double, tag="9")]
+    pub close: f64,
+    /// Volumes in base units, as decimal strings since they can overflow a uint64.
+    #[prost(string, tag="10")]
+    pub volume_coin: ::prost::alloc::string::String,
+    #[prost(string, tag="11")]
+    pub volume_pc: ::prost::alloc::string::String,
+    #[prost(uint64, tag="12")]
+    pub trades: u64,
+    #[prost(uint64, tag="13")]
+    pub traders: u64,
 }
 // @@protoc_insertion_point(module)
@@ -1,4 +1 @@
-use substreams_solana_utils::pubkey::Pubkey;
-use substreams_solana::b58;
-
-pub const RAYDIUM_AMM_PROGRAM_ID: Pubkey = Pubkey(b58!("675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8"));
+pub use solana_substreams_common::programs::RAYDIUM_AMM_PROGRAM_ID;
@@ -0,0 +1,250 @@
+//! Error types
+
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+use thiserror::Error;
+
+/// Errors that may be returned by the Raydium AMM program.
+#[derive(Clone, Debug, Eq, Error, FromPrimitive, PartialEq)]
+pub enum AmmError {
+    /// 0
+    #[error("AlreadyInUse")]
+    AlreadyInUse,
+
+    /// 1
+    #[error("InvalidProgramAddress")]
+    InvalidProgramAddress,
+
+    /// 2
+    #[error("ExpectedMint")]
+    ExpectedMint,
+
+    /// 3
+    #[error("ExpectedAccount")]
+    ExpectedAccount,
+
+    /// 4
+    #[error("InvalidCoinVault")]
+    InvalidCoinVault,
+
+    /// 5
+    #[error("InvalidPCVault")]
+    InvalidPCVault,
+
+    /// 6
+    #[error("InvalidTokenLP")]
+    InvalidTokenLP,
+
+    /// 7
+    #[error("InvalidDestTokenCoin")]
+    InvalidDestTokenCoin,
+
+    /// 8
+    #[error("InvalidDestTokenPC")]
+    InvalidDestTokenPC,
+
+    /// 9
+    #[error("InvalidPoolMint")]
+    InvalidPoolMint,
+
+    /// 10
+    #[error("InvalidOpenOrders")]
+    InvalidOpenOrders,
+
+    /// 11
+    #[error("InvalidSerumMarket")]
+    InvalidSerumMarket,
+
+    /// 12
+    #[error("InvalidSerumProgram")]
+    InvalidSerumProgram,
+
+    /// 13
+    #[error("InvalidTargetOrders")]
+    InvalidTargetOrders,
+
+    /// 14
+    #[error("InvalidWithdrawQueue")]
+    InvalidWithdrawQueue,
+
+    /// 15
+    #[error("InvalidTempLp")]
+    InvalidTempLp,
+
+    /// 16
+    #[error("InvalidCoinMint")]
+    InvalidCoinMint,
+
+    /// 17
+    #[error("InvalidPCMint")]
+    InvalidPCMint,
+
+    /// 18
+    #[error("InvalidOwner")]
+    InvalidOwner,
+
+    /// 19
+    #[error("InvalidSupply")]
+    InvalidSupply,
+
+    /// 20
+    #[error("InvalidDelegate")]
+    InvalidDelegate,
+
+    /// 21
+    #[error("Invalid Sign Account")]
+    InvalidSignAccount,
+
+    /// 22
+    #[error("InvalidStatus")]
+    InvalidStatus,
+
+    /// 23
+    #[error("Invalid instruction")]
+    InvalidInstruction,
+
+    /// 24
+    #[error("Wrong accounts number")]
+    WrongAccountsNumber,
+
+    /// 25
+    #[error("Withdraw_transfer is busy")]
+    WithdrawTransferBusy,
+
+    /// 26
+    #[error("WithdrawQueue is full")]
+    WithdrawQueueFull,
+
+    /// 27
+    #[error("WithdrawQueue is empty")]
+    WithdrawQueueEmpty,
+
+    /// 28
+    #[error("Params Set is invalid")]
+    InvalidParamsSet,
+
+    /// 29
+    #[error("InvalidInput")]
+    InvalidInput,
+
+    /// 30
+    #[error("instruction exceeds desired slippage limit")]
+    ExceededSlippage,
+
+    /// 31
+    #[error("CalculationExRateFailure")]
+    CalculationExRateFailure,
+
+    /// 32
+    #[error("Checked_Sub Overflow")]
+    CheckedSubOverflow,
+
+    /// 33
+    #[error("Checked_Add Overflow")]
+    CheckedAddOverflow,
+
+    /// 34
+    #[error("Checked_Mul Overflow")]
+    CheckedMulOverflow,
+
+    /// 35
+    #[error("Checked_Div Overflow")]
+    CheckedDivOverflow,
+
+    /// 36
+    #[error("Empty Funds")]
+    CheckedEmptyFunds,
+
+    /// 37
+    #[error("Calc pnl error")]
+    CalcPnlError,
+
+    /// 38
+    #[error("InvalidSplTokenProgram")]
+    InvalidSplTokenProgram,
+
+    /// 39
+    #[error("Take Pnl error")]
+    TakePnlError,
+
+    /// 40
+    #[error("Insufficient funds")]
+    InsufficientFunds,
+
+    /// 41
+    #[error("Conversion to u64 failed with an overflow or underflow")]
+    ConversionFailure,
+
+    /// 42
+    #[error("user token input does not match amm")]
+    InvalidUserToken,
+
+    /// 43
+    #[error("InvalidSrmMint")]
+    InvalidSrmMint,
+
+    /// 44
+    #[error("InvalidSrmToken")]
+    InvalidSrmToken,
+
+    /// 45
+    #[error("TooManyOpenOrders")]
+    TooManyOpenOrders,
+
+    /// 46
+    #[error("OrderAtSlotIsPlaced")]
+    OrderAtSlotIsPlaced,
+
+    /// 47
+    #[error("InvalidSysProgramAddress")]
+    InvalidSysProgramAddress,
+
+    /// 48
+    #[error("The provided fee does not match the program owner's constraints")]
+    InvalidFee,
+
+    /// 49
+    #[error("Repeat create amm about market")]
+    RepeatCreateAmm,
+
+    /// 50
+    #[error("Not allow Zero LP")]
+    NotAllowZeroLP,
+
+    /// 51
+    #[error("Token account has a close authority")]
+    InvalidCloseAuthority,
+
+    /// 52
+    #[error("Pool token mint has a freeze authority")]
+    InvalidFreezeAuthority,
+
+    /// 53
+    #[error("InvalidReferPCMint")]
+    InvalidReferPCMint,
+
+    /// 54
+    #[error("InvalidConfigAccount")]
+    InvalidConfigAccount,
+
+    /// 55
+    #[error("Repeat create config account")]
+    RepeatCreateConfigAccount,
+
+    /// 56
+    #[error("Market lotSize is too large")]
+    MarketLotSizeIsTooLarge,
+
+    /// 57
+    #[error("Init lp amount is too less(Because 10**lp_decimals amount lp will be locked)")]
+    InitLpAmountTooLess,
+
+    /// 58
+    #[error("Unknown Amm Error")]
+    UnknownAmmError,
+}
+
+/// Name of a Raydium AMM custom error code, if any.
+pub fn amm_error_name(code: u32) -> Option<String> {
+    AmmError::from_u32(code).map(|x| format!("{:?}", x))
+}
@@ -545,6 +545,9 @@ impl AmmInstruction {
                 let (param, rest) = Self::unpack_u8(rest)?;
                 match param {
                     0 | 1 => {
+                        if rest.len() < 32 {
+                            return Err("Invalid instruction data");
+                        }
                         let bytes: [u8; 32] = array_ref![rest, 0, 32].clone();
                             let owner = Some(Pubkey(bytes));
                         Self::UpdateConfigAccount(ConfigArgs {
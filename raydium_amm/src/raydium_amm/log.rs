@@ -24,14 +24,14 @@ pub enum RayLog {
 }
 
 impl LogType {
-    pub fn from_u8(log_type: u8) -> Self {
+    pub fn from_u8(log_type: u8) -> Option<Self> {
         match log_type {
-            0 => LogType::Init,
-            1 => LogType::Deposit,
-            2 => LogType::Withdraw,
-            3 => LogType::SwapBaseIn,
-            4 => LogType::SwapBaseOut,
-            _ => unreachable!(),
+            0 => Some(LogType::Init),
+            1 => Some(LogType::Deposit),
+            2 => Some(LogType::Withdraw),
+            3 => Some(LogType::SwapBaseIn),
+            4 => Some(LogType::SwapBaseOut),
+            _ => None,
         }
     }
 
@@ -128,28 +128,29 @@ pub struct SwapBaseOutLog {
     pub deduct_in: u64,
 }
 
-pub fn decode_ray_log(log: &str) -> RayLog {
-    let bytes = base64::decode(log).unwrap();
-    match LogType::from_u8(bytes[0]) {
+pub fn decode_ray_log(log: &str) -> Result<RayLog, &'static str> {
+    let bytes = base64::decode(log).map_err(|_| "Failed to decode ray_log base64.")?;
+    let log_type = bytes.first().and_then(|x| LogType::from_u8(*x)).ok_or("Unknown ray_log type.")?;
+    match log_type {
         LogType::Init => {
-            let log: InitLog = bincode::deserialize(&bytes).unwrap();
-            return RayLog::Init(log);
+            let log: InitLog = bincode::deserialize(&bytes).map_err(|_| "Failed to deserialize InitLog.")?;
+            return Ok(RayLog::Init(log));
         }
         LogType::Deposit => {
-            let log: DepositLog = bincode::deserialize(&bytes).unwrap();
-            return RayLog::Deposit(log);
+            let log: DepositLog = bincode::deserialize(&bytes).map_err(|_| "Failed to deserialize DepositLog.")?;
+            return Ok(RayLog::Deposit(log));
         }
         LogType::Withdraw => {
-            let log: WithdrawLog = bincode::deserialize(&bytes).unwrap();
-            return RayLog::Withdraw(log);
+            let log: WithdrawLog = bincode::deserialize(&bytes).map_err(|_| "Failed to deserialize WithdrawLog.")?;
+            return Ok(RayLog::Withdraw(log));
         }
         LogType::SwapBaseIn => {
-            let log: SwapBaseInLog = bincode::deserialize(&bytes).unwrap();
-            return RayLog::SwapBaseIn(log);
+            let log: SwapBaseInLog = bincode::deserialize(&bytes).map_err(|_| "Failed to deserialize SwapBaseInLog.")?;
+            return Ok(RayLog::SwapBaseIn(log));
         }
         LogType::SwapBaseOut => {
-            let log: SwapBaseOutLog = bincode::deserialize(&bytes).unwrap();
-            return RayLog::SwapBaseOut(log);
+            let log: SwapBaseOutLog = bincode::deserialize(&bytes).map_err(|_| "Failed to deserialize SwapBaseOutLog.")?;
+            return Ok(RayLog::SwapBaseOut(log));
         }
     }
 }
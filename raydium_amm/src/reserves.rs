@@ -0,0 +1,156 @@
+use std::collections::BTreeMap;
+
+use substreams_solana::pb::sf::solana::r#type::v1::Block;
+
+use spl_token_substream::decimals::MintDecimals;
+
+use crate::pb::raydium_amm::{PoolPrice, PoolReserves, RaydiumAmmTransactionEvents};
+use crate::pb::raydium_amm::raydium_amm_event::Event;
+
+/// End of block reserves of the pools touched by the events, ordered by AMM.
+///
+/// The ray_log of an instruction reports the pool amounts before it executes, so the reserves
+/// are those amounts moved by the instruction. The LP supply is known from initializations,
+/// deposits and withdrawals, and otherwise kept from an earlier event of the block.
+pub fn get_pool_reserves(block: &Block, transactions: &[RaydiumAmmTransactionEvents]) -> Vec<PoolReserves> {
+    let mut pools: BTreeMap<String, PoolReserves> = BTreeMap::new();
+    for transaction in transactions {
+        let mint_decimals = match block.transactions.get(transaction.transaction_index as usize) {
+            Some(confirmed_transaction) => MintDecimals::new(confirmed_transaction),
+            None => continue,
+        };
+        for event in transaction.events.iter().filter_map(|x| x.event.as_ref()) {
+            let mut reserves = match _event_reserves(event) {
+                Some(reserves) => reserves,
+                None => continue,
+            };
+            reserves.slot = block.slot;
+            reserves.coin_decimals = mint_decimals.get(&reserves.coin_mint);
+            reserves.pc_decimals = mint_decimals.get(&reserves.pc_mint);
+            if let Some(previous) = pools.get(&reserves.amm) {
+                reserves.lp_supply = reserves.lp_supply.or(previous.lp_supply);
+                reserves.coin_decimals = reserves.coin_decimals.or(previous.coin_decimals);
+                reserves.pc_decimals = reserves.pc_decimals.or(previous.pc_decimals);
+            }
+            pools.insert(reserves.amm.clone(), reserves);
+        }
+    }
+    pools.into_values().collect()
+}
+
+fn _event_reserves(event: &Event) -> Option<PoolReserves> {
+    let (amm, coin_mint, pc_mint, coin_reserve, pc_reserve, lp_supply) = match event {
+        Event::Initialize(initialize) => {
+            // The pool mints the square root of the initial amounts product as LP.
+            let lp_supply = _integer_sqrt(initialize.coin_init_amount as u128 * initialize.pc_init_amount as u128) as u64;
+            (&initialize.amm, &initialize.coin_mint, &initialize.pc_mint, initialize.coin_init_amount, initialize.pc_init_amount, Some(lp_supply))
+        },
+        Event::Deposit(deposit) => (
+            &deposit.amm,
+            &deposit.coin_mint,
+            &deposit.pc_mint,
+            deposit.pool_coin_amount?.saturating_add(deposit.coin_amount),
+            deposit.pool_pc_amount?.saturating_add(deposit.pc_amount),
+            deposit.pool_lp_amount.map(|x| x.saturating_add(deposit.lp_amount)),
+        ),
+        Event::Withdraw(withdraw) => (
+            &withdraw.amm,
+            &withdraw.coin_mint,
+            &withdraw.pc_mint,
+            withdraw.pool_coin_amount?.saturating_sub(withdraw.coin_amount),
+            withdraw.pool_pc_amount?.saturating_sub(withdraw.pc_amount),
+            withdraw.pool_lp_amount.map(|x| x.saturating_sub(withdraw.lp_amount)),
+        ),
+        Event::Swap(swap) => {
+            let (pool_coin_amount, pool_pc_amount) = (swap.pool_coin_amount?, swap.pool_pc_amount?);
+            // The direction is the side the user receives.
+            let (coin_reserve, pc_reserve) = if swap.direction == "coin" {
+                (pool_coin_amount.saturating_sub(swap.amount_out), pool_pc_amount.saturating_add(swap.amount_in))
+            } else {
+                (pool_coin_amount.saturating_add(swap.amount_in), pool_pc_amount.saturating_sub(swap.amount_out))
+            };
+            (&swap.amm, &swap.coin_mint, &swap.pc_mint, coin_reserve, pc_reserve, None)
+        },
+        _ => return None,
+    };
+    Some(PoolReserves {
+        amm: amm.clone(),
+        coin_mint: coin_mint.clone(),
+        pc_mint: pc_mint.clone(),
+        coin_reserve,
+        pc_reserve,
+        lp_supply,
+        ..Default::default()
+    })
+}
+
+/// Prices a pool from its reserves, with the latest LP supply known for it.
+pub fn get_pool_price(reserves: &PoolReserves, lp_supply: Option<u64>) -> PoolPrice {
+    PoolPrice {
+        amm: reserves.amm.clone(),
+        coin_mint: reserves.coin_mint.clone(),
+        pc_mint: reserves.pc_mint.clone(),
+        coin_reserve: reserves.coin_reserve,
+        pc_reserve: reserves.pc_reserve,
+        lp_supply: reserves.lp_supply.or(lp_supply),
+        mid_price: mid_price(reserves),
+    }
+}
+
+/// Price of the coin in pc, adjusted by the mint decimals.
+pub fn mid_price(reserves: &PoolReserves) -> Option<f64> {
+    if reserves.coin_reserve == 0 {
+        return None;
+    }
+    let coin = reserves.coin_reserve as f64 / 10f64.powi(reserves.coin_decimals? as i32);
+    let pc = reserves.pc_reserve as f64 / 10f64.powi(reserves.pc_decimals? as i32);
+    Some(pc / coin)
+}
+
+fn _integer_sqrt(value: u128) -> u128 {
+    if value < 2 {
+        return value;
+    }
+    let mut x = value;
+    let mut y = (x + 1) / 2;
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reserves(coin_reserve: u64, pc_reserve: u64, coin_decimals: Option<u32>, pc_decimals: Option<u32>) -> PoolReserves {
+        PoolReserves { coin_reserve, pc_reserve, coin_decimals, pc_decimals, ..Default::default() }
+    }
+
+    #[test]
+    fn test_integer_sqrt() {
+        assert_eq!(_integer_sqrt(0), 0);
+        assert_eq!(_integer_sqrt(1), 1);
+        assert_eq!(_integer_sqrt(3), 1);
+        assert_eq!(_integer_sqrt(4), 2);
+        assert_eq!(_integer_sqrt(15), 3);
+        assert_eq!(_integer_sqrt(16), 4);
+        assert_eq!(_integer_sqrt(10u128.pow(18)), 10u128.pow(9));
+        assert_eq!(_integer_sqrt(u64::MAX as u128 * u64::MAX as u128), u64::MAX as u128);
+    }
+
+    #[test]
+    fn test_mid_price() {
+        // 1 coin with 9 decimals for 150 pc with 6 decimals.
+        assert_eq!(mid_price(&reserves(1_000_000_000, 150_000_000, Some(9), Some(6))), Some(150.0));
+        assert_eq!(mid_price(&reserves(2_000, 1_000, Some(0), Some(0))), Some(0.5));
+    }
+
+    #[test]
+    fn test_mid_price_unknown() {
+        assert_eq!(mid_price(&reserves(0, 150_000_000, Some(9), Some(6))), None);
+        assert_eq!(mid_price(&reserves(1_000_000_000, 150_000_000, None, Some(6))), None);
+        assert_eq!(mid_price(&reserves(1_000_000_000, 150_000_000, Some(9), None)), None);
+    }
+}
@@ -0,0 +1,32 @@
+use std::collections::HashSet;
+
+use solana_substreams_common::params::{matches, Params};
+
+use crate::pb::spl_memo::spl_memo_event::Event;
+
+/// Filters events by the `signers` module param, matching any signer of the memo.
+///
+/// An unset filter matches every event.
+#[derive(Clone, Debug, Default)]
+pub struct Filter {
+    signers: Option<HashSet<String>>,
+}
+
+impl Filter {
+    pub fn new(params: &Params) -> Self {
+        Self {
+            signers: params.get_set("signers"),
+        }
+    }
+
+    pub fn matches(&self, event: &Event) -> bool {
+        matches(&self.signers, event_signers(event))
+    }
+}
+
+pub fn event_signers(event: &Event) -> Vec<&str> {
+    match event {
+        Event::Memo(memo) => memo.signers.iter().map(|x| x.as_str()).collect(),
+        Event::FailedInstruction(_) | Event::UnknownInstruction(_) => Vec::new(),
+    }
+}
@@ -0,0 +1,22 @@
+use std::collections::BTreeSet;
+
+use substreams::pb::sf::substreams::index::v1::Keys;
+
+use crate::filter::event_signers;
+use crate::pb::spl_memo::{SplMemoEvent, SplMemoTransactionEvents};
+
+/// Block index keys of the events: `program:` and `signer:`.
+pub fn index_keys(transactions: &[SplMemoTransactionEvents]) -> Keys {
+    let keys: BTreeSet<String> = transactions.iter().flat_map(|x| x.events.iter()).flat_map(event_keys).collect();
+    Keys { keys: keys.into_iter().collect() }
+}
+
+/// Index keys of a single event, which `filtered_spl_memo_events` matches its query against.
+pub fn event_keys(event: &SplMemoEvent) -> BTreeSet<String> {
+    let mut keys = BTreeSet::new();
+    keys.insert(format!("program:{}", event.memo_program));
+    if let Some(event) = event.event.as_ref() {
+        keys.extend(event_signers(event).into_iter().map(|x| format!("signer:{}", x)));
+    }
+    keys
+}
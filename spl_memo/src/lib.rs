@@ -0,0 +1,305 @@
+use std::collections::HashMap;
+
+use substreams::errors::Error;
+use substreams::pb::sf::substreams::index::v1::Keys;
+use substreams_solana::pb::sf::solana::r#type::v1::ConfirmedTransaction;
+use substreams_solana::pb::sf::solana::r#type::v1::Block;
+
+use substreams_solana_utils as utils;
+use utils::transaction::{get_context, TransactionContext};
+use utils::instruction::{get_structured_instructions, StructuredInstructions, StructuredInstruction};
+
+use solana_substreams_common::{get_failed_instruction, get_transaction_envelope, is_program_invoked};
+use solana_substreams_common::params::Params;
+use solana_substreams_common::query::Query;
+use solana_substreams_common::instruction::{flattened_index, get_call_path, get_unknown_instruction, stack_depth};
+
+pub mod error;
+use error::ParseError;
+
+pub mod memo;
+use memo::{decode_memo, is_memo_instruction, MEMO_PROGRAM_ID, MEMO_V1_PROGRAM_ID};
+
+pub mod filter;
+use filter::Filter;
+
+pub mod index;
+use index::{event_keys, index_keys};
+
+pub mod pb;
+use pb::spl_memo::*;
+use pb::spl_memo::spl_memo_event::Event;
+
+#[substreams::handlers::map]
+fn spl_memo_events(params: String, block: Block) -> Result<SplMemoBlockEvents, Error> {
+    Ok(_block_events(&block, &Params::parse(&params)))
+}
+
+#[substreams::handlers::map]
+fn spl_memo_index(block: Block) -> Result<Keys, Error> {
+    let transactions = parse_block(&block, &Params::default());
+    Ok(index_keys(&transactions))
+}
+
+/// Events matching the block filter query given as params, such as `signer:<signer>`, on their own index keys.
+#[substreams::handlers::map]
+fn filtered_spl_memo_events(query: String, block: Block) -> Result<SplMemoBlockEvents, Error> {
+    let query = Query::parse(&query).map_err(Error::msg)?;
+    let mut block_events = _block_events(&block, &Params::default());
+    for transaction in block_events.transactions.iter_mut() {
+        transaction.events.retain(|x| query.matches(&event_keys(x)));
+    }
+    block_events.transactions.retain(|x| !x.events.is_empty());
+    Ok(block_events)
+}
+
+fn _block_events(block: &Block, params: &Params) -> SplMemoBlockEvents {
+    let transactions = parse_block(block, params);
+    SplMemoBlockEvents {
+        slot: block.slot,
+        block_time: block.block_time.as_ref().map(|x| x.timestamp).unwrap_or_default(),
+        block_hash: block.blockhash.clone(),
+        transactions,
+    }
+}
+
+pub fn parse_block(block: &Block, params: &Params) -> Vec<SplMemoTransactionEvents> {
+    let include_failed = params.get_bool("include_failed");
+    let filter = Filter::new(params);
+    let mut block_events: Vec<SplMemoTransactionEvents> = Vec::new();
+    for (i, transaction) in block.transactions.iter().enumerate() {
+        let failed = transaction.meta.as_ref().map_or(false, |x| x.err.is_some());
+        if failed && !include_failed {
+            continue;
+        }
+        let signature = utils::transaction::get_signature(&transaction);
+        let result = if failed {
+            Ok(parse_failed_transaction(transaction, &filter))
+        } else {
+            parse_transaction(transaction, &filter)
+        };
+        match result {
+            Ok(events) if !events.is_empty() => {
+                block_events.push(SplMemoTransactionEvents {
+                    signature,
+                    transaction_index: i as u32,
+                    envelope: Some(get_transaction_envelope(transaction)),
+                    events,
+                });
+            },
+            Ok(_) => (),
+            Err(error) => substreams::log::println(format!("Failed to process transaction {}: {}", signature, error)),
+        }
+    }
+    block_events
+}
+
+pub fn parse_transaction(transaction: &ConfirmedTransaction, filter: &Filter) -> Result<Vec<SplMemoEvent>, ParseError> {
+    if let Some(_) = transaction.meta.as_ref().ok_or(ParseError::MissingTransactionMeta)?.err {
+        return Ok(Vec::new())
+    }
+
+    let mut events: Vec<SplMemoEvent> = Vec::new();
+
+    let context = get_context(transaction).map_err(|x| ParseError::TransactionContext(x.to_string()))?;
+    let instructions = get_structured_instructions(transaction).map_err(|x| ParseError::TransactionContext(x.to_string()))?;
+    let signers = get_transaction_envelope(transaction).signers;
+
+    let flattened = instructions.flattened();
+    for (i, instruction) in flattened.iter().enumerate() {
+        if !is_memo_instruction(instruction) {
+            continue;
+        }
+
+        let call_path = get_call_path(instruction, &flattened);
+        match parse_instruction(instruction, &context, &signers) {
+            Ok(event) if filter.matches(&event) => {
+                events.push(SplMemoEvent {
+                    memo_program: instruction.program_id().to_string(),
+                    instruction_index: i as u32,
+                    stack_depth: stack_depth(instruction),
+                    top_instruction_program_id: call_path.top_instruction_program_id,
+                    parent_instruction_program_id: call_path.parent_instruction_program_id,
+                    top_instruction_index: call_path.top_instruction_index,
+                    parent_instruction_index: call_path.parent_instruction_index,
+                    event: Some(event),
+                });
+            },
+            Ok(_) => (),
+            Err(error) => substreams::log::println(format!("Failed to process instruction of transaction {}: {}", &context.signature, error)),
+        }
+    }
+
+    Ok(events)
+}
+
+/// Parses a failed transaction into a `FailedInstruction` event, if it invoked a Memo program.
+pub fn parse_failed_transaction(transaction: &ConfirmedTransaction, filter: &Filter) -> Vec<SplMemoEvent> {
+    let memo_program = [MEMO_PROGRAM_ID, MEMO_V1_PROGRAM_ID].iter()
+        .map(|x| x.to_string())
+        .find(|x| is_program_invoked(transaction, x));
+
+    // The Memo programs don't define custom errors.
+    let events = match (memo_program, get_failed_instruction(transaction, |_, _| None)) {
+        (Some(memo_program), Some(failed_instruction)) => vec![SplMemoEvent {
+            memo_program,
+            instruction_index: flattened_index(transaction, failed_instruction.instruction_index),
+            stack_depth: 0,
+            top_instruction_program_id: None,
+            parent_instruction_program_id: None,
+            top_instruction_index: None,
+            parent_instruction_index: None,
+            event: Some(Event::FailedInstruction(failed_instruction)),
+        }],
+        _ => Vec::new(),
+    };
+    events.into_iter().filter(|x| x.event.as_ref().map_or(false, |x| filter.matches(x))).collect()
+}
+
+/// Memos of the transaction, by the flattened index of the instruction right after them at the same
+/// invocation level, which is where the Token-2022 memo transfer extension expects the memo of a transfer.
+pub fn get_transaction_memos(transaction: &ConfirmedTransaction) -> HashMap<u32, String> {
+    let instructions = match get_structured_instructions(transaction) {
+        Ok(instructions) => instructions,
+        Err(_) => return HashMap::new(),
+    };
+    let flattened = instructions.flattened();
+    let depths: Vec<u32> = flattened.iter().map(|x| stack_depth(x)).collect();
+    flattened.iter()
+        .enumerate()
+        .filter(|(_, x)| is_memo_instruction(x))
+        .filter_map(|(i, x)| _next_sibling(&depths, i).map(|next| (next as u32, decode_memo(x.data()))))
+        .collect()
+}
+
+/// Index of the instruction following a memo at the same invocation level. The Memo programs
+/// invoke no other program, so it can only be the next flattened instruction.
+fn _next_sibling(depths: &[u32], index: usize) -> Option<usize> {
+    let next = index + 1;
+    (depths.get(next)? == depths.get(index)?).then_some(next)
+}
+
+pub fn parse_instruction<'a>(
+    instruction: &StructuredInstruction<'a>,
+    context: &TransactionContext,
+    signers: &[String],
+) -> Result<Event, ParseError> {
+    if !is_memo_instruction(instruction) {
+        return Err(ParseError::UnexpectedProgram(instruction.program_id().to_string()));
+    }
+    // Memos have no discriminator, and the Memo programs reject any data which isn't UTF-8.
+    if std::str::from_utf8(instruction.data()).is_err() {
+        return Ok(Event::UnknownInstruction(get_unknown_instruction(instruction, 0)));
+    }
+    _parse_memo_instruction(instruction, context, signers).map(Event::Memo)
+}
+
+fn _parse_memo_instruction(
+    instruction: &StructuredInstruction,
+    _context: &TransactionContext,
+    signers: &[String],
+) -> Result<MemoEvent, ParseError> {
+    let memo = decode_memo(instruction.data());
+    // Memo v2 requires every account of the instruction to be a signer, while Memo v1 ignores them.
+    let signers = instruction.accounts()
+        .iter()
+        .map(|x| x.to_string())
+        .filter(|x| instruction.program_id() == MEMO_PROGRAM_ID || signers.contains(x))
+        .collect();
+
+    Ok(MemoEvent {
+        memo,
+        signers,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use substreams_solana::pb::sf::solana::r#type::v1::{CompiledInstruction, Message, MessageHeader, Transaction, TransactionStatusMeta};
+    use utils::pubkey::Pubkey;
+    use utils::system_program::SYSTEM_PROGRAM_ID;
+
+    use super::*;
+
+    // A signer, a non signer, the Memo programs and the System program.
+    fn transaction(instructions: Vec<CompiledInstruction>) -> ConfirmedTransaction {
+        let account_keys = vec![
+            vec![1; 32],
+            vec![2; 32],
+            MEMO_PROGRAM_ID.0.to_vec(),
+            MEMO_V1_PROGRAM_ID.0.to_vec(),
+            SYSTEM_PROGRAM_ID.0.to_vec(),
+        ];
+        ConfirmedTransaction {
+            transaction: Some(Transaction {
+                signatures: vec![vec![0; 64]],
+                message: Some(Message {
+                    header: Some(MessageHeader { num_required_signatures: 1, ..Default::default() }),
+                    account_keys,
+                    instructions,
+                    ..Default::default()
+                }),
+            }),
+            meta: Some(TransactionStatusMeta {
+                pre_balances: vec![0; 5],
+                post_balances: vec![0; 5],
+                ..Default::default()
+            }),
+        }
+    }
+
+    fn memo(program_id_index: u32, accounts: Vec<u8>, memo: &str) -> CompiledInstruction {
+        CompiledInstruction { program_id_index, accounts, data: memo.as_bytes().to_vec() }
+    }
+
+    fn transfer() -> CompiledInstruction {
+        let data = [&2u32.to_le_bytes()[..], &1_000u64.to_le_bytes()].concat();
+        CompiledInstruction { program_id_index: 4, accounts: vec![0, 1], data }
+    }
+
+    #[test]
+    fn test_memo_signers() {
+        let transaction = transaction(vec![memo(2, vec![0], "v2"), memo(3, vec![0, 1], "v1"), memo(3, vec![1], "v1")]);
+        let signers: Vec<Vec<String>> = parse_transaction(&transaction, &Filter::default()).unwrap()
+            .into_iter()
+            .map(|x| match x.event {
+                Some(Event::Memo(memo)) => memo.signers,
+                event => panic!("unexpected event {:?}", event),
+            })
+            .collect();
+
+        // Memo v2 fails unless its accounts sign, while Memo v1 accepts any account.
+        let signer = Pubkey([1; 32]).to_string();
+        assert_eq!(signers, vec![vec![signer.clone()], vec![signer], vec![]]);
+    }
+
+    #[test]
+    fn test_transaction_memos() {
+        let transaction = transaction(vec![
+            memo(2, vec![], "first"),
+            transfer(),
+            transfer(),
+            memo(3, vec![], "second"),
+            memo(2, vec![], "third"),
+            transfer(),
+            memo(2, vec![], "last"),
+        ]);
+        let memos = get_transaction_memos(&transaction);
+
+        assert_eq!(memos.get(&1).map(|x| x.as_str()), Some("first"));
+        assert_eq!(memos.get(&2), None);
+        assert_eq!(memos.get(&4).map(|x| x.as_str()), Some("second"));
+        assert_eq!(memos.get(&5).map(|x| x.as_str()), Some("third"));
+        assert_eq!(memos.len(), 3);
+    }
+
+    #[test]
+    fn test_next_sibling() {
+        let depths = [0, 0, 1, 1, 0];
+        assert_eq!(_next_sibling(&depths, 0), Some(1));
+        assert_eq!(_next_sibling(&depths, 2), Some(3));
+        // The memo is the last instruction invoked by its parent.
+        assert_eq!(_next_sibling(&depths, 3), None);
+        assert_eq!(_next_sibling(&depths, 4), None);
+    }
+}
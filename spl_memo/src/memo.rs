@@ -0,0 +1,15 @@
+use substreams_solana::b58;
+use substreams_solana_utils::pubkey::Pubkey;
+use substreams_solana_utils::instruction::StructuredInstruction;
+
+pub const MEMO_PROGRAM_ID: Pubkey = Pubkey(b58!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr"));
+pub const MEMO_V1_PROGRAM_ID: Pubkey = Pubkey(b58!("Memo1UhkJRfHyvLMcVucJwxXeuD728EqVDDwQDxFMNo"));
+
+pub fn is_memo_instruction(instruction: &StructuredInstruction) -> bool {
+    instruction.program_id() == MEMO_PROGRAM_ID || instruction.program_id() == MEMO_V1_PROGRAM_ID
+}
+
+/// Decodes the memo of an instruction, which the Memo programs require to be valid UTF-8.
+pub fn decode_memo(data: &[u8]) -> String {
+    String::from_utf8_lossy(data).into_owned()
+}
@@ -0,0 +1,6 @@
+// @generated
+// @@protoc_insertion_point(attribute:spl_memo)
+pub mod spl_memo {
+    include!("spl_memo.rs");
+    // @@protoc_insertion_point(spl_memo)
+}
@@ -0,0 +1,70 @@
+// @generated
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SplMemoBlockEvents {
+    #[prost(uint64, tag="1")]
+    pub slot: u64,
+    #[prost(message, repeated, tag="2")]
+    pub transactions: ::prost::alloc::vec::Vec<SplMemoTransactionEvents>,
+    #[prost(int64, tag="3")]
+    pub block_time: i64,
+    #[prost(string, tag="4")]
+    pub block_hash: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SplMemoTransactionEvents {
+    #[prost(string, tag="1")]
+    pub signature: ::prost::alloc::string::String,
+    #[prost(uint32, tag="2")]
+    pub transaction_index: u32,
+    #[prost(message, repeated, tag="3")]
+    pub events: ::prost::alloc::vec::Vec<SplMemoEvent>,
+    #[prost(message, optional, tag="4")]
+    pub envelope: ::core::option::Option<::solana_substreams_common::pb::common::TransactionEnvelope>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SplMemoEvent {
+    /// Memo program of the instruction, either Memo v1 or v2.
+    #[prost(string, tag="1")]
+    pub memo_program: ::prost::alloc::string::String,
+    #[prost(uint32, tag="2")]
+    pub instruction_index: u32,
+    /// Depth in the invocation stack, 0 for top level instructions.
+    #[prost(uint32, tag="3")]
+    pub stack_depth: u32,
+    #[prost(string, optional, tag="4")]
+    pub top_instruction_program_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag="5")]
+    pub parent_instruction_program_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(uint32, optional, tag="6")]
+    pub top_instruction_index: ::core::option::Option<u32>,
+    #[prost(uint32, optional, tag="7")]
+    pub parent_instruction_index: ::core::option::Option<u32>,
+    #[prost(oneof="spl_memo_event::Event", tags="8, 9, 10")]
+    pub event: ::core::option::Option<spl_memo_event::Event>,
+}
+/// Nested message and enum types in `SplMemoEvent`.
+pub mod spl_memo_event {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Event {
+        #[prost(message, tag="8")]
+        Memo(super::MemoEvent),
+        #[prost(message, tag="9")]
+        FailedInstruction(::solana_substreams_common::pb::common::FailedInstruction),
+        #[prost(message, tag="10")]
+        UnknownInstruction(::solana_substreams_common::pb::common::UnknownInstruction),
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MemoEvent {
+    #[prost(string, tag="1")]
+    pub memo: ::prost::alloc::string::String,
+    /// Accounts that signed the memo.
+    #[prost(string, repeated, tag="2")]
+    pub signers: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+// @@protoc_insertion_point(module)
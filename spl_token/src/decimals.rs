@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+
+use substreams_solana::pb::sf::solana::r#type::v1::ConfirmedTransaction;
+
+/// Decimals of the mints known to a transaction.
+///
+/// Seeded from the transaction's token balance metadata, and extended as mints get initialized
+/// or Checked instructions reveal their decimals.
+pub struct MintDecimals {
+    decimals: HashMap<String, u32>,
+}
+
+impl MintDecimals {
+    pub fn new(transaction: &ConfirmedTransaction) -> Self {
+        let decimals = transaction.meta.iter()
+            .flat_map(|meta| meta.pre_token_balances.iter().chain(meta.post_token_balances.iter()))
+            .filter_map(|x| x.ui_token_amount.as_ref().map(|amount| (x.mint.clone(), amount.decimals)))
+            .collect();
+
+        Self { decimals }
+    }
+
+    pub fn get(&self, mint: &str) -> Option<u32> {
+        self.decimals.get(mint).cloned()
+    }
+
+    pub fn insert(&mut self, mint: String, decimals: u32) {
+        self.decimals.insert(mint, decimals);
+    }
+}
+
+/// Formats a raw token amount as a decimal string, without losing precision or keeping trailing zeros.
+pub fn to_ui_amount(amount: u64, decimals: u32) -> String {
+    let decimals = decimals as usize;
+    let digits = format!("{:0>width$}", amount, width = decimals + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+
+    if fraction.is_empty() {
+        integer.to_string()
+    } else {
+        format!("{}.{}", integer, fraction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_ui_amount() {
+        assert_eq!(to_ui_amount(1_500_000, 6), "1.5");
+        assert_eq!(to_ui_amount(1_000_000, 6), "1");
+        assert_eq!(to_ui_amount(1, 9), "0.000000001");
+        assert_eq!(to_ui_amount(0, 6), "0");
+        assert_eq!(to_ui_amount(42, 0), "42");
+        assert_eq!(to_ui_amount(u64::MAX, 9), "18446744073.709551615");
+        assert_eq!(to_ui_amount(u64::MAX, 20), "0.18446744073709551615");
+    }
+}
@@ -0,0 +1,19 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ParseError {
+    #[error("Transaction meta is missing")]
+    MissingTransactionMeta,
+    #[error("Failed to build transaction context: {0}")]
+    TransactionContext(String),
+    #[error("Instruction does not originate from the Token program: {0}")]
+    UnexpectedProgram(String),
+    #[error("Missing account at index {0}")]
+    MissingAccount(usize),
+    #[error("Token account {0} not found in transaction context")]
+    MissingTokenAccount(String),
+    #[error("Invalid instruction data: {0}")]
+    InvalidInstructionData(String),
+    #[error("Expected a {0} instruction")]
+    InstructionMismatch(&'static str),
+}
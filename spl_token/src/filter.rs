@@ -0,0 +1,95 @@
+use std::collections::HashSet;
+
+use solana_substreams_common::params::{matches, Params};
+
+use crate::pb::spl_token::TokenAccount;
+use crate::pb::spl_token::spl_token_event::Event;
+
+/// Filters events by the `mints` and `owners` module params.
+///
+/// An event matches when one of its mints, and one of the owners of its token accounts, are in
+/// the respective filters. Unset filters match every event.
+#[derive(Clone, Debug, Default)]
+pub struct Filter {
+    mints: Option<HashSet<String>>,
+    owners: Option<HashSet<String>>,
+}
+
+impl Filter {
+    pub fn new(params: &Params) -> Self {
+        Self {
+            mints: params.get_set("mints"),
+            owners: params.get_set("owners"),
+        }
+    }
+
+    pub fn matches(&self, event: &Event) -> bool {
+        matches(&self.mints, event_mints(event)) && matches(&self.owners, event_owners(event))
+    }
+
+    /// Whether neither filter is set, in which case events without mints or owners still match.
+    pub fn is_empty(&self) -> bool {
+        self.mints.is_none() && self.owners.is_none()
+    }
+}
+
+/// Mints of the event and of its token accounts.
+pub fn event_mints(event: &Event) -> Vec<&str> {
+    _token_accounts(event).iter().map(|x| x.mint.as_str()).chain(_mint(event)).collect()
+}
+
+/// Owners of the token accounts of the event.
+pub fn event_owners(event: &Event) -> Vec<&str> {
+    _token_accounts(event).iter().map(|x| x.owner.as_str()).chain(_owner(event)).collect()
+}
+
+fn _token_accounts(event: &Event) -> Vec<&TokenAccount> {
+    let token_accounts = match event {
+        Event::InitializeAccount(initialize_account) => vec![&initialize_account.account],
+        Event::Transfer(transfer) => vec![&transfer.source, &transfer.destination],
+        Event::Approve(approve) => vec![&approve.source],
+        Event::Revoke(revoke) => vec![&revoke.source],
+        Event::MintTo(mint_to) => vec![&mint_to.destination],
+        Event::Burn(burn) => vec![&burn.source],
+        Event::CloseAccount(close_account) => vec![&close_account.source],
+        Event::FreezeAccount(freeze_account) => vec![&freeze_account.source],
+        Event::ThawAccount(thaw_account) => vec![&thaw_account.source],
+        Event::InitializeImmutableOwner(initialize_immutable_owner) => vec![&initialize_immutable_owner.account],
+        Event::SyncNative(sync_native) => vec![&sync_native.account],
+        Event::WithdrawWithheldTokensFromMint(withdraw) => vec![&withdraw.destination],
+        Event::WithdrawWithheldTokensFromAccounts(withdraw) => vec![&withdraw.destination],
+        _ => Vec::new(),
+    };
+    token_accounts.into_iter().flatten().collect()
+}
+
+fn _mint(event: &Event) -> Option<&str> {
+    let mint = match event {
+        Event::InitializeMint(initialize_mint) => &initialize_mint.mint,
+        Event::SetAuthority(set_authority) => &set_authority.mint,
+        Event::MintTo(mint_to) => &mint_to.mint,
+        Event::InitializeTransferFeeConfig(initialize) => &initialize.mint,
+        Event::WithdrawWithheldTokensFromMint(withdraw) => &withdraw.mint,
+        Event::WithdrawWithheldTokensFromAccounts(withdraw) => &withdraw.mint,
+        Event::HarvestWithheldTokensToMint(harvest) => &harvest.mint,
+        Event::SetTransferFee(set_transfer_fee) => &set_transfer_fee.mint,
+        Event::InitializeDefaultAccountState(initialize) => &initialize.mint,
+        Event::UpdateDefaultAccountState(update) => &update.mint,
+        Event::InitializeInterestBearingMint(initialize) => &initialize.mint,
+        Event::UpdateInterestBearingMintRate(update) => &update.mint,
+        Event::InitializeMetadataPointer(initialize) => &initialize.mint,
+        Event::UpdateMetadataPointer(update) => &update.mint,
+        Event::TokenMetadataInitialize(initialize) => &initialize.mint,
+        _ => return None,
+    };
+    Some(mint.as_str())
+}
+
+fn _owner(event: &Event) -> Option<&str> {
+    let owner = match event {
+        Event::EnableRequiredMemoTransfers(enable) => &enable.owner,
+        Event::DisableRequiredMemoTransfers(disable) => &disable.owner,
+        _ => return None,
+    };
+    Some(owner.as_str())
+}
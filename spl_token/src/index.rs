@@ -0,0 +1,23 @@
+use std::collections::BTreeSet;
+
+use substreams::pb::sf::substreams::index::v1::Keys;
+
+use crate::filter::{event_mints, event_owners};
+use crate::pb::spl_token::{SplTokenEvent, SplTokenTransactionEvents};
+
+/// Block index keys of the events: `program:`, `mint:` and `owner:`.
+pub fn index_keys(transactions: &[SplTokenTransactionEvents]) -> Keys {
+    let keys: BTreeSet<String> = transactions.iter().flat_map(|x| x.events.iter()).flat_map(event_keys).collect();
+    Keys { keys: keys.into_iter().collect() }
+}
+
+/// Index keys of a single event, which `filtered_spl_token_events` matches its query against.
+pub fn event_keys(event: &SplTokenEvent) -> BTreeSet<String> {
+    let mut keys = BTreeSet::new();
+    keys.insert(format!("program:{}", event.token_program));
+    if let Some(event) = event.event.as_ref() {
+        keys.extend(event_mints(event).into_iter().map(|x| format!("mint:{}", x)));
+        keys.extend(event_owners(event).into_iter().map(|x| format!("owner:{}", x)));
+    }
+    keys
+}
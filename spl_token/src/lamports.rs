@@ -0,0 +1,343 @@
+use std::collections::{HashMap, HashSet};
+
+use substreams_solana::b58;
+use substreams_solana::pb::sf::solana::r#type::v1::{ConfirmedTransaction, TokenBalance};
+
+use substreams_solana_utils as utils;
+use utils::instruction::StructuredInstruction;
+use utils::pubkey::Pubkey;
+use utils::spl_token::TokenInstruction;
+use utils::system_program::{SystemInstruction, SYSTEM_PROGRAM_ID};
+
+use solana_substreams_common::programs::is_token_program;
+
+/// Mint of wrapped SOL for the Token program.
+pub const NATIVE_MINT: Pubkey = Pubkey(b58!("So11111111111111111111111111111111111111112"));
+
+/// Mint of wrapped SOL for the Token-2022 program.
+pub const NATIVE_MINT_2022: Pubkey = Pubkey(b58!("9pan9bMn5HatX4EJdBwg9VgCa7Uz5HL8N1m5D3NdXejP"));
+
+/// Size of a token account without extensions.
+pub const TOKEN_ACCOUNT_LEN: u64 = 165;
+
+const ACCOUNT_STORAGE_OVERHEAD: u64 = 128;
+const LAMPORTS_PER_BYTE_YEAR: u64 = 3480;
+const EXEMPTION_THRESHOLD_YEARS: u64 = 2;
+
+/// Minimum balance for an account holding `data_len` bytes to be rent exempt.
+pub fn rent_exempt_minimum(data_len: u64) -> u64 {
+    (ACCOUNT_STORAGE_OVERHEAD + data_len) * LAMPORTS_PER_BYTE_YEAR * EXEMPTION_THRESHOLD_YEARS
+}
+
+pub fn is_native_mint(mint: &str) -> bool {
+    mint == NATIVE_MINT.to_string() || mint == NATIVE_MINT_2022.to_string()
+}
+
+/// Tracks the lamport balances of the transaction accounts as its instructions are executed.
+///
+/// Balances start from the transaction's `pre_balances` (with the fee already charged to the
+/// fee payer) and are moved by the system and token instructions that transfer lamports,
+/// including token transfers between native (wrapped SOL) accounts.
+pub struct LamportContext {
+    balances: HashMap<String, u64>,
+    previous: HashMap<String, u64>,
+    native_accounts: HashSet<String>,
+    data_sizes: HashMap<String, u64>,
+    reserves: HashMap<String, u64>,
+}
+
+impl LamportContext {
+    pub fn new(transaction: &ConfirmedTransaction) -> Self {
+        let mut balances: HashMap<String, u64> = HashMap::new();
+        let mut native_accounts: HashSet<String> = HashSet::new();
+        let mut reserves: HashMap<String, u64> = HashMap::new();
+
+        if let Some(meta) = transaction.meta.as_ref() {
+            let accounts: Vec<String> = transaction.resolved_accounts()
+                .iter()
+                .map(|x| bs58::encode(x).into_string())
+                .collect();
+
+            balances = accounts.iter().cloned().zip(meta.pre_balances.iter().cloned()).collect();
+
+            // A native account holds its token amount plus its rent-exempt reserve, and more
+            // only when lamports were sent to it without a SyncNative.
+            let token_balances = meta.pre_token_balances.iter().map(|x| (x, &meta.pre_balances))
+                .chain(meta.post_token_balances.iter().map(|x| (x, &meta.post_balances)));
+            for (token_balance, lamports) in token_balances {
+                if !is_native_mint(&token_balance.mint) {
+                    continue;
+                }
+                let address = match accounts.get(token_balance.account_index as usize) {
+                    Some(address) => address.clone(),
+                    None => continue,
+                };
+                if let (Some(lamports), Some(amount)) = (lamports.get(token_balance.account_index as usize), _token_amount(token_balance)) {
+                    let reserve = lamports.saturating_sub(amount);
+                    reserves.entry(address.clone()).and_modify(|x| *x = (*x).min(reserve)).or_insert(reserve);
+                }
+                native_accounts.insert(address);
+            }
+
+            if let Some(fee_payer) = accounts.first() {
+                if let Some(balance) = balances.get_mut(fee_payer) {
+                    *balance = balance.saturating_sub(meta.fee);
+                }
+            }
+        }
+
+        Self {
+            balances,
+            previous: HashMap::new(),
+            native_accounts,
+            data_sizes: HashMap::new(),
+            reserves,
+        }
+    }
+
+    /// Lamports held by the account right before the last processed instruction.
+    pub fn pre_lamports(&self, address: &str) -> Option<u64> {
+        self.previous.get(address).or_else(|| self.balances.get(address)).cloned()
+    }
+
+    /// Lamports held by the account right after the last processed instruction.
+    pub fn post_lamports(&self, address: &str) -> Option<u64> {
+        self.balances.get(address).cloned()
+    }
+
+    /// Rent-exempt reserve of a native token account.
+    ///
+    /// Derived from the account size when the account is created by the transaction, otherwise
+    /// from its balances and token amounts, falling back to the reserve of an account without
+    /// extensions.
+    pub fn rent_exempt_reserve(&self, address: &str) -> u64 {
+        match self.data_sizes.get(address) {
+            Some(data_len) => rent_exempt_minimum(*data_len),
+            None => self.reserves.get(address).cloned().unwrap_or_else(|| rent_exempt_minimum(TOKEN_ACCOUNT_LEN)),
+        }
+    }
+
+    /// Native amount of a native token account, its lamports above the rent-exempt reserve.
+    pub fn native_amount(&self, address: &str) -> u64 {
+        self.post_lamports(address).unwrap_or(0).saturating_sub(self.rent_exempt_reserve(address))
+    }
+
+    pub fn update_balance(&mut self, instruction: &StructuredInstruction) {
+        let accounts: Vec<String> = instruction.accounts().iter().map(|x| x.to_string()).collect();
+        self.replay(&instruction.program_id(), &accounts, instruction.data());
+    }
+
+    fn replay(&mut self, program_id: &Pubkey, accounts: &[String], data: &[u8]) {
+        self.previous.clear();
+
+        if *program_id == SYSTEM_PROGRAM_ID {
+            self.update_system_balance(accounts, data);
+        } else if is_token_program(program_id) {
+            self.update_token_balance(accounts, data);
+        }
+    }
+
+    fn update_system_balance(&mut self, accounts: &[String], data: &[u8]) {
+        let movement = match SystemInstruction::unpack(data) {
+            Ok(SystemInstruction::CreateAccount(create_account)) => {
+                if let Some(new_account) = accounts.get(1) {
+                    self.data_sizes.insert(new_account.clone(), create_account.space);
+                }
+                Some((0, 1, create_account.lamports))
+            },
+            Ok(SystemInstruction::CreateAccountWithSeed(create_account_with_seed)) => {
+                if let Some(new_account) = accounts.get(1) {
+                    self.data_sizes.insert(new_account.clone(), create_account_with_seed.space);
+                }
+                Some((0, 1, create_account_with_seed.lamports))
+            },
+            Ok(SystemInstruction::Transfer(transfer)) => Some((0, 1, transfer.lamports)),
+            Ok(SystemInstruction::TransferWithSeed(transfer_with_seed)) => Some((0, 2, transfer_with_seed.lamports)),
+            Ok(SystemInstruction::WithdrawNonceAccount(lamports)) => Some((0, 1, lamports)),
+            _ => None,
+        };
+
+        if let Some((from, to, lamports)) = movement {
+            if let (Some(from), Some(to)) = (accounts.get(from), accounts.get(to)) {
+                self.transfer(from, to, lamports);
+            }
+        }
+    }
+
+    fn update_token_balance(&mut self, accounts: &[String], data: &[u8]) {
+        match TokenInstruction::unpack(data) {
+            Ok(TokenInstruction::InitializeAccount) |
+            Ok(TokenInstruction::InitializeAccount2 { .. }) |
+            Ok(TokenInstruction::InitializeAccount3 { .. }) => {
+                if let (Some(account), Some(mint)) = (accounts.first(), accounts.get(1)) {
+                    if is_native_mint(mint) {
+                        self.native_accounts.insert(account.clone());
+                    }
+                }
+            },
+            Ok(TokenInstruction::Transfer { amount }) => {
+                if let (Some(source), Some(destination)) = (accounts.first(), accounts.get(1)) {
+                    self.native_transfer(source, destination, amount);
+                }
+            },
+            Ok(TokenInstruction::TransferChecked { amount, .. }) => {
+                if let (Some(source), Some(destination)) = (accounts.first(), accounts.get(2)) {
+                    self.native_transfer(source, destination, amount);
+                }
+            },
+            Ok(TokenInstruction::CloseAccount) => {
+                if let (Some(source), Some(destination)) = (accounts.first(), accounts.get(1)) {
+                    let lamports = self.balances.get(source).cloned().unwrap_or(0);
+                    self.transfer(source, destination, lamports);
+                }
+            },
+            _ => (),
+        }
+    }
+
+    /// Token transfers between native accounts move the transferred amount in lamports.
+    fn native_transfer(&mut self, source: &str, destination: &str, amount: u64) {
+        if self.native_accounts.contains(source) {
+            self.transfer(source, destination, amount);
+        }
+    }
+
+    fn transfer(&mut self, from: &str, to: &str, lamports: u64) {
+        self.set(from, self.balances.get(from).cloned().unwrap_or(0).saturating_sub(lamports));
+        self.set(to, self.balances.get(to).cloned().unwrap_or(0).saturating_add(lamports));
+    }
+
+    fn set(&mut self, address: &str, lamports: u64) {
+        let old = self.balances.insert(address.to_string(), lamports).unwrap_or(0);
+        self.previous.entry(address.to_string()).or_insert(old);
+    }
+}
+
+fn _token_amount(token_balance: &TokenBalance) -> Option<u64> {
+    token_balance.ui_token_amount.as_ref().and_then(|x| x.amount.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use substreams_solana::pb::sf::solana::r#type::v1::{Message, Transaction, TransactionStatusMeta, UiTokenAmount};
+    use utils::spl_token::TOKEN_PROGRAM_ID;
+
+    use super::*;
+
+    const TOKEN_2022_RESERVE: u64 = 2_074_080;
+
+    fn address(byte: u8) -> String {
+        Pubkey([byte; 32]).to_string()
+    }
+
+    fn native_balance(account_index: u32, amount: u64) -> TokenBalance {
+        TokenBalance {
+            account_index,
+            mint: NATIVE_MINT.to_string(),
+            ui_token_amount: Some(UiTokenAmount { amount: amount.to_string(), decimals: 9, ..Default::default() }),
+            ..Default::default()
+        }
+    }
+
+    fn transaction(pre_balances: Vec<u64>, pre_token_balances: Vec<TokenBalance>, post_token_balances: Vec<TokenBalance>) -> ConfirmedTransaction {
+        let account_keys = (0..pre_balances.len() as u8).map(|x| vec![x; 32]).collect();
+        ConfirmedTransaction {
+            transaction: Some(Transaction {
+                message: Some(Message { account_keys, ..Default::default() }),
+                ..Default::default()
+            }),
+            meta: Some(TransactionStatusMeta {
+                fee: 5_000,
+                post_balances: pre_balances.clone(),
+                pre_balances,
+                pre_token_balances,
+                post_token_balances,
+                ..Default::default()
+            }),
+        }
+    }
+
+    fn system_transfer(lamports: u64) -> Vec<u8> {
+        [&2u32.to_le_bytes()[..], &lamports.to_le_bytes()].concat()
+    }
+
+    fn token_transfer(amount: u64) -> Vec<u8> {
+        [&[3u8][..], &amount.to_le_bytes()].concat()
+    }
+
+    #[test]
+    fn test_close_account_after_system_transfer() {
+        let mut lamports = LamportContext::new(&transaction(vec![10_000_000, 2_039_280, 0], vec![], vec![]));
+        let (payer, token_account, destination) = (address(0), address(1), address(2));
+
+        lamports.replay(&SYSTEM_PROGRAM_ID, &[payer.clone(), token_account.clone()], &system_transfer(1_000_000));
+        assert_eq!(lamports.post_lamports(&payer), Some(8_995_000));
+        assert_eq!(lamports.post_lamports(&token_account), Some(3_039_280));
+
+        lamports.replay(&TOKEN_PROGRAM_ID, &[token_account.clone(), destination.clone(), payer.clone()], &[9]);
+        // CloseAccount.lamports is the balance of the source right before the close.
+        assert_eq!(lamports.pre_lamports(&token_account), Some(3_039_280));
+        assert_eq!(lamports.post_lamports(&token_account), Some(0));
+        assert_eq!(lamports.post_lamports(&destination), Some(3_039_280));
+    }
+
+    #[test]
+    fn test_native_transfer() {
+        let pre_token_balances = vec![native_balance(1, 1_000_000), native_balance(2, 0)];
+        let mut lamports = LamportContext::new(&transaction(vec![10_000_000, 3_039_280, 2_039_280], pre_token_balances, vec![]));
+        let (owner, source, destination) = (address(0), address(1), address(2));
+
+        lamports.replay(&TOKEN_PROGRAM_ID, &[source.clone(), destination.clone(), owner.clone()], &token_transfer(400_000));
+        assert_eq!(lamports.pre_lamports(&source), Some(3_039_280));
+        assert_eq!(lamports.post_lamports(&source), Some(2_639_280));
+        assert_eq!(lamports.post_lamports(&destination), Some(2_439_280));
+        assert_eq!(lamports.native_amount(&destination), 400_000);
+
+        // Transfers of other mints don't move lamports.
+        lamports.replay(&TOKEN_PROGRAM_ID, &[owner.clone(), destination.clone(), owner.clone()], &token_transfer(400_000));
+        assert_eq!(lamports.post_lamports(&owner), Some(9_995_000));
+        assert_eq!(lamports.post_lamports(&destination), Some(2_439_280));
+    }
+
+    #[test]
+    fn test_sync_native_after_system_transfer() {
+        // A Token-2022 sized native account, whose reserve is derived from its token balances.
+        let pre_balances = vec![10_000_000, TOKEN_2022_RESERVE + 1_000_000];
+        let mut transaction = transaction(pre_balances, vec![native_balance(1, 1_000_000)], vec![native_balance(1, 1_500_000)]);
+        transaction.meta.as_mut().unwrap().post_balances = vec![9_495_000, TOKEN_2022_RESERVE + 1_500_000];
+        let mut lamports = LamportContext::new(&transaction);
+        let (payer, account) = (address(0), address(1));
+
+        assert_eq!(lamports.rent_exempt_reserve(&account), TOKEN_2022_RESERVE);
+        lamports.replay(&SYSTEM_PROGRAM_ID, &[payer.clone(), account.clone()], &system_transfer(500_000));
+        lamports.replay(&TOKEN_PROGRAM_ID, std::slice::from_ref(&account), &[17]);
+        assert_eq!(lamports.native_amount(&account), 1_500_000);
+        // SyncNative picks up the lamports above the previous token amount.
+        assert_eq!(lamports.native_amount(&account).saturating_sub(1_000_000), 500_000);
+    }
+
+    #[test]
+    fn test_reserve_of_created_account() {
+        let mut lamports = LamportContext::new(&transaction(vec![10_000_000, 0], vec![], vec![]));
+        let (payer, account) = (address(0), address(1));
+        let create_account = [&0u32.to_le_bytes()[..], &2_074_080u64.to_le_bytes(), &170u64.to_le_bytes(), &TOKEN_PROGRAM_ID.0].concat();
+        lamports.replay(&SYSTEM_PROGRAM_ID, &[payer, account.clone()], &create_account);
+        assert_eq!(lamports.rent_exempt_reserve(&account), TOKEN_2022_RESERVE);
+        assert_eq!(lamports.rent_exempt_reserve(&address(2)), rent_exempt_minimum(TOKEN_ACCOUNT_LEN));
+    }
+
+    #[test]
+    fn test_rent_exempt_minimum() {
+        assert_eq!(rent_exempt_minimum(0), 890_880);
+        assert_eq!(rent_exempt_minimum(TOKEN_ACCOUNT_LEN), 2_039_280);
+        // Token-2022 account with the ImmutableOwner extension.
+        assert_eq!(rent_exempt_minimum(170), 2_074_080);
+    }
+
+    #[test]
+    fn test_is_native_mint() {
+        assert!(is_native_mint("So11111111111111111111111111111111111111112"));
+        assert!(is_native_mint("9pan9bMn5HatX4EJdBwg9VgCa7Uz5HL8N1m5D3NdXejP"));
+        assert!(!is_native_mint("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"));
+    }
+}
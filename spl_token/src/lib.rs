@@ -1,6 +1,8 @@
-use anyhow::{anyhow, Context, Error};
+use substreams::errors::Error;
+use substreams::pb::sf::substreams::index::v1::Keys;
 
 use substreams_solana::pb::sf::solana::r#type::v1::ConfirmedTransaction;
+use substreams_solana::pb::sf::solana::r#type::v1::Block;
 
 use substreams_solana_utils as utils;
 use utils::instruction::{get_structured_instructions, StructuredInstruction, StructuredInstructions};
@@ -8,141 +10,474 @@ use utils::transaction::{get_context, TransactionContext};
 use utils::spl_token::{TokenInstruction, TOKEN_PROGRAM_ID};
 use utils::pubkey::Pubkey;
 
+use solana_substreams_common::{get_failed_instruction, get_transaction_envelope, is_program_invoked};
+use solana_substreams_common::params::Params;
+use solana_substreams_common::query::Query;
+use solana_substreams_common::instruction::{flattened_index, get_call_path, get_unknown_instruction, stack_depth};
+use solana_substreams_common::programs::is_token_program;
+
+use spl_memo_substream::get_transaction_memos;
+
+pub mod error;
+use error::ParseError;
+
+pub mod lamports;
+use lamports::LamportContext;
+
+pub mod decimals;
+use decimals::{MintDecimals, to_ui_amount};
+
+pub mod token_error;
+use token_error::token_error_name;
+
+pub mod token_2022;
+use token_2022::TOKEN_2022_PROGRAM_ID;
+use token_2022::instruction::{ExtensionInstruction, TokenMetadataInstruction, TokenMetadataField};
+
+pub mod filter;
+use filter::Filter;
+
+pub mod index;
+use index::{event_keys, index_keys};
+
 pub mod pb;
 use pb::spl_token::*;
 use pb::spl_token::spl_token_event::Event;
 
-pub fn parse_transaction(transaction: &ConfirmedTransaction) -> Result<Vec<SplTokenEvent>, Error> {
-    if let Some(_) = transaction.meta.as_ref().unwrap().err {
+#[substreams::handlers::map]
+fn spl_token_events(params: String, block: Block) -> Result<SplTokenBlockEvents, Error> {
+    Ok(_block_events(&block, &Params::parse(&params)))
+}
+
+#[substreams::handlers::map]
+fn spl_token_index(block: Block) -> Result<Keys, Error> {
+    let transactions = parse_block(&block, &Params::default());
+    Ok(index_keys(&transactions))
+}
+
+/// Events matching the block filter query given as params, such as `mint:<mint>`, on their own index keys.
+#[substreams::handlers::map]
+fn filtered_spl_token_events(query: String, block: Block) -> Result<SplTokenBlockEvents, Error> {
+    let query = Query::parse(&query).map_err(Error::msg)?;
+    let mut block_events = _block_events(&block, &Params::default());
+    for transaction in block_events.transactions.iter_mut() {
+        transaction.events.retain(|x| query.matches(&event_keys(x)));
+    }
+    block_events.transactions.retain(|x| !x.events.is_empty());
+    Ok(block_events)
+}
+
+fn _block_events(block: &Block, params: &Params) -> SplTokenBlockEvents {
+    let transactions = parse_block(block, params);
+    SplTokenBlockEvents {
+        slot: block.slot,
+        block_time: block.block_time.as_ref().map(|x| x.timestamp).unwrap_or_default(),
+        block_hash: block.blockhash.clone(),
+        transactions,
+    }
+}
+
+pub fn parse_block(block: &Block, params: &Params) -> Vec<SplTokenTransactionEvents> {
+    let include_failed = params.get_bool("include_failed");
+    let include_memos = params.get_bool("include_memos");
+    let filter = Filter::new(params);
+    let mut block_events: Vec<SplTokenTransactionEvents> = Vec::new();
+    for (i, transaction) in block.transactions.iter().enumerate() {
+        let failed = transaction.meta.as_ref().map_or(false, |x| x.err.is_some());
+        if failed && !include_failed {
+            continue;
+        }
+        let signature = utils::transaction::get_signature(&transaction);
+        let result = if failed {
+            Ok(parse_failed_transaction(transaction, &filter))
+        } else {
+            parse_transaction(transaction, &filter)
+        };
+        match result {
+            Ok(mut events) if !events.is_empty() => {
+                if include_memos {
+                    attach_memo(&mut events, transaction);
+                }
+                block_events.push(SplTokenTransactionEvents {
+                    signature,
+                    transaction_index: i as u32,
+                    envelope: Some(get_transaction_envelope(transaction)),
+                    events,
+                });
+            },
+            Ok(_) => (),
+            Err(error) => substreams::log::println(format!("Failed to process transaction {}: {}", signature, error)),
+        }
+    }
+    block_events
+}
+
+pub fn parse_transaction(transaction: &ConfirmedTransaction, filter: &Filter) -> Result<Vec<SplTokenEvent>, ParseError> {
+    if let Some(_) = transaction.meta.as_ref().ok_or(ParseError::MissingTransactionMeta)?.err {
         return Ok(Vec::new())
     }
 
     let mut events: Vec<SplTokenEvent> = Vec::new();
 
-    let mut context = get_context(transaction)?;
-    let instructions = get_structured_instructions(transaction)?;
+    let mut context = get_context(transaction).map_err(|x| ParseError::TransactionContext(x.to_string()))?;
+    let mut lamports = LamportContext::new(transaction);
+    let mut mint_decimals = MintDecimals::new(transaction);
+    let instructions = get_structured_instructions(transaction).map_err(|x| ParseError::TransactionContext(x.to_string()))?;
 
-    for instruction in instructions.flattened().iter() {
+    let flattened = instructions.flattened();
+    for (i, instruction) in flattened.iter().enumerate() {
         context.update_balance(&instruction.instruction);
-        if instruction.program_id() == TOKEN_PROGRAM_ID {
-            let event = parse_instruction(instruction, &context)?;
-            events.push(SplTokenEvent { event });
+        lamports.update_balance(instruction);
+        if !is_token_instruction(instruction) {
+            continue;
+        }
+
+        let call_path = get_call_path(instruction, &flattened);
+        match parse_instruction(instruction, &context) {
+            Ok(mut event) => {
+                if let Some(event) = event.as_mut() {
+                    _annotate_lamports(event, &lamports);
+                    _annotate_decimals(event, &mut mint_decimals);
+                }
+                if !event.as_ref().map_or(filter.is_empty(), |x| filter.matches(x)) {
+                    continue;
+                }
+                events.push(SplTokenEvent {
+                    token_program: instruction.program_id().to_string(),
+                    instruction_index: i as u32,
+                    stack_depth: stack_depth(instruction),
+                    top_instruction_program_id: call_path.top_instruction_program_id,
+                    parent_instruction_program_id: call_path.parent_instruction_program_id,
+                    top_instruction_index: call_path.top_instruction_index,
+                    parent_instruction_index: call_path.parent_instruction_index,
+                    event,
+                });
+            },
+            Err(error) => substreams::log::println(format!("Failed to process instruction of transaction {}: {}", &context.signature, error)),
         }
     }
 
     Ok(events)
 }
 
+/// Sets the memo written right before each transfer at the same invocation level, if any, on its event.
+fn attach_memo(events: &mut [SplTokenEvent], transaction: &ConfirmedTransaction) {
+    let memos = get_transaction_memos(transaction);
+    if memos.is_empty() {
+        return;
+    }
+    for event in events.iter_mut() {
+        if let Some(Event::Transfer(transfer)) = event.event.as_mut() {
+            transfer.memo = memos.get(&event.instruction_index).cloned();
+        }
+    }
+}
+
+/// Parses a failed transaction into a `FailedInstruction` event, if it invoked a token program.
+pub fn parse_failed_transaction(transaction: &ConfirmedTransaction, filter: &Filter) -> Vec<SplTokenEvent> {
+    let token_program = [TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID].iter()
+        .map(|x| x.to_string())
+        .find(|x| is_program_invoked(transaction, x));
+
+    let events = match (token_program, get_failed_instruction(transaction, custom_error_name)) {
+        (Some(token_program), Some(failed_instruction)) => vec![SplTokenEvent {
+            token_program,
+            instruction_index: flattened_index(transaction, failed_instruction.instruction_index),
+            stack_depth: 0,
+            top_instruction_program_id: None,
+            parent_instruction_program_id: None,
+            top_instruction_index: None,
+            parent_instruction_index: None,
+            event: Some(Event::FailedInstruction(failed_instruction)),
+        }],
+        _ => Vec::new(),
+    };
+    events.into_iter().filter(|x| x.event.as_ref().map_or(false, |x| filter.matches(x))).collect()
+}
+
+/// Name of a custom error code raised by a token program.
+pub fn custom_error_name(program_id: &str, code: u32) -> Option<String> {
+    if program_id == TOKEN_PROGRAM_ID.to_string() || program_id == TOKEN_2022_PROGRAM_ID.to_string() {
+        token_error_name(code)
+    } else {
+        None
+    }
+}
+
+fn _annotate_lamports(event: &mut Event, lamports: &LamportContext) {
+    let token_accounts = match event {
+        Event::InitializeAccount(initialize_account) => vec![&mut initialize_account.account],
+        Event::Transfer(transfer) => vec![&mut transfer.source, &mut transfer.destination],
+        Event::Approve(approve) => vec![&mut approve.source],
+        Event::Revoke(revoke) => vec![&mut revoke.source],
+        Event::MintTo(mint_to) => vec![&mut mint_to.destination],
+        Event::Burn(burn) => vec![&mut burn.source],
+        Event::CloseAccount(close_account) => vec![&mut close_account.source],
+        Event::FreezeAccount(freeze_account) => vec![&mut freeze_account.source],
+        Event::ThawAccount(thaw_account) => vec![&mut thaw_account.source],
+        Event::InitializeImmutableOwner(initialize_immutable_owner) => vec![&mut initialize_immutable_owner.account],
+        Event::SyncNative(sync_native) => vec![&mut sync_native.account],
+        Event::WithdrawWithheldTokensFromMint(withdraw) => vec![&mut withdraw.destination],
+        Event::WithdrawWithheldTokensFromAccounts(withdraw) => vec![&mut withdraw.destination],
+        _ => Vec::new(),
+    };
+    for token_account in token_accounts.into_iter().flatten() {
+        token_account.pre_lamports = lamports.pre_lamports(&token_account.address);
+        token_account.post_lamports = lamports.post_lamports(&token_account.address);
+    }
+
+    match event {
+        Event::CloseAccount(close_account) => {
+            close_account.lamports = close_account.source.as_ref().and_then(|x| x.pre_lamports).unwrap_or(0);
+        },
+        Event::SyncNative(sync_native) => {
+            // The lamports picked up by the sync, above the token amount the account held before.
+            if let Some(account) = sync_native.account.as_ref() {
+                sync_native.amount = lamports.native_amount(&account.address).saturating_sub(account.pre_balance.unwrap_or(0));
+            }
+        },
+        _ => (),
+    }
+}
+
+fn _annotate_decimals(event: &mut Event, mint_decimals: &mut MintDecimals) {
+    let (mint, amount, decimals, ui_amount) = match event {
+        Event::InitializeMint(initialize_mint) => {
+            mint_decimals.insert(initialize_mint.mint.clone(), initialize_mint.decimals);
+            return;
+        },
+        Event::Transfer(transfer) => (transfer.source.as_ref().map(|x| x.mint.clone()), transfer.amount, &mut transfer.decimals, &mut transfer.ui_amount),
+        Event::Approve(approve) => (approve.source.as_ref().map(|x| x.mint.clone()), approve.amount, &mut approve.decimals, &mut approve.ui_amount),
+        Event::MintTo(mint_to) => (Some(mint_to.mint.clone()), mint_to.amount, &mut mint_to.decimals, &mut mint_to.ui_amount),
+        Event::Burn(burn) => (burn.source.as_ref().map(|x| x.mint.clone()), burn.amount, &mut burn.decimals, &mut burn.ui_amount),
+        _ => return,
+    };
+
+    if let Some(mint) = mint {
+        match *decimals {
+            Some(known) => mint_decimals.insert(mint, known),
+            None => *decimals = mint_decimals.get(&mint),
+        }
+    }
+    *ui_amount = decimals.map(|x| to_ui_amount(amount, x));
+}
+
+fn _account(instruction: &StructuredInstruction, index: usize) -> Result<String, ParseError> {
+    instruction.accounts().get(index).map(|x| x.to_string()).ok_or(ParseError::MissingAccount(index))
+}
+
+fn _token_account(
+    instruction: &StructuredInstruction,
+    context: &TransactionContext,
+    index: usize,
+) -> Result<TokenAccount, ParseError> {
+    let accounts = instruction.accounts();
+    let address = accounts.get(index).ok_or(ParseError::MissingAccount(index))?;
+    context.get_token_account(address).map(|x| x.into()).ok_or_else(|| ParseError::MissingTokenAccount(address.to_string()))
+}
+
+/// Returns true if the instruction was issued to either the Token or the Token-2022 program.
+pub fn is_token_instruction(instruction: &StructuredInstruction) -> bool {
+    is_token_program(&instruction.program_id())
+}
+
 pub fn parse_instruction<'a>(
     instruction: &StructuredInstruction<'a>,
     context: &TransactionContext,
-) -> Result<Option<Event>, Error> {
-    if instruction.program_id() != TOKEN_PROGRAM_ID {
-        return Err(anyhow!("Not a Token program instruction"));
+) -> Result<Option<Event>, ParseError> {
+    if !is_token_instruction(instruction) {
+        return Err(ParseError::UnexpectedProgram(instruction.program_id().to_string()));
+    }
+    if ExtensionInstruction::is_extension(instruction.data()) {
+        return _parse_extension_instruction(instruction, context);
     }
 
-    let unpacked = TokenInstruction::unpack(&instruction.data())
-        .map_err(|x| anyhow!(x).context("Failed to unpack Token instruction"))?;
+    let unpacked = match TokenInstruction::unpack(&instruction.data()) {
+        Ok(unpacked) => unpacked,
+        Err(_) => return Ok(Some(Event::UnknownInstruction(get_unknown_instruction(instruction, 1)))),
+    };
     match unpacked {
         TokenInstruction::InitializeMint { decimals, mint_authority, freeze_authority } |
         TokenInstruction::InitializeMint2 { decimals, mint_authority, freeze_authority } => {
             let event = _parse_initialize_mint_instruction(instruction, context, decimals as u32, mint_authority, freeze_authority);
-            event.map(|x| Some(Event::InitializeMint(x))).map_err(|x| anyhow!(x))
+            event.map(|x| Some(Event::InitializeMint(x)))
         },
 
         TokenInstruction::InitializeAccount => {
             let event = _parse_initialize_account_instruction(instruction, context, None);
-            event.map(|x| Some(Event::InitializeAccount(x))).map_err(|x| anyhow!(x))
+            event.map(|x| Some(Event::InitializeAccount(x)))
         },
         TokenInstruction::InitializeAccount2 { owner } |
         TokenInstruction::InitializeAccount3 { owner } => {
             let event = _parse_initialize_account_instruction(instruction, context, Some(owner));
-            event.map(|x| Some(Event::InitializeAccount(x))).map_err(|x| anyhow!(x))
+            event.map(|x| Some(Event::InitializeAccount(x)))
         },
 
         TokenInstruction::InitializeMultisig { m } => {
             let event = _parse_initialize_multisig_instruction(instruction, context, m, true);
-            event.map(|x| Some(Event::InitializeMultisig(x))).map_err(|x| anyhow!(x))
+            event.map(|x| Some(Event::InitializeMultisig(x)))
         }
         TokenInstruction::InitializeMultisig2 { m } => {
             let event = _parse_initialize_multisig_instruction(instruction, context, m, false);
-            event.map(|x| Some(Event::InitializeMultisig(x))).map_err(|x| anyhow!(x))
+            event.map(|x| Some(Event::InitializeMultisig(x)))
         },
 
         TokenInstruction::Transfer { amount } => {
-            let event = _parse_transfer_instruction(instruction, context, amount, None);
-            event.map(|x| Some(Event::Transfer(x))).map_err(|x| anyhow!(x))
+            let event = _parse_transfer_instruction(instruction, context, amount, None, None);
+            event.map(|x| Some(Event::Transfer(x)))
         },
         TokenInstruction::TransferChecked { amount, decimals } => {
-            let event = _parse_transfer_instruction(instruction, context, amount, Some(decimals));
-            event.map(|x| Some(Event::Transfer(x))).map_err(|x| anyhow!(x))
+            let event = _parse_transfer_instruction(instruction, context, amount, Some(decimals), None);
+            event.map(|x| Some(Event::Transfer(x)))
         },
 
         TokenInstruction::Approve { amount } => {
             let event = _parse_approve_instruction(instruction, context, amount, None);
-            event.map(|x| Some(Event::Approve(x))).map_err(|x| anyhow!(x))
+            event.map(|x| Some(Event::Approve(x)))
         },
         TokenInstruction::ApproveChecked { amount, decimals } => {
             let event = _parse_approve_instruction(instruction, context, amount, Some(decimals));
-            event.map(|x| Some(Event::Approve(x))).map_err(|x| anyhow!(x))
+            event.map(|x| Some(Event::Approve(x)))
         },
 
         TokenInstruction::Revoke => {
             let event = _parse_revoke_instruction(instruction, context);
-            event.map(|x| Some(Event::Revoke(x))).map_err(|x| anyhow!(x))
+            event.map(|x| Some(Event::Revoke(x)))
         },
 
         TokenInstruction::SetAuthority { authority_type, new_authority } => {
             let event = _parse_set_authority_instruction(instruction, context, authority_type, new_authority);
-            event.map(|x| Some(Event::SetAuthority(x))).map_err(|x| anyhow!(x))
+            event.map(|x| Some(Event::SetAuthority(x)))
         },
 
         TokenInstruction::MintTo { amount } => {
-            let event = _parse_mint_to_instruction(instruction, context, amount);
-            event.map(|x| Some(Event::MintTo(x))).map_err(|x| anyhow!(x))
+            let event = _parse_mint_to_instruction(instruction, context, amount, None);
+            event.map(|x| Some(Event::MintTo(x)))
         },
-        TokenInstruction::MintToChecked { amount, decimals: _ } => {
-            let event = _parse_mint_to_instruction(instruction, context, amount);
-            event.map(|x| Some(Event::MintTo(x))).map_err(|x| anyhow!(x))
+        TokenInstruction::MintToChecked { amount, decimals } => {
+            let event = _parse_mint_to_instruction(instruction, context, amount, Some(decimals));
+            event.map(|x| Some(Event::MintTo(x)))
         },
 
         TokenInstruction::Burn { amount } => {
-            let event = _parse_burn_instruction(instruction, context, amount);
-            event.map(|x| Some(Event::Burn(x))).map_err(|x| anyhow!(x))
+            let event = _parse_burn_instruction(instruction, context, amount, None);
+            event.map(|x| Some(Event::Burn(x)))
         },
-        TokenInstruction::BurnChecked { amount, decimals: _ } => {
-            let event = _parse_burn_instruction(instruction, context, amount);
-            event.map(|x| Some(Event::Burn(x))).map_err(|x| anyhow!(x))
+        TokenInstruction::BurnChecked { amount, decimals } => {
+            let event = _parse_burn_instruction(instruction, context, amount, Some(decimals));
+            event.map(|x| Some(Event::Burn(x)))
         },
 
         TokenInstruction::CloseAccount => {
             let event = _parse_close_account_instruction(instruction, context);
-            event.map(|x| Some(Event::CloseAccount(x))).map_err(|x| anyhow!(x))
+            event.map(|x| Some(Event::CloseAccount(x)))
         },
 
         TokenInstruction::FreezeAccount => {
             let event = _parse_freeze_account_instruction(instruction, context);
-            event.map(|x| Some(Event::FreezeAccount(x))).map_err(|x| anyhow!(x))
+            event.map(|x| Some(Event::FreezeAccount(x)))
         },
 
         TokenInstruction::ThawAccount => {
             let event = _parse_thaw_account_instruction(instruction, context);
-            event.map(|x| Some(Event::ThawAccount(x))).map_err(|x| anyhow!(x))
+            event.map(|x| Some(Event::ThawAccount(x)))
         },
 
         TokenInstruction::InitializeImmutableOwner => {
             let event = _parse_initialize_immutable_owner_instruction(instruction, context);
-            event.map(|x| Some(Event::InitializeImmutableOwner(x))).map_err(|x| anyhow!(x))
+            event.map(|x| Some(Event::InitializeImmutableOwner(x)))
         },
 
         TokenInstruction::SyncNative => {
             let event = _parse_sync_native_instruction(instruction, context);
-            event.map(|x| Some(Event::SyncNative(x))).map_err(|x| anyhow!(x))
+            event.map(|x| Some(Event::SyncNative(x)))
         },
         TokenInstruction::AmountToUiAmount { amount: _ } => Ok(None),
         TokenInstruction::GetAccountDataSize => Ok(None),
         TokenInstruction::UiAmountToAmount { ui_amount: _ } => Ok(None),
-    }.context("Failed to parse Token instruction")
+    }
+}
+
+fn _parse_extension_instruction<'a>(
+    instruction: &StructuredInstruction<'a>,
+    context: &TransactionContext,
+) -> Result<Option<Event>, ParseError> {
+    if instruction.program_id() != TOKEN_2022_PROGRAM_ID {
+        return Err(ParseError::UnexpectedProgram(instruction.program_id().to_string()));
+    }
+
+    // Extension instructions are identified by the extension and instruction type bytes.
+    let unpacked = match ExtensionInstruction::unpack(instruction.data()) {
+        Ok(unpacked) => unpacked,
+        Err(_) => return Ok(Some(Event::UnknownInstruction(get_unknown_instruction(instruction, 2)))),
+    };
+    match unpacked {
+        ExtensionInstruction::TransferCheckedWithFee(transfer) => {
+            let event = _parse_transfer_instruction(instruction, context, transfer.amount, Some(transfer.decimals), Some(transfer.fee));
+            event.map(|x| Some(Event::Transfer(x)))
+        },
+        ExtensionInstruction::InitializeTransferFeeConfig(initialize) => {
+            let event = _parse_initialize_transfer_fee_config_instruction(instruction, context, initialize);
+            event.map(|x| Some(Event::InitializeTransferFeeConfig(x)))
+        },
+        ExtensionInstruction::WithdrawWithheldTokensFromMint => {
+            let event = _parse_withdraw_withheld_tokens_from_mint_instruction(instruction, context);
+            event.map(|x| Some(Event::WithdrawWithheldTokensFromMint(x)))
+        },
+        ExtensionInstruction::WithdrawWithheldTokensFromAccounts { num_token_accounts } => {
+            let event = _parse_withdraw_withheld_tokens_from_accounts_instruction(instruction, context, num_token_accounts);
+            event.map(|x| Some(Event::WithdrawWithheldTokensFromAccounts(x)))
+        },
+        ExtensionInstruction::HarvestWithheldTokensToMint => {
+            let event = _parse_harvest_withheld_tokens_to_mint_instruction(instruction, context);
+            event.map(|x| Some(Event::HarvestWithheldTokensToMint(x)))
+        },
+        ExtensionInstruction::SetTransferFee(set_transfer_fee) => {
+            let event = _parse_set_transfer_fee_instruction(instruction, context, set_transfer_fee);
+            event.map(|x| Some(Event::SetTransferFee(x)))
+        },
+
+        ExtensionInstruction::InitializeDefaultAccountState { state } => {
+            let event = _parse_initialize_default_account_state_instruction(instruction, context, state);
+            event.map(|x| Some(Event::InitializeDefaultAccountState(x)))
+        },
+        ExtensionInstruction::UpdateDefaultAccountState { state } => {
+            let event = _parse_update_default_account_state_instruction(instruction, context, state);
+            event.map(|x| Some(Event::UpdateDefaultAccountState(x)))
+        },
+
+        ExtensionInstruction::EnableRequiredMemoTransfers => {
+            let event = _parse_memo_transfers_instruction(instruction, context);
+            event.map(|(account, owner)| Some(Event::EnableRequiredMemoTransfers(EnableRequiredMemoTransfersEvent { account, owner })))
+        },
+        ExtensionInstruction::DisableRequiredMemoTransfers => {
+            let event = _parse_memo_transfers_instruction(instruction, context);
+            event.map(|(account, owner)| Some(Event::DisableRequiredMemoTransfers(DisableRequiredMemoTransfersEvent { account, owner })))
+        },
+
+        ExtensionInstruction::InitializeInterestBearingMint(initialize) => {
+            let event = _parse_initialize_interest_bearing_mint_instruction(instruction, context, initialize);
+            event.map(|x| Some(Event::InitializeInterestBearingMint(x)))
+        },
+        ExtensionInstruction::UpdateInterestBearingMintRate { rate } => {
+            let event = _parse_update_interest_bearing_mint_rate_instruction(instruction, context, rate);
+            event.map(|x| Some(Event::UpdateInterestBearingMintRate(x)))
+        },
+
+        ExtensionInstruction::InitializeMetadataPointer(initialize) => {
+            let event = _parse_initialize_metadata_pointer_instruction(instruction, context, initialize);
+            event.map(|x| Some(Event::InitializeMetadataPointer(x)))
+        },
+        ExtensionInstruction::UpdateMetadataPointer { metadata_address } => {
+            let event = _parse_update_metadata_pointer_instruction(instruction, context, metadata_address);
+            event.map(|x| Some(Event::UpdateMetadataPointer(x)))
+        },
+
+        ExtensionInstruction::TokenMetadata(token_metadata) => {
+            _parse_token_metadata_instruction(instruction, context, token_metadata)
+        },
+        ExtensionInstruction::Unknown => Ok(Some(Event::UnknownInstruction(get_unknown_instruction(instruction, 2)))),
+    }
 }
 
 fn _parse_initialize_mint_instruction(
@@ -151,8 +486,8 @@ fn _parse_initialize_mint_instruction(
     decimals: u32,
     mint_authority: Pubkey,
     freeze_authority: Option<Pubkey>,
-) -> Result<InitializeMintEvent, &'static str> {
-    let mint = instruction.accounts()[0].to_string();
+) -> Result<InitializeMintEvent, ParseError> {
+    let mint = _account(instruction, 0)?;
     let mint_authority = mint_authority.to_string();
     let freeze_authority = freeze_authority.map(|x| x.to_string());
 
@@ -168,9 +503,8 @@ fn _parse_initialize_account_instruction(
     instruction: &StructuredInstruction,
     context: &TransactionContext,
     _owner: Option<Pubkey>,
-) -> Result<InitializeAccountEvent, &'static str> {
-    let address = &instruction.accounts()[0];
-    let token_account = context.get_token_account(address).unwrap();
+) -> Result<InitializeAccountEvent, ParseError> {
+    let token_account = _token_account(instruction, context, 0)?;
 
     Ok(InitializeAccountEvent {
         account: Some(token_account.into())
@@ -182,11 +516,11 @@ fn _parse_initialize_multisig_instruction(
     _context: &TransactionContext,
     m: u8,
     rent_sysvar_account: bool,
-) -> Result<InitializeMultisigEvent, &'static str> {
-    let multisig = instruction.accounts()[0].to_string();
+) -> Result<InitializeMultisigEvent, ParseError> {
+    let multisig = _account(instruction, 0)?;
     let mut signers: Vec<String> = Vec::new();
     let delta = if rent_sysvar_account { 2 } else { 1 };
-    for account in instruction.accounts()[delta..].iter() {
+    for account in instruction.accounts().iter().skip(delta) {
         signers.push(account.to_string());
     }
 
@@ -202,17 +536,22 @@ fn _parse_transfer_instruction(
     context: &TransactionContext,
     amount: u64,
     expected_decimals: Option<u8>,
-) -> Result<TransferEvent, &'static str> {
+    fee: Option<u64>,
+) -> Result<TransferEvent, ParseError> {
     let delta: usize = if expected_decimals.is_none() { 0 } else { 1 };
-    let source = context.get_token_account(&instruction.accounts()[0]).unwrap();
-    let destination = context.get_token_account(&instruction.accounts()[1 + delta]).unwrap();
-    let authority = instruction.accounts()[2 + delta].to_string();
+    let source = _token_account(instruction, context, 0)?;
+    let destination = _token_account(instruction, context, 1 + delta)?;
+    let authority = _account(instruction, 2 + delta)?;
 
     Ok(TransferEvent {
         source: Some(source.into()),
         destination: Some(destination.into()),
         amount,
         authority,
+        fee,
+        decimals: expected_decimals.map(|x| x.into()),
+        ui_amount: expected_decimals.map(|x| to_ui_amount(amount, x.into())),
+        memo: None,
     })
 }
 
@@ -221,23 +560,25 @@ fn _parse_approve_instruction(
     context: &TransactionContext,
     amount: u64,
     expected_decimals: Option<u8>,
-) -> Result<ApproveEvent, &'static str> {
+) -> Result<ApproveEvent, ParseError> {
     let delta: usize = if expected_decimals.is_none() { 0 } else { 1 };
-    let source = context.get_token_account(&instruction.accounts()[0]).unwrap();
-    let delegate = instruction.accounts()[1 + delta].to_string();
+    let source = _token_account(instruction, context, 0)?;
+    let delegate = _account(instruction, 1 + delta)?;
 
     Ok(ApproveEvent {
         source: Some(source.into()),
         delegate,
         amount,
+        decimals: expected_decimals.map(|x| x.into()),
+        ui_amount: expected_decimals.map(|x| to_ui_amount(amount, x.into())),
     })
 }
 
 fn _parse_revoke_instruction(
     instruction: &StructuredInstruction,
     context: &TransactionContext,
-) -> Result<RevokeEvent, &'static str> {
-    let source = context.get_token_account(&instruction.accounts()[0]).unwrap();
+) -> Result<RevokeEvent, ParseError> {
+    let source = _token_account(instruction, context, 0)?;
 
     Ok(RevokeEvent {
         source: Some(source.into()),
@@ -249,9 +590,9 @@ fn _parse_set_authority_instruction(
     _context: &TransactionContext,
     authority_type: utils::spl_token::AuthorityType,
     new_authority: Option<Pubkey>,
-) -> Result<SetAuthorityEvent, &'static str> {
-    let mint = instruction.accounts()[0].to_string();
-    let authority = instruction.accounts()[1].to_string();
+) -> Result<SetAuthorityEvent, ParseError> {
+    let mint = _account(instruction, 0)?;
+    let authority = _account(instruction, 1)?;
     let authority_type: i32 = match authority_type {
         utils::spl_token::AuthorityType::MintTokens => AuthorityType::MintTokens.into(),
         utils::spl_token::AuthorityType::FreezeAccount => AuthorityType::FreezeAccount.into(),
@@ -272,16 +613,19 @@ fn _parse_mint_to_instruction(
     instruction: &StructuredInstruction,
     context: &TransactionContext,
     amount: u64,
-) -> Result<MintToEvent, &'static str> {
-    let mint = instruction.accounts()[0].to_string();
-    let destination = context.get_token_account(&instruction.accounts()[1]).unwrap();
-    let mint_authority = instruction.accounts()[2].to_string();
+    expected_decimals: Option<u8>,
+) -> Result<MintToEvent, ParseError> {
+    let mint = _account(instruction, 0)?;
+    let destination = _token_account(instruction, context, 1)?;
+    let mint_authority = _account(instruction, 2)?;
 
     Ok(MintToEvent {
         mint,
         destination: Some(destination.into()),
         mint_authority,
         amount,
+        decimals: expected_decimals.map(|x| x.into()),
+        ui_amount: expected_decimals.map(|x| to_ui_amount(amount, x.into())),
     })
 }
 
@@ -289,37 +633,41 @@ fn _parse_burn_instruction(
     instruction: &StructuredInstruction,
     context: &TransactionContext,
     amount: u64,
-) -> Result<BurnEvent, &'static str> {
-    let source = context.get_token_account(&instruction.accounts()[0]).unwrap();
-    let _mint = instruction.accounts()[1].to_string();
-    let authority = instruction.accounts()[2].to_string();
+    expected_decimals: Option<u8>,
+) -> Result<BurnEvent, ParseError> {
+    let source = _token_account(instruction, context, 0)?;
+    let _mint = _account(instruction, 1)?;
+    let authority = _account(instruction, 2)?;
 
     Ok(BurnEvent {
         source: Some(source.into()),
         authority,
         amount,
+        decimals: expected_decimals.map(|x| x.into()),
+        ui_amount: expected_decimals.map(|x| to_ui_amount(amount, x.into())),
     })
 }
 
 fn _parse_close_account_instruction(
     instruction: &StructuredInstruction,
     context: &TransactionContext,
-) -> Result<CloseAccountEvent, &'static str> {
-    let source = context.get_token_account(&instruction.accounts()[0]).unwrap();
-    let destination = instruction.accounts()[1].to_string();
+) -> Result<CloseAccountEvent, ParseError> {
+    let source = _token_account(instruction, context, 0)?;
+    let destination = _account(instruction, 1)?;
 
     Ok(CloseAccountEvent {
         source: Some(source.into()),
         destination,
+        lamports: 0,
     })
 }
 
 fn _parse_freeze_account_instruction(
     instruction: &StructuredInstruction,
     context: &TransactionContext,
-) -> Result<FreezeAccountEvent, &'static str> {
-    let source = context.get_token_account(&instruction.accounts()[0]).unwrap();
-    let freeze_authority = instruction.accounts()[1].to_string();
+) -> Result<FreezeAccountEvent, ParseError> {
+    let source = _token_account(instruction, context, 0)?;
+    let freeze_authority = _account(instruction, 1)?;
 
     Ok(FreezeAccountEvent {
         source: Some(source.into()),
@@ -330,9 +678,9 @@ fn _parse_freeze_account_instruction(
 fn _parse_thaw_account_instruction(
     instruction: &StructuredInstruction,
     context: &TransactionContext,
-) -> Result<ThawAccountEvent, &'static str> {
-    let source = context.get_token_account(&instruction.accounts()[0]).unwrap();
-    let freeze_authority = instruction.accounts()[1].to_string();
+) -> Result<ThawAccountEvent, ParseError> {
+    let source = _token_account(instruction, context, 0)?;
+    let freeze_authority = _account(instruction, 1)?;
 
     Ok(ThawAccountEvent {
         source: Some(source.into()),
@@ -343,8 +691,8 @@ fn _parse_thaw_account_instruction(
 fn _parse_initialize_immutable_owner_instruction(
     instruction: &StructuredInstruction,
     context: &TransactionContext,
-) -> Result<InitializeImmutableOwnerEvent, &'static str> {
-    let account = context.get_token_account(&instruction.accounts()[0]).unwrap();
+) -> Result<InitializeImmutableOwnerEvent, ParseError> {
+    let account = _token_account(instruction, context, 0)?;
 
     Ok(InitializeImmutableOwnerEvent {
         account: Some(account.into()),
@@ -354,41 +702,290 @@ fn _parse_initialize_immutable_owner_instruction(
 fn _parse_sync_native_instruction(
     instruction: &StructuredInstruction,
     context: &TransactionContext,
-) -> Result<SyncNativeEvent, &'static str> {
-    let account = context.get_token_account(&instruction.accounts()[0]).unwrap();
+) -> Result<SyncNativeEvent, ParseError> {
+    let account = _token_account(instruction, context, 0)?;
 
     Ok(SyncNativeEvent {
-        account: Some(account.into())
+        account: Some(account.into()),
+        amount: 0,
+    })
+}
+
+fn _parse_initialize_transfer_fee_config_instruction(
+    instruction: &StructuredInstruction,
+    _context: &TransactionContext,
+    initialize: token_2022::instruction::InitializeTransferFeeConfigInstruction,
+) -> Result<InitializeTransferFeeConfigEvent, ParseError> {
+    let mint = _account(instruction, 0)?;
+    let transfer_fee_config_authority = initialize.transfer_fee_config_authority.map(|x| x.to_string());
+    let withdraw_withheld_authority = initialize.withdraw_withheld_authority.map(|x| x.to_string());
+
+    Ok(InitializeTransferFeeConfigEvent {
+        mint,
+        transfer_fee_config_authority,
+        withdraw_withheld_authority,
+        transfer_fee_basis_points: initialize.transfer_fee_basis_points.into(),
+        maximum_fee: initialize.maximum_fee,
+    })
+}
+
+fn _parse_withdraw_withheld_tokens_from_mint_instruction(
+    instruction: &StructuredInstruction,
+    context: &TransactionContext,
+) -> Result<WithdrawWithheldTokensFromMintEvent, ParseError> {
+    let mint = _account(instruction, 0)?;
+    let destination = _token_account(instruction, context, 1)?;
+    let authority = _account(instruction, 2)?;
+
+    Ok(WithdrawWithheldTokensFromMintEvent {
+        mint,
+        destination: Some(destination.into()),
+        authority,
+    })
+}
+
+fn _parse_withdraw_withheld_tokens_from_accounts_instruction(
+    instruction: &StructuredInstruction,
+    context: &TransactionContext,
+    num_token_accounts: u8,
+) -> Result<WithdrawWithheldTokensFromAccountsEvent, ParseError> {
+    let mint = _account(instruction, 0)?;
+    let destination = _token_account(instruction, context, 1)?;
+    let authority = _account(instruction, 2)?;
+    let first_source = instruction.accounts().len().saturating_sub(num_token_accounts as usize).max(3);
+    let sources = instruction.accounts().iter().skip(first_source).map(|x| x.to_string()).collect();
+
+    Ok(WithdrawWithheldTokensFromAccountsEvent {
+        mint,
+        destination: Some(destination.into()),
+        authority,
+        sources,
+    })
+}
+
+fn _parse_harvest_withheld_tokens_to_mint_instruction(
+    instruction: &StructuredInstruction,
+    _context: &TransactionContext,
+) -> Result<HarvestWithheldTokensToMintEvent, ParseError> {
+    let mint = _account(instruction, 0)?;
+    let sources = instruction.accounts().iter().skip(1).map(|x| x.to_string()).collect();
+
+    Ok(HarvestWithheldTokensToMintEvent {
+        mint,
+        sources,
+    })
+}
+
+fn _parse_set_transfer_fee_instruction(
+    instruction: &StructuredInstruction,
+    _context: &TransactionContext,
+    set_transfer_fee: token_2022::instruction::SetTransferFeeInstruction,
+) -> Result<SetTransferFeeEvent, ParseError> {
+    let mint = _account(instruction, 0)?;
+    let authority = _account(instruction, 1)?;
+
+    Ok(SetTransferFeeEvent {
+        mint,
+        authority,
+        transfer_fee_basis_points: set_transfer_fee.transfer_fee_basis_points.into(),
+        maximum_fee: set_transfer_fee.maximum_fee,
+    })
+}
+
+fn _parse_initialize_default_account_state_instruction(
+    instruction: &StructuredInstruction,
+    _context: &TransactionContext,
+    state: u8,
+) -> Result<InitializeDefaultAccountStateEvent, ParseError> {
+    let mint = _account(instruction, 0)?;
+
+    Ok(InitializeDefaultAccountStateEvent {
+        mint,
+        state: _account_state(state)?.into(),
+    })
+}
+
+fn _parse_update_default_account_state_instruction(
+    instruction: &StructuredInstruction,
+    _context: &TransactionContext,
+    state: u8,
+) -> Result<UpdateDefaultAccountStateEvent, ParseError> {
+    let mint = _account(instruction, 0)?;
+    let freeze_authority = _account(instruction, 1)?;
+
+    Ok(UpdateDefaultAccountStateEvent {
+        mint,
+        freeze_authority,
+        state: _account_state(state)?.into(),
     })
 }
 
+fn _account_state(state: u8) -> Result<AccountState, ParseError> {
+    match state {
+        0 => Ok(AccountState::Uninitialized),
+        1 => Ok(AccountState::Initialized),
+        2 => Ok(AccountState::Frozen),
+        _ => Err(ParseError::InvalidInstructionData(format!("invalid account state {}", state))),
+    }
+}
+
+fn _parse_memo_transfers_instruction(
+    instruction: &StructuredInstruction,
+    _context: &TransactionContext,
+) -> Result<(String, String), ParseError> {
+    let account = _account(instruction, 0)?;
+    let owner = _account(instruction, 1)?;
+
+    Ok((account, owner))
+}
+
+fn _parse_initialize_interest_bearing_mint_instruction(
+    instruction: &StructuredInstruction,
+    _context: &TransactionContext,
+    initialize: token_2022::instruction::InitializeInterestBearingMintInstruction,
+) -> Result<InitializeInterestBearingMintEvent, ParseError> {
+    let mint = _account(instruction, 0)?;
+    let rate_authority = initialize.rate_authority.map(|x| x.to_string());
+
+    Ok(InitializeInterestBearingMintEvent {
+        mint,
+        rate_authority,
+        rate: initialize.rate.into(),
+    })
+}
+
+fn _parse_update_interest_bearing_mint_rate_instruction(
+    instruction: &StructuredInstruction,
+    _context: &TransactionContext,
+    rate: i16,
+) -> Result<UpdateInterestBearingMintRateEvent, ParseError> {
+    let mint = _account(instruction, 0)?;
+    let rate_authority = _account(instruction, 1)?;
+
+    Ok(UpdateInterestBearingMintRateEvent {
+        mint,
+        rate_authority,
+        rate: rate.into(),
+    })
+}
+
+fn _parse_initialize_metadata_pointer_instruction(
+    instruction: &StructuredInstruction,
+    _context: &TransactionContext,
+    initialize: token_2022::instruction::InitializeMetadataPointerInstruction,
+) -> Result<InitializeMetadataPointerEvent, ParseError> {
+    let mint = _account(instruction, 0)?;
+    let authority = initialize.authority.map(|x| x.to_string());
+    let metadata_address = initialize.metadata_address.map(|x| x.to_string());
+
+    Ok(InitializeMetadataPointerEvent {
+        mint,
+        authority,
+        metadata_address,
+    })
+}
+
+fn _parse_update_metadata_pointer_instruction(
+    instruction: &StructuredInstruction,
+    _context: &TransactionContext,
+    metadata_address: Option<Pubkey>,
+) -> Result<UpdateMetadataPointerEvent, ParseError> {
+    let mint = _account(instruction, 0)?;
+    let authority = _account(instruction, 1)?;
+    let metadata_address = metadata_address.map(|x| x.to_string());
+
+    Ok(UpdateMetadataPointerEvent {
+        mint,
+        authority,
+        metadata_address,
+    })
+}
+
+fn _parse_token_metadata_instruction(
+    instruction: &StructuredInstruction,
+    _context: &TransactionContext,
+    token_metadata: TokenMetadataInstruction,
+) -> Result<Option<Event>, ParseError> {
+    let metadata = _account(instruction, 0)?;
+    let update_authority = _account(instruction, 1)?;
+
+    match token_metadata {
+        TokenMetadataInstruction::Initialize(initialize) => {
+            let mint = _account(instruction, 2)?;
+            let mint_authority = _account(instruction, 3)?;
+            Ok(Some(Event::TokenMetadataInitialize(TokenMetadataInitializeEvent {
+                metadata,
+                update_authority,
+                mint,
+                mint_authority,
+                name: initialize.name,
+                symbol: initialize.symbol,
+                uri: initialize.uri,
+            })))
+        },
+        TokenMetadataInstruction::UpdateField(update_field) => {
+            let field = match update_field.field {
+                TokenMetadataField::Name => "name".to_string(),
+                TokenMetadataField::Symbol => "symbol".to_string(),
+                TokenMetadataField::Uri => "uri".to_string(),
+                TokenMetadataField::Key(key) => key,
+            };
+            Ok(Some(Event::TokenMetadataUpdateField(TokenMetadataUpdateFieldEvent {
+                metadata,
+                update_authority,
+                field,
+                value: update_field.value,
+            })))
+        },
+        TokenMetadataInstruction::RemoveKey(remove_key) => {
+            Ok(Some(Event::TokenMetadataRemoveKey(TokenMetadataRemoveKeyEvent {
+                metadata,
+                update_authority,
+                idempotent: remove_key.idempotent,
+                key: remove_key.key,
+            })))
+        },
+        TokenMetadataInstruction::UpdateAuthority { new_authority } => {
+            Ok(Some(Event::TokenMetadataUpdateAuthority(TokenMetadataUpdateAuthorityEvent {
+                metadata,
+                update_authority,
+                new_authority: new_authority.map(|x| x.to_string()),
+            })))
+        },
+        TokenMetadataInstruction::Emit => Ok(None),
+    }
+}
+
 pub fn parse_initialize_mint_instruction<'a>(
     instruction: &StructuredInstruction<'a>,
     context: &TransactionContext,
-) -> Result<InitializeMintEvent, &'static str> {
+) -> Result<InitializeMintEvent, ParseError> {
     match parse_instruction(instruction, context) {
         Ok(Some(Event::InitializeMint(initialize_mint))) => Ok(initialize_mint),
-        _ => Err("Failed to parse initialize mint instruction."),
+        Ok(_) => Err(ParseError::InstructionMismatch("initialize mint")),
+        Err(error) => Err(error),
     }
 }
 
 pub fn parse_initialize_account_instruction<'a>(
     instruction: &StructuredInstruction<'a>,
     context: &TransactionContext,
-) -> Result<InitializeAccountEvent, &'static str> {
+) -> Result<InitializeAccountEvent, ParseError> {
     match parse_instruction(instruction, context) {
         Ok(Some(Event::InitializeAccount(initialize_account))) => Ok(initialize_account),
-        _ => Err("Failed to parse initialize account instruction."),
+        Ok(_) => Err(ParseError::InstructionMismatch("initialize account")),
+        Err(error) => Err(error),
     }
 }
 
 pub fn parse_initialize_multisig_instruction<'a>(
     instruction: &StructuredInstruction<'a>,
     context: &TransactionContext,
-) -> Result<InitializeMultisigEvent, &'static str> {
+) -> Result<InitializeMultisigEvent, ParseError> {
     match parse_instruction(instruction, context) {
         Ok(Some(Event::InitializeMultisig(initialize_multisig))) => Ok(initialize_multisig),
-        _ => Err("Failed to parse initialize multisig instruction."),
+        Ok(_) => Err(ParseError::InstructionMismatch("initialize multisig")),
+        Err(error) => Err(error),
     }
 }
 
@@ -396,60 +993,66 @@ pub fn parse_initialize_multisig_instruction<'a>(
 pub fn parse_transfer_instruction<'a>(
     instruction: &StructuredInstruction<'a>,
     context: &TransactionContext,
-) -> Result<TransferEvent, &'static str> {
+) -> Result<TransferEvent, ParseError> {
     match parse_instruction(instruction, context) {
         Ok(Some(Event::Transfer(transfer))) => Ok(transfer),
-        _ => Err("Failed to parse transfer instruction."),
+        Ok(_) => Err(ParseError::InstructionMismatch("transfer")),
+        Err(error) => Err(error),
     }
 }
 
 pub fn parse_approve_instruction<'a>(
     instruction: &StructuredInstruction<'a>,
     context: &TransactionContext,
-) -> Result<ApproveEvent, &'static str> {
+) -> Result<ApproveEvent, ParseError> {
     match parse_instruction(instruction, context) {
         Ok(Some(Event::Approve(approve))) => Ok(approve),
-        _ => Err("Failed to parse approve instruction."),
+        Ok(_) => Err(ParseError::InstructionMismatch("approve")),
+        Err(error) => Err(error),
     }
 }
 
 pub fn parse_revoke_instruction<'a>(
     instruction: &StructuredInstruction<'a>,
     context: &TransactionContext,
-) -> Result<RevokeEvent, &'static str> {
+) -> Result<RevokeEvent, ParseError> {
     match parse_instruction(instruction, context) {
         Ok(Some(Event::Revoke(revoke))) => Ok(revoke),
-        _ => Err("Failed to parse revoke instruction."),
+        Ok(_) => Err(ParseError::InstructionMismatch("revoke")),
+        Err(error) => Err(error),
     }
 }
 
 pub fn parse_set_authority_instruction<'a>(
     instruction: &StructuredInstruction<'a>,
     context: &TransactionContext,
-) -> Result<SetAuthorityEvent, &'static str> {
+) -> Result<SetAuthorityEvent, ParseError> {
     match parse_instruction(instruction, context) {
         Ok(Some(Event::SetAuthority(set_authority))) => Ok(set_authority),
-        _ => Err("Failed to parse set authority instruction."),
+        Ok(_) => Err(ParseError::InstructionMismatch("set authority")),
+        Err(error) => Err(error),
     }
 }
 
 pub fn parse_mint_to_instruction<'a>(
     instruction: &StructuredInstruction<'a>,
     context: &TransactionContext,
-) -> Result<MintToEvent, &'static str> {
+) -> Result<MintToEvent, ParseError> {
     match parse_instruction(instruction, context) {
         Ok(Some(Event::MintTo(mint_to))) => Ok(mint_to),
-        _ => Err("Failed to parse mint to instruction."),
+        Ok(_) => Err(ParseError::InstructionMismatch("mint to")),
+        Err(error) => Err(error),
     }
 }
 
 pub fn parse_burn_instruction<'a>(
     instruction: &StructuredInstruction<'a>,
     context: &TransactionContext,
-) -> Result<BurnEvent, &'static str> {
+) -> Result<BurnEvent, ParseError> {
     match parse_instruction(instruction, context) {
         Ok(Some(Event::Burn(burn))) => Ok(burn),
-        _ => Err("Failed to parse burn instruction."),
+        Ok(_) => Err(ParseError::InstructionMismatch("burn")),
+        Err(error) => Err(error),
     }
 }
 
@@ -457,40 +1060,44 @@ pub fn parse_burn_instruction<'a>(
 pub fn parse_close_account_instruction<'a>(
     instruction: &StructuredInstruction<'a>,
     context: &TransactionContext,
-) -> Result<CloseAccountEvent, &'static str> {
+) -> Result<CloseAccountEvent, ParseError> {
     match parse_instruction(instruction, context) {
         Ok(Some(Event::CloseAccount(close_account))) => Ok(close_account),
-        _ => Err("Failed to parse close account instruction."),
+        Ok(_) => Err(ParseError::InstructionMismatch("close account")),
+        Err(error) => Err(error),
     }
 }
 
 pub fn parse_freeze_account_instruction<'a>(
     instruction: &StructuredInstruction<'a>,
     context: &TransactionContext,
-) -> Result<FreezeAccountEvent, &'static str> {
+) -> Result<FreezeAccountEvent, ParseError> {
     match parse_instruction(instruction, context) {
         Ok(Some(Event::FreezeAccount(freeze_account))) => Ok(freeze_account),
-        _ => Err("Failed to parse freeze account instruction."),
+        Ok(_) => Err(ParseError::InstructionMismatch("freeze account")),
+        Err(error) => Err(error),
     }
 }
 
 pub fn parse_thaw_account_instruction<'a>(
     instruction: &StructuredInstruction<'a>,
     context: &TransactionContext,
-) -> Result<ThawAccountEvent, &'static str> {
+) -> Result<ThawAccountEvent, ParseError> {
     match parse_instruction(instruction, context) {
         Ok(Some(Event::ThawAccount(thaw_account))) => Ok(thaw_account),
-        _ => Err("Failed to parse thaw account instruction."),
+        Ok(_) => Err(ParseError::InstructionMismatch("thaw account")),
+        Err(error) => Err(error),
     }
 }
 
 pub fn parse_initialize_immutable_owner_instruction<'a>(
     instruction: &StructuredInstruction<'a>,
     context: &TransactionContext,
-) -> Result<InitializeImmutableOwnerEvent, &'static str> {
+) -> Result<InitializeImmutableOwnerEvent, ParseError> {
     match parse_instruction(instruction, context) {
         Ok(Some(Event::InitializeImmutableOwner(initialize_immutable_owner))) => Ok(initialize_immutable_owner),
-        _ => Err("Failed to parse initialize immutable owner instruction."),
+        Ok(_) => Err(ParseError::InstructionMismatch("initialize immutable owner")),
+        Err(error) => Err(error),
     }
 }
 
@@ -502,6 +1109,8 @@ impl<'a> From<&'a utils::spl_token::TokenAccount<'a>> for TokenAccount {
             mint: value.mint.to_string(),
             pre_balance: value.pre_balance,
             post_balance: value.post_balance,
+            pre_lamports: None,
+            post_lamports: None,
         }
     }
 }
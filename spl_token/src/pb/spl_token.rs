@@ -4,19 +4,44 @@
 pub struct SplTokenBlockEvents {
     #[prost(message, repeated, tag="1")]
     pub transactions: ::prost::alloc::vec::Vec<SplTokenTransactionEvents>,
+    #[prost(uint64, tag="2")]
+    pub slot: u64,
+    #[prost(int64, tag="3")]
+    pub block_time: i64,
+    #[prost(string, tag="4")]
+    pub block_hash: ::prost::alloc::string::String,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SplTokenTransactionEvents {
     #[prost(string, tag="1")]
     pub signature: ::prost::alloc::string::String,
+    #[prost(uint32, tag="2")]
+    pub transaction_index: u32,
     #[prost(message, repeated, tag="3")]
     pub events: ::prost::alloc::vec::Vec<SplTokenEvent>,
+    #[prost(message, optional, tag="4")]
+    pub envelope: ::core::option::Option<::solana_substreams_common::pb::common::TransactionEnvelope>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SplTokenEvent {
-    #[prost(oneof="spl_token_event::Event", tags="1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14")]
+    #[prost(string, tag="15")]
+    pub token_program: ::prost::alloc::string::String,
+    #[prost(uint32, tag="34")]
+    pub instruction_index: u32,
+    /// Depth in the invocation stack, 0 for top level instructions.
+    #[prost(uint32, tag="35")]
+    pub stack_depth: u32,
+    #[prost(string, optional, tag="36")]
+    pub top_instruction_program_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag="37")]
+    pub parent_instruction_program_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(uint32, optional, tag="38")]
+    pub top_instruction_index: ::core::option::Option<u32>,
+    #[prost(uint32, optional, tag="39")]
+    pub parent_instruction_index: ::core::option::Option<u32>,
+    #[prost(oneof="spl_token_event::Event", tags="1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 40")]
     pub event: ::core::option::Option<spl_token_event::Event>,
 }
 /// Nested message and enum types in `SplTokenEvent`.
@@ -52,6 +77,44 @@ pub mod spl_token_event {
         ThawAccount(super::ThawAccountEvent),
         #[prost(message, tag="14")]
         SyncNative(super::SyncNativeEvent),
+        #[prost(message, tag="16")]
+        InitializeTransferFeeConfig(super::InitializeTransferFeeConfigEvent),
+        #[prost(message, tag="17")]
+        WithdrawWithheldTokensFromMint(super::WithdrawWithheldTokensFromMintEvent),
+        #[prost(message, tag="18")]
+        WithdrawWithheldTokensFromAccounts(super::WithdrawWithheldTokensFromAccountsEvent),
+        #[prost(message, tag="19")]
+        HarvestWithheldTokensToMint(super::HarvestWithheldTokensToMintEvent),
+        #[prost(message, tag="20")]
+        SetTransferFee(super::SetTransferFeeEvent),
+        #[prost(message, tag="21")]
+        InitializeDefaultAccountState(super::InitializeDefaultAccountStateEvent),
+        #[prost(message, tag="22")]
+        UpdateDefaultAccountState(super::UpdateDefaultAccountStateEvent),
+        #[prost(message, tag="23")]
+        EnableRequiredMemoTransfers(super::EnableRequiredMemoTransfersEvent),
+        #[prost(message, tag="24")]
+        DisableRequiredMemoTransfers(super::DisableRequiredMemoTransfersEvent),
+        #[prost(message, tag="25")]
+        InitializeInterestBearingMint(super::InitializeInterestBearingMintEvent),
+        #[prost(message, tag="26")]
+        UpdateInterestBearingMintRate(super::UpdateInterestBearingMintRateEvent),
+        #[prost(message, tag="27")]
+        InitializeMetadataPointer(super::InitializeMetadataPointerEvent),
+        #[prost(message, tag="28")]
+        UpdateMetadataPointer(super::UpdateMetadataPointerEvent),
+        #[prost(message, tag="29")]
+        TokenMetadataInitialize(super::TokenMetadataInitializeEvent),
+        #[prost(message, tag="30")]
+        TokenMetadataUpdateField(super::TokenMetadataUpdateFieldEvent),
+        #[prost(message, tag="31")]
+        TokenMetadataRemoveKey(super::TokenMetadataRemoveKeyEvent),
+        #[prost(message, tag="32")]
+        TokenMetadataUpdateAuthority(super::TokenMetadataUpdateAuthorityEvent),
+        #[prost(message, tag="33")]
+        FailedInstruction(::solana_substreams_common::pb::common::FailedInstruction),
+        #[prost(message, tag="40")]
+        UnknownInstruction(::solana_substreams_common::pb::common::UnknownInstruction),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -91,10 +154,17 @@ pub struct TransferEvent {
     pub destination: ::core::option::Option<TokenAccount>,
     #[prost(string, tag="3")]
     pub authority: ::prost::alloc::string::String,
-    /// optional uint64 source_pre_balance = 5;
-    /// optional uint64 destination_pre_balance = 6;
     #[prost(uint64, tag="4")]
     pub amount: u64,
+    #[prost(uint64, optional, tag="5")]
+    pub fee: ::core::option::Option<u64>,
+    #[prost(uint32, optional, tag="6")]
+    pub decimals: ::core::option::Option<u32>,
+    #[prost(string, optional, tag="7")]
+    pub ui_amount: ::core::option::Option<::prost::alloc::string::String>,
+    /// Memo written right before the transfer at the same invocation level, set with the `include_memos` param.
+    #[prost(string, optional, tag="8")]
+    pub memo: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -105,6 +175,10 @@ pub struct ApproveEvent {
     pub delegate: ::prost::alloc::string::String,
     #[prost(uint64, tag="3")]
     pub amount: u64,
+    #[prost(uint32, optional, tag="4")]
+    pub decimals: ::core::option::Option<u32>,
+    #[prost(string, optional, tag="5")]
+    pub ui_amount: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -135,6 +209,10 @@ pub struct MintToEvent {
     pub destination: ::core::option::Option<TokenAccount>,
     #[prost(uint64, tag="4")]
     pub amount: u64,
+    #[prost(uint32, optional, tag="5")]
+    pub decimals: ::core::option::Option<u32>,
+    #[prost(string, optional, tag="6")]
+    pub ui_amount: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -145,15 +223,20 @@ pub struct BurnEvent {
     pub authority: ::prost::alloc::string::String,
     #[prost(uint64, tag="2")]
     pub amount: u64,
+    #[prost(uint32, optional, tag="4")]
+    pub decimals: ::core::option::Option<u32>,
+    #[prost(string, optional, tag="5")]
+    pub ui_amount: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CloseAccountEvent {
     #[prost(message, optional, tag="1")]
     pub source: ::core::option::Option<TokenAccount>,
-    /// TODO: amount
     #[prost(string, tag="2")]
     pub destination: ::prost::alloc::string::String,
+    #[prost(uint64, tag="3")]
+    pub lamports: u64,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -180,9 +263,192 @@ pub struct InitializeImmutableOwnerEvent {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SyncNativeEvent {
-    /// TODO: amount
     #[prost(message, optional, tag="1")]
     pub account: ::core::option::Option<TokenAccount>,
+    #[prost(uint64, tag="2")]
+    pub amount: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct InitializeTransferFeeConfigEvent {
+    #[prost(string, tag="1")]
+    pub mint: ::prost::alloc::string::String,
+    #[prost(string, optional, tag="2")]
+    pub transfer_fee_config_authority: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag="3")]
+    pub withdraw_withheld_authority: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(uint32, tag="4")]
+    pub transfer_fee_basis_points: u32,
+    #[prost(uint64, tag="5")]
+    pub maximum_fee: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WithdrawWithheldTokensFromMintEvent {
+    #[prost(string, tag="1")]
+    pub mint: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="2")]
+    pub destination: ::core::option::Option<TokenAccount>,
+    #[prost(string, tag="3")]
+    pub authority: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WithdrawWithheldTokensFromAccountsEvent {
+    #[prost(string, tag="1")]
+    pub mint: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="2")]
+    pub destination: ::core::option::Option<TokenAccount>,
+    #[prost(string, tag="3")]
+    pub authority: ::prost::alloc::string::String,
+    #[prost(string, repeated, tag="4")]
+    pub sources: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HarvestWithheldTokensToMintEvent {
+    #[prost(string, tag="1")]
+    pub mint: ::prost::alloc::string::String,
+    #[prost(string, repeated, tag="2")]
+    pub sources: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetTransferFeeEvent {
+    #[prost(string, tag="1")]
+    pub mint: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub authority: ::prost::alloc::string::String,
+    #[prost(uint32, tag="3")]
+    pub transfer_fee_basis_points: u32,
+    #[prost(uint64, tag="4")]
+    pub maximum_fee: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct InitializeDefaultAccountStateEvent {
+    #[prost(string, tag="1")]
+    pub mint: ::prost::alloc::string::String,
+    #[prost(enumeration="AccountState", tag="2")]
+    pub state: i32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateDefaultAccountStateEvent {
+    #[prost(string, tag="1")]
+    pub mint: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub freeze_authority: ::prost::alloc::string::String,
+    #[prost(enumeration="AccountState", tag="3")]
+    pub state: i32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EnableRequiredMemoTransfersEvent {
+    #[prost(string, tag="1")]
+    pub account: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub owner: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DisableRequiredMemoTransfersEvent {
+    #[prost(string, tag="1")]
+    pub account: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub owner: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct InitializeInterestBearingMintEvent {
+    #[prost(string, tag="1")]
+    pub mint: ::prost::alloc::string::String,
+    #[prost(string, optional, tag="2")]
+    pub rate_authority: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(int32, tag="3")]
+    pub rate: i32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateInterestBearingMintRateEvent {
+    #[prost(string, tag="1")]
+    pub mint: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub rate_authority: ::prost::alloc::string::String,
+    #[prost(int32, tag="3")]
+    pub rate: i32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct InitializeMetadataPointerEvent {
+    #[prost(string, tag="1")]
+    pub mint: ::prost::alloc::string::String,
+    #[prost(string, optional, tag="2")]
+    pub authority: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag="3")]
+    pub metadata_address: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateMetadataPointerEvent {
+    #[prost(string, tag="1")]
+    pub mint: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub authority: ::prost::alloc::string::String,
+    #[prost(string, optional, tag="3")]
+    pub metadata_address: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TokenMetadataInitializeEvent {
+    #[prost(string, tag="1")]
+    pub metadata: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub update_authority: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub mint: ::prost::alloc::string::String,
+    #[prost(string, tag="4")]
+    pub mint_authority: ::prost::alloc::string::String,
+    #[prost(string, tag="5")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag="6")]
+    pub symbol: ::prost::alloc::string::String,
+    #[prost(string, tag="7")]
+    pub uri: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TokenMetadataUpdateFieldEvent {
+    #[prost(string, tag="1")]
+    pub metadata: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub update_authority: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub field: ::prost::alloc::string::String,
+    #[prost(string, tag="4")]
+    pub value: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TokenMetadataRemoveKeyEvent {
+    #[prost(string, tag="1")]
+    pub metadata: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub update_authority: ::prost::alloc::string::String,
+    #[prost(bool, tag="3")]
+    pub idempotent: bool,
+    #[prost(string, tag="4")]
+    pub key: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TokenMetadataUpdateAuthorityEvent {
+    #[prost(string, tag="1")]
+    pub metadata: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub update_authority: ::prost::alloc::string::String,
+    #[prost(string, optional, tag="3")]
+    pub new_authority: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -197,6 +463,10 @@ pub struct TokenAccount {
     pub pre_balance: ::core::option::Option<u64>,
     #[prost(uint64, optional, tag="5")]
     pub post_balance: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag="6")]
+    pub pre_lamports: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag="7")]
+    pub post_lamports: ::core::option::Option<u64>,
 }
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
@@ -233,4 +503,33 @@ impl AuthorityType {
         }
     }
 }
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum AccountState {
+    Uninitialized = 0,
+    Initialized = 1,
+    Frozen = 2,
+}
+impl AccountState {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            AccountState::Uninitialized => "Uninitialized",
+            AccountState::Initialized => "Initialized",
+            AccountState::Frozen => "Frozen",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Uninitialized" => Some(Self::Uninitialized),
+            "Initialized" => Some(Self::Initialized),
+            "Frozen" => Some(Self::Frozen),
+            _ => None,
+        }
+    }
+}
 // @@protoc_insertion_point(module)
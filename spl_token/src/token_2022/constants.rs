@@ -0,0 +1 @@
+pub use solana_substreams_common::programs::TOKEN_2022_PROGRAM_ID;
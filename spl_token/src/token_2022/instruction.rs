@@ -0,0 +1,409 @@
+use borsh::BorshDeserialize;
+use substreams_solana_utils::pubkey::Pubkey;
+
+/// Instructions that only exist in the Token-2022 program. The base instruction set (tags 0..=24)
+/// is shared with the Token program and decoded by `TokenInstruction`.
+#[derive(Debug)]
+pub enum ExtensionInstruction {
+    InitializeTransferFeeConfig(InitializeTransferFeeConfigInstruction),
+    TransferCheckedWithFee(TransferCheckedWithFeeInstruction),
+    WithdrawWithheldTokensFromMint,
+    WithdrawWithheldTokensFromAccounts { num_token_accounts: u8 },
+    HarvestWithheldTokensToMint,
+    SetTransferFee(SetTransferFeeInstruction),
+    InitializeDefaultAccountState { state: u8 },
+    UpdateDefaultAccountState { state: u8 },
+    EnableRequiredMemoTransfers,
+    DisableRequiredMemoTransfers,
+    InitializeInterestBearingMint(InitializeInterestBearingMintInstruction),
+    UpdateInterestBearingMintRate { rate: i16 },
+    InitializeMetadataPointer(InitializeMetadataPointerInstruction),
+    UpdateMetadataPointer { metadata_address: Option<Pubkey> },
+    TokenMetadata(TokenMetadataInstruction),
+    Unknown,
+}
+
+const TRANSFER_FEE_EXTENSION: u8 = 26;
+const DEFAULT_ACCOUNT_STATE_EXTENSION: u8 = 28;
+const MEMO_TRANSFER_EXTENSION: u8 = 30;
+const INTEREST_BEARING_MINT_EXTENSION: u8 = 33;
+const METADATA_POINTER_EXTENSION: u8 = 39;
+
+impl ExtensionInstruction {
+    /// Returns true if the instruction data belongs to the Token-2022 extension set rather than
+    /// to the base instruction set.
+    pub fn is_extension(data: &[u8]) -> bool {
+        data.first().map_or(false, |tag| *tag > 24)
+    }
+
+    pub fn unpack(data: &[u8]) -> Result<Self, &'static str> {
+        if data.len() >= 8 {
+            if let Some(instruction) = TokenMetadataInstruction::unpack(data)? {
+                return Ok(Self::TokenMetadata(instruction));
+            }
+        }
+
+        let (&tag, rest) = data.split_first().ok_or("Empty Token-2022 instruction data.")?;
+        match tag {
+            TRANSFER_FEE_EXTENSION => {
+                let (&sub_tag, rest) = rest.split_first().ok_or("Missing TransferFee instruction type.")?;
+                match sub_tag {
+                    0 => Ok(Self::InitializeTransferFeeConfig(InitializeTransferFeeConfigInstruction::unpack(rest)?)),
+                    1 => Ok(Self::TransferCheckedWithFee(TransferCheckedWithFeeInstruction::unpack(rest)?)),
+                    2 => Ok(Self::WithdrawWithheldTokensFromMint),
+                    3 => Ok(Self::WithdrawWithheldTokensFromAccounts { num_token_accounts: unpack_u8(rest)?.0 }),
+                    4 => Ok(Self::HarvestWithheldTokensToMint),
+                    5 => Ok(Self::SetTransferFee(SetTransferFeeInstruction::unpack(rest)?)),
+                    _ => Ok(Self::Unknown),
+                }
+            },
+            DEFAULT_ACCOUNT_STATE_EXTENSION => {
+                let (&sub_tag, rest) = rest.split_first().ok_or("Missing DefaultAccountState instruction type.")?;
+                let (state, _) = unpack_u8(rest)?;
+                match sub_tag {
+                    0 => Ok(Self::InitializeDefaultAccountState { state }),
+                    1 => Ok(Self::UpdateDefaultAccountState { state }),
+                    _ => Ok(Self::Unknown),
+                }
+            },
+            MEMO_TRANSFER_EXTENSION => {
+                let (&sub_tag, _) = rest.split_first().ok_or("Missing MemoTransfer instruction type.")?;
+                match sub_tag {
+                    0 => Ok(Self::EnableRequiredMemoTransfers),
+                    1 => Ok(Self::DisableRequiredMemoTransfers),
+                    _ => Ok(Self::Unknown),
+                }
+            },
+            INTEREST_BEARING_MINT_EXTENSION => {
+                let (&sub_tag, rest) = rest.split_first().ok_or("Missing InterestBearingMint instruction type.")?;
+                match sub_tag {
+                    0 => Ok(Self::InitializeInterestBearingMint(InitializeInterestBearingMintInstruction::unpack(rest)?)),
+                    1 => Ok(Self::UpdateInterestBearingMintRate { rate: unpack_i16(rest)?.0 }),
+                    _ => Ok(Self::Unknown),
+                }
+            },
+            METADATA_POINTER_EXTENSION => {
+                let (&sub_tag, rest) = rest.split_first().ok_or("Missing MetadataPointer instruction type.")?;
+                match sub_tag {
+                    0 => Ok(Self::InitializeMetadataPointer(InitializeMetadataPointerInstruction::unpack(rest)?)),
+                    1 => Ok(Self::UpdateMetadataPointer { metadata_address: unpack_optional_nonzero_pubkey(rest)?.0 }),
+                    _ => Ok(Self::Unknown),
+                }
+            },
+            _ => Ok(Self::Unknown),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct InitializeTransferFeeConfigInstruction {
+    pub transfer_fee_config_authority: Option<Pubkey>,
+    pub withdraw_withheld_authority: Option<Pubkey>,
+    pub transfer_fee_basis_points: u16,
+    pub maximum_fee: u64,
+}
+
+impl InitializeTransferFeeConfigInstruction {
+    fn unpack(data: &[u8]) -> Result<Self, &'static str> {
+        let (transfer_fee_config_authority, data) = unpack_coption_pubkey(data)?;
+        let (withdraw_withheld_authority, data) = unpack_coption_pubkey(data)?;
+        let (transfer_fee_basis_points, data) = unpack_u16(data)?;
+        let (maximum_fee, _) = unpack_u64(data)?;
+        Ok(Self {
+            transfer_fee_config_authority,
+            withdraw_withheld_authority,
+            transfer_fee_basis_points,
+            maximum_fee,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct TransferCheckedWithFeeInstruction {
+    pub amount: u64,
+    pub decimals: u8,
+    pub fee: u64,
+}
+
+impl TransferCheckedWithFeeInstruction {
+    fn unpack(data: &[u8]) -> Result<Self, &'static str> {
+        let (amount, data) = unpack_u64(data)?;
+        let (decimals, data) = unpack_u8(data)?;
+        let (fee, _) = unpack_u64(data)?;
+        Ok(Self { amount, decimals, fee })
+    }
+}
+
+#[derive(Debug)]
+pub struct SetTransferFeeInstruction {
+    pub transfer_fee_basis_points: u16,
+    pub maximum_fee: u64,
+}
+
+impl SetTransferFeeInstruction {
+    fn unpack(data: &[u8]) -> Result<Self, &'static str> {
+        let (transfer_fee_basis_points, data) = unpack_u16(data)?;
+        let (maximum_fee, _) = unpack_u64(data)?;
+        Ok(Self { transfer_fee_basis_points, maximum_fee })
+    }
+}
+
+#[derive(Debug)]
+pub struct InitializeInterestBearingMintInstruction {
+    pub rate_authority: Option<Pubkey>,
+    pub rate: i16,
+}
+
+impl InitializeInterestBearingMintInstruction {
+    fn unpack(data: &[u8]) -> Result<Self, &'static str> {
+        let (rate_authority, data) = unpack_optional_nonzero_pubkey(data)?;
+        let (rate, _) = unpack_i16(data)?;
+        Ok(Self { rate_authority, rate })
+    }
+}
+
+#[derive(Debug)]
+pub struct InitializeMetadataPointerInstruction {
+    pub authority: Option<Pubkey>,
+    pub metadata_address: Option<Pubkey>,
+}
+
+impl InitializeMetadataPointerInstruction {
+    fn unpack(data: &[u8]) -> Result<Self, &'static str> {
+        let (authority, data) = unpack_optional_nonzero_pubkey(data)?;
+        let (metadata_address, _) = unpack_optional_nonzero_pubkey(data)?;
+        Ok(Self { authority, metadata_address })
+    }
+}
+
+/// Instructions of the TokenMetadata interface, which Token-2022 implements for mints that store
+/// their metadata in-line.
+#[derive(Debug)]
+pub enum TokenMetadataInstruction {
+    Initialize(TokenMetadataInitializeInstruction),
+    UpdateField(TokenMetadataUpdateFieldInstruction),
+    RemoveKey(TokenMetadataRemoveKeyInstruction),
+    UpdateAuthority { new_authority: Option<Pubkey> },
+    Emit,
+}
+
+impl TokenMetadataInstruction {
+    pub fn unpack(data: &[u8]) -> Result<Option<Self>, &'static str> {
+        if data.len() < 8 {
+            return Err("Failed to unpack TokenMetadata discriminator.");
+        }
+        let (discriminator, data) = data.split_at(8);
+        match discriminator {
+            [210, 225, 30, 162, 88, 184, 77, 141] => {
+                TokenMetadataInitializeInstruction::try_from_slice(data).map(|x| Some(Self::Initialize(x))).map_err(|_| "Failed to deserialize TokenMetadata Initialize.")
+            },
+            [221, 233, 49, 45, 181, 202, 220, 200] => {
+                TokenMetadataUpdateFieldInstruction::try_from_slice(data).map(|x| Some(Self::UpdateField(x))).map_err(|_| "Failed to deserialize TokenMetadata UpdateField.")
+            },
+            [234, 18, 32, 56, 89, 141, 37, 181] => {
+                TokenMetadataRemoveKeyInstruction::try_from_slice(data).map(|x| Some(Self::RemoveKey(x))).map_err(|_| "Failed to deserialize TokenMetadata RemoveKey.")
+            },
+            [215, 228, 166, 228, 84, 100, 86, 123] => {
+                Ok(Some(Self::UpdateAuthority { new_authority: unpack_optional_nonzero_pubkey(data)?.0 }))
+            },
+            [250, 166, 180, 250, 13, 12, 184, 70] => Ok(Some(Self::Emit)),
+            _ => Ok(None),
+        }
+    }
+}
+
+#[derive(Debug, BorshDeserialize)]
+pub struct TokenMetadataInitializeInstruction {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
+
+#[derive(Debug, BorshDeserialize)]
+pub enum TokenMetadataField {
+    Name,
+    Symbol,
+    Uri,
+    Key(String),
+}
+
+#[derive(Debug, BorshDeserialize)]
+pub struct TokenMetadataUpdateFieldInstruction {
+    pub field: TokenMetadataField,
+    pub value: String,
+}
+
+#[derive(Debug, BorshDeserialize)]
+pub struct TokenMetadataRemoveKeyInstruction {
+    pub idempotent: bool,
+    pub key: String,
+}
+
+fn unpack_u8(data: &[u8]) -> Result<(u8, &[u8]), &'static str> {
+    data.split_first().map(|(x, rest)| (*x, rest)).ok_or("Failed to unpack u8.")
+}
+
+fn unpack_u16(data: &[u8]) -> Result<(u16, &[u8]), &'static str> {
+    if data.len() < 2 {
+        return Err("Failed to unpack u16.");
+    }
+    let (bytes, rest) = data.split_at(2);
+    Ok((u16::from_le_bytes([bytes[0], bytes[1]]), rest))
+}
+
+fn unpack_i16(data: &[u8]) -> Result<(i16, &[u8]), &'static str> {
+    unpack_u16(data).map(|(x, rest)| (x as i16, rest))
+}
+
+fn unpack_u64(data: &[u8]) -> Result<(u64, &[u8]), &'static str> {
+    if data.len() < 8 {
+        return Err("Failed to unpack u64.");
+    }
+    let (bytes, rest) = data.split_at(8);
+    let mut buf = [0u8; 8];
+    buf.copy_from_slice(bytes);
+    Ok((u64::from_le_bytes(buf), rest))
+}
+
+fn unpack_pubkey(data: &[u8]) -> Result<(Pubkey, &[u8]), &'static str> {
+    if data.len() < 32 {
+        return Err("Failed to unpack Pubkey.");
+    }
+    let (bytes, rest) = data.split_at(32);
+    let mut buf = [0u8; 32];
+    buf.copy_from_slice(bytes);
+    Ok((Pubkey(buf), rest))
+}
+
+/// `COption<Pubkey>` as packed by Token-2022: a one byte flag, followed by the key when set.
+fn unpack_coption_pubkey(data: &[u8]) -> Result<(Option<Pubkey>, &[u8]), &'static str> {
+    match data.split_first() {
+        Some((0, rest)) => Ok((None, rest)),
+        Some((1, rest)) => unpack_pubkey(rest).map(|(x, rest)| (Some(x), rest)),
+        _ => Err("Failed to unpack COption<Pubkey>."),
+    }
+}
+
+/// `OptionalNonZeroPubkey`: always 32 bytes, with the zero key standing for `None`.
+fn unpack_optional_nonzero_pubkey(data: &[u8]) -> Result<(Option<Pubkey>, &[u8]), &'static str> {
+    let (pubkey, rest) = unpack_pubkey(data)?;
+    if pubkey.0 == [0u8; 32] {
+        Ok((None, rest))
+    } else {
+        Ok((Some(pubkey), rest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_extension() {
+        assert!(!ExtensionInstruction::is_extension(&[]));
+        assert!(!ExtensionInstruction::is_extension(&[24]));
+        assert!(ExtensionInstruction::is_extension(&[25]));
+    }
+
+    #[test]
+    fn test_unpack_transfer_checked_with_fee() {
+        let mut data = vec![TRANSFER_FEE_EXTENSION, 1];
+        data.extend_from_slice(&1_000u64.to_le_bytes());
+        data.push(6);
+        data.extend_from_slice(&25u64.to_le_bytes());
+
+        match ExtensionInstruction::unpack(&data).unwrap() {
+            ExtensionInstruction::TransferCheckedWithFee(x) => {
+                assert_eq!(x.amount, 1_000);
+                assert_eq!(x.decimals, 6);
+                assert_eq!(x.fee, 25);
+            },
+            x => panic!("Unexpected instruction {:?}", x),
+        }
+    }
+
+    #[test]
+    fn test_unpack_initialize_transfer_fee_config() {
+        let mut data = vec![TRANSFER_FEE_EXTENSION, 0, 1];
+        data.extend_from_slice(&[7u8; 32]);
+        data.push(0);
+        data.extend_from_slice(&50u16.to_le_bytes());
+        data.extend_from_slice(&u64::MAX.to_le_bytes());
+
+        match ExtensionInstruction::unpack(&data).unwrap() {
+            ExtensionInstruction::InitializeTransferFeeConfig(x) => {
+                assert_eq!(x.transfer_fee_config_authority.map(|x| x.0), Some([7u8; 32]));
+                assert!(x.withdraw_withheld_authority.is_none());
+                assert_eq!(x.transfer_fee_basis_points, 50);
+                assert_eq!(x.maximum_fee, u64::MAX);
+            },
+            x => panic!("Unexpected instruction {:?}", x),
+        }
+    }
+
+    #[test]
+    fn test_unpack_interest_bearing_mint_rate() {
+        let mut data = vec![INTEREST_BEARING_MINT_EXTENSION, 1];
+        data.extend_from_slice(&(-300i16).to_le_bytes());
+
+        match ExtensionInstruction::unpack(&data).unwrap() {
+            ExtensionInstruction::UpdateInterestBearingMintRate { rate } => assert_eq!(rate, -300),
+            x => panic!("Unexpected instruction {:?}", x),
+        }
+    }
+
+    #[test]
+    fn test_unpack_metadata_pointer_zero_key() {
+        let mut data = vec![METADATA_POINTER_EXTENSION, 0];
+        data.extend_from_slice(&[0u8; 32]);
+        data.extend_from_slice(&[9u8; 32]);
+
+        match ExtensionInstruction::unpack(&data).unwrap() {
+            ExtensionInstruction::InitializeMetadataPointer(x) => {
+                assert!(x.authority.is_none());
+                assert_eq!(x.metadata_address.map(|x| x.0), Some([9u8; 32]));
+            },
+            x => panic!("Unexpected instruction {:?}", x),
+        }
+    }
+
+    #[test]
+    fn test_unpack_truncated_data() {
+        assert!(ExtensionInstruction::unpack(&[]).is_err());
+        assert!(ExtensionInstruction::unpack(&[TRANSFER_FEE_EXTENSION]).is_err());
+        assert!(ExtensionInstruction::unpack(&[TRANSFER_FEE_EXTENSION, 1, 0, 0]).is_err());
+        assert!(ExtensionInstruction::unpack(&[DEFAULT_ACCOUNT_STATE_EXTENSION, 0]).is_err());
+    }
+
+    #[test]
+    fn test_unpack_unknown_extension() {
+        assert!(matches!(ExtensionInstruction::unpack(&[MEMO_TRANSFER_EXTENSION, 2]), Ok(ExtensionInstruction::Unknown)));
+        assert!(matches!(ExtensionInstruction::unpack(&[255]), Ok(ExtensionInstruction::Unknown)));
+    }
+
+    #[test]
+    fn test_unpack_token_metadata() {
+        let mut data = vec![210, 225, 30, 162, 88, 184, 77, 141];
+        for field in ["Name", "SYM", "https://example.com"] {
+            data.extend_from_slice(&(field.len() as u32).to_le_bytes());
+            data.extend_from_slice(field.as_bytes());
+        }
+
+        match TokenMetadataInstruction::unpack(&data).unwrap() {
+            Some(TokenMetadataInstruction::Initialize(x)) => {
+                assert_eq!(x.name, "Name");
+                assert_eq!(x.symbol, "SYM");
+                assert_eq!(x.uri, "https://example.com");
+            },
+            x => panic!("Unexpected instruction {:?}", x),
+        }
+        assert!(matches!(TokenMetadataInstruction::unpack(&[250, 166, 180, 250, 13, 12, 184, 70]), Ok(Some(TokenMetadataInstruction::Emit))));
+        assert!(matches!(TokenMetadataInstruction::unpack(&[0u8; 8]), Ok(None)));
+    }
+
+    #[test]
+    fn test_unpack_token_metadata_short_data() {
+        assert!(TokenMetadataInstruction::unpack(&[]).is_err());
+        assert!(TokenMetadataInstruction::unpack(&[210, 225, 30]).is_err());
+    }
+}
@@ -0,0 +1,73 @@
+use std::collections::HashSet;
+
+use solana_substreams_common::params::{matches, Params};
+
+use crate::pb::stake_program::stake_program_event::Event;
+
+/// Filters events by the `stake_accounts`, `authorities` and `vote_accounts` module params.
+///
+/// An event matches when one of its stake accounts, authorities and vote account are in the
+/// respective filters. Unset filters match every event.
+#[derive(Clone, Debug, Default)]
+pub struct Filter {
+    stake_accounts: Option<HashSet<String>>,
+    authorities: Option<HashSet<String>>,
+    vote_accounts: Option<HashSet<String>>,
+}
+
+impl Filter {
+    pub fn new(params: &Params) -> Self {
+        Self {
+            stake_accounts: params.get_set("stake_accounts"),
+            authorities: params.get_set("authorities"),
+            vote_accounts: params.get_set("vote_accounts"),
+        }
+    }
+
+    pub fn matches(&self, event: &Event) -> bool {
+        matches(&self.stake_accounts, event_stake_accounts(event))
+            && matches(&self.authorities, event_authorities(event))
+            && matches(&self.vote_accounts, event_vote_account(event))
+    }
+}
+
+pub fn event_stake_accounts(event: &Event) -> Vec<&str> {
+    match event {
+        Event::Initialize(x) | Event::InitializeChecked(x) => vec![x.stake_account.as_str()],
+        Event::Authorize(x) | Event::AuthorizeChecked(x) => vec![x.stake_account.as_str()],
+        Event::AuthorizeWithSeed(x) | Event::AuthorizeCheckedWithSeed(x) => vec![x.stake_account.as_str()],
+        Event::Delegate(x) => vec![x.stake_account.as_str()],
+        Event::Split(x) => vec![x.stake_account.as_str(), x.split_stake_account.as_str()],
+        Event::Withdraw(x) => vec![x.stake_account.as_str()],
+        Event::Deactivate(x) => vec![x.stake_account.as_str()],
+        Event::SetLockup(x) | Event::SetLockupChecked(x) => vec![x.stake_account.as_str()],
+        Event::Merge(x) => vec![x.destination_stake_account.as_str(), x.source_stake_account.as_str()],
+        Event::Redelegate(x) => vec![x.stake_account.as_str(), x.new_stake_account.as_str()],
+        Event::FailedInstruction(_) | Event::UnknownInstruction(_) => Vec::new(),
+    }
+}
+
+/// Authorities signing the event or set by it.
+pub fn event_authorities(event: &Event) -> Vec<&str> {
+    match event {
+        Event::Initialize(x) | Event::InitializeChecked(x) => vec![x.staker.as_str(), x.withdrawer.as_str()],
+        Event::Authorize(x) | Event::AuthorizeChecked(x) => vec![x.authority.as_str(), x.new_authority.as_str()],
+        Event::AuthorizeWithSeed(x) | Event::AuthorizeCheckedWithSeed(x) => vec![x.authority_base.as_str(), x.new_authority.as_str()],
+        Event::Delegate(x) => vec![x.stake_authority.as_str()],
+        Event::Split(x) => vec![x.stake_authority.as_str()],
+        Event::Withdraw(x) => vec![x.withdraw_authority.as_str()],
+        Event::Deactivate(x) => vec![x.stake_authority.as_str()],
+        Event::SetLockup(x) | Event::SetLockupChecked(x) => vec![x.authority.as_str()],
+        Event::Merge(x) => vec![x.stake_authority.as_str()],
+        Event::Redelegate(x) => vec![x.stake_authority.as_str()],
+        Event::FailedInstruction(_) | Event::UnknownInstruction(_) => Vec::new(),
+    }
+}
+
+pub fn event_vote_account(event: &Event) -> Option<&str> {
+    match event {
+        Event::Delegate(x) => Some(&x.vote_account),
+        Event::Redelegate(x) => Some(&x.vote_account),
+        _ => None,
+    }
+}
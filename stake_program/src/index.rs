@@ -0,0 +1,25 @@
+use std::collections::BTreeSet;
+
+use substreams::pb::sf::substreams::index::v1::Keys;
+
+use crate::instruction::STAKE_PROGRAM_ID;
+use crate::filter::{event_authorities, event_stake_accounts, event_vote_account};
+use crate::pb::stake_program::{StakeProgramEvent, StakeProgramTransactionEvents};
+
+/// Block index keys of the events: `program:`, `stake_account:`, `authority:` and `vote_account:`.
+pub fn index_keys(transactions: &[StakeProgramTransactionEvents]) -> Keys {
+    let keys: BTreeSet<String> = transactions.iter().flat_map(|x| x.events.iter()).flat_map(event_keys).collect();
+    Keys { keys: keys.into_iter().collect() }
+}
+
+/// Index keys of a single event, which `filtered_stake_program_events` matches its query against.
+pub fn event_keys(event: &StakeProgramEvent) -> BTreeSet<String> {
+    let mut keys = BTreeSet::new();
+    if let Some(event) = event.event.as_ref() {
+        keys.insert(format!("program:{}", STAKE_PROGRAM_ID));
+        keys.extend(event_stake_accounts(event).into_iter().map(|x| format!("stake_account:{}", x)));
+        keys.extend(event_authorities(event).into_iter().map(|x| format!("authority:{}", x)));
+        keys.extend(event_vote_account(event).map(|x| format!("vote_account:{}", x)));
+    }
+    keys
+}
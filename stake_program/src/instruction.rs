@@ -0,0 +1,247 @@
+use substreams_solana::b58;
+use substreams_solana_utils::pubkey::Pubkey;
+
+pub const STAKE_PROGRAM_ID: Pubkey = Pubkey(b58!("Stake11111111111111111111111111111111111111"));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StakeAuthorize {
+    Staker,
+    Withdrawer,
+}
+
+impl StakeAuthorize {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Staker => "Staker",
+            Self::Withdrawer => "Withdrawer",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Authorized {
+    pub staker: Pubkey,
+    pub withdrawer: Pubkey,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lockup {
+    pub unix_timestamp: i64,
+    pub epoch: u64,
+    pub custodian: Pubkey,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockupArgs {
+    pub unix_timestamp: Option<i64>,
+    pub epoch: Option<u64>,
+    /// Unset for SetLockupChecked, whose new custodian is an account of the instruction.
+    pub custodian: Option<Pubkey>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthorizeWithSeedArgs {
+    /// Unset for AuthorizeCheckedWithSeed, whose new authority is an account of the instruction.
+    pub new_authorized_pubkey: Option<Pubkey>,
+    pub stake_authorize: StakeAuthorize,
+    pub authority_seed: String,
+    pub authority_owner: Pubkey,
+}
+
+/// Instructions of the Stake program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StakeInstruction {
+    Initialize(Authorized, Lockup),
+    Authorize(Pubkey, StakeAuthorize),
+    DelegateStake,
+    Split(u64),
+    Withdraw(u64),
+    Deactivate,
+    SetLockup(LockupArgs),
+    Merge,
+    AuthorizeWithSeed(AuthorizeWithSeedArgs),
+    InitializeChecked,
+    AuthorizeChecked(StakeAuthorize),
+    AuthorizeCheckedWithSeed(AuthorizeWithSeedArgs),
+    SetLockupChecked(LockupArgs),
+    GetMinimumDelegation,
+    DeactivateDelinquent,
+    Redelegate,
+    MoveStake(u64),
+    MoveLamports(u64),
+}
+
+impl StakeInstruction {
+    /// Unpacks the bincode encoded instruction data.
+    pub fn unpack(data: &[u8]) -> Result<Self, &'static str> {
+        let mut reader = Reader(data);
+        match reader.u32()? {
+            0 => {
+                let authorized = Authorized { staker: reader.pubkey()?, withdrawer: reader.pubkey()? };
+                let lockup = Lockup { unix_timestamp: reader.i64()?, epoch: reader.u64()?, custodian: reader.pubkey()? };
+                Ok(Self::Initialize(authorized, lockup))
+            },
+            1 => Ok(Self::Authorize(reader.pubkey()?, reader.stake_authorize()?)),
+            2 => Ok(Self::DelegateStake),
+            3 => Ok(Self::Split(reader.u64()?)),
+            4 => Ok(Self::Withdraw(reader.u64()?)),
+            5 => Ok(Self::Deactivate),
+            6 => Ok(Self::SetLockup(LockupArgs {
+                unix_timestamp: reader.option(Reader::i64)?,
+                epoch: reader.option(Reader::u64)?,
+                custodian: reader.option(Reader::pubkey)?,
+            })),
+            7 => Ok(Self::Merge),
+            8 => Ok(Self::AuthorizeWithSeed(AuthorizeWithSeedArgs {
+                new_authorized_pubkey: Some(reader.pubkey()?),
+                stake_authorize: reader.stake_authorize()?,
+                authority_seed: reader.string()?,
+                authority_owner: reader.pubkey()?,
+            })),
+            9 => Ok(Self::InitializeChecked),
+            10 => Ok(Self::AuthorizeChecked(reader.stake_authorize()?)),
+            11 => Ok(Self::AuthorizeCheckedWithSeed(AuthorizeWithSeedArgs {
+                new_authorized_pubkey: None,
+                stake_authorize: reader.stake_authorize()?,
+                authority_seed: reader.string()?,
+                authority_owner: reader.pubkey()?,
+            })),
+            12 => Ok(Self::SetLockupChecked(LockupArgs {
+                unix_timestamp: reader.option(Reader::i64)?,
+                epoch: reader.option(Reader::u64)?,
+                custodian: None,
+            })),
+            13 => Ok(Self::GetMinimumDelegation),
+            14 => Ok(Self::DeactivateDelinquent),
+            15 => Ok(Self::Redelegate),
+            16 => Ok(Self::MoveStake(reader.u64()?)),
+            17 => Ok(Self::MoveLamports(reader.u64()?)),
+            _ => Err("Unknown instruction discriminator."),
+        }
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], &'static str> {
+        if self.0.len() < len {
+            return Err("Instruction data is too short.");
+        }
+        let (bytes, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, &'static str> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, &'static str> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64, &'static str> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn i64(&mut self) -> Result<i64, &'static str> {
+        Ok(i64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn pubkey(&mut self) -> Result<Pubkey, &'static str> {
+        Ok(Pubkey(self.take(32)?.try_into().unwrap()))
+    }
+
+    fn string(&mut self) -> Result<String, &'static str> {
+        let len = self.u64()? as usize;
+        String::from_utf8(self.take(len)?.to_vec()).map_err(|_| "Invalid UTF-8 string.")
+    }
+
+    fn stake_authorize(&mut self) -> Result<StakeAuthorize, &'static str> {
+        match self.u32()? {
+            0 => Ok(StakeAuthorize::Staker),
+            1 => Ok(StakeAuthorize::Withdrawer),
+            _ => Err("Invalid stake authorize."),
+        }
+    }
+
+    fn option<T>(&mut self, read: fn(&mut Self) -> Result<T, &'static str>) -> Result<Option<T>, &'static str> {
+        match self.u8()? {
+            0 => Ok(None),
+            1 => read(self).map(Some),
+            _ => Err("Invalid option tag."),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(tag: u32, fields: &[&[u8]]) -> Vec<u8> {
+        let mut data = tag.to_le_bytes().to_vec();
+        for field in fields {
+            data.extend_from_slice(field);
+        }
+        data
+    }
+
+    #[test]
+    fn test_unpack_initialize() {
+        let data = encode(0, &[&[1; 32], &[2; 32], &(-5i64).to_le_bytes(), &7u64.to_le_bytes(), &[3; 32]]);
+        assert_eq!(
+            StakeInstruction::unpack(&data),
+            Ok(StakeInstruction::Initialize(
+                Authorized { staker: Pubkey([1; 32]), withdrawer: Pubkey([2; 32]) },
+                Lockup { unix_timestamp: -5, epoch: 7, custodian: Pubkey([3; 32]) },
+            )),
+        );
+    }
+
+    #[test]
+    fn test_unpack_amounts() {
+        assert_eq!(StakeInstruction::unpack(&encode(3, &[&42u64.to_le_bytes()])), Ok(StakeInstruction::Split(42)));
+        assert_eq!(StakeInstruction::unpack(&encode(4, &[&u64::MAX.to_le_bytes()])), Ok(StakeInstruction::Withdraw(u64::MAX)));
+        assert_eq!(StakeInstruction::unpack(&encode(17, &[&1u64.to_le_bytes()])), Ok(StakeInstruction::MoveLamports(1)));
+    }
+
+    #[test]
+    fn test_unpack_set_lockup() {
+        let data = encode(6, &[&[0], &[1], &9u64.to_le_bytes(), &[1], &[4; 32]]);
+        assert_eq!(
+            StakeInstruction::unpack(&data),
+            Ok(StakeInstruction::SetLockup(LockupArgs { unix_timestamp: None, epoch: Some(9), custodian: Some(Pubkey([4; 32])) })),
+        );
+
+        let data = encode(12, &[&[1], &10i64.to_le_bytes(), &[0]]);
+        assert_eq!(
+            StakeInstruction::unpack(&data),
+            Ok(StakeInstruction::SetLockupChecked(LockupArgs { unix_timestamp: Some(10), epoch: None, custodian: None })),
+        );
+    }
+
+    #[test]
+    fn test_unpack_authorize_with_seed() {
+        let seed = "stake:0";
+        let data = encode(8, &[&[5; 32], &1u32.to_le_bytes(), &(seed.len() as u64).to_le_bytes(), seed.as_bytes(), &[6; 32]]);
+        assert_eq!(
+            StakeInstruction::unpack(&data),
+            Ok(StakeInstruction::AuthorizeWithSeed(AuthorizeWithSeedArgs {
+                new_authorized_pubkey: Some(Pubkey([5; 32])),
+                stake_authorize: StakeAuthorize::Withdrawer,
+                authority_seed: seed.to_string(),
+                authority_owner: Pubkey([6; 32]),
+            })),
+        );
+    }
+
+    #[test]
+    fn test_unpack_invalid_data() {
+        assert!(StakeInstruction::unpack(&[]).is_err());
+        assert!(StakeInstruction::unpack(&encode(3, &[&[0; 7]])).is_err());
+        assert!(StakeInstruction::unpack(&encode(10, &[&2u32.to_le_bytes()])).is_err());
+        assert!(StakeInstruction::unpack(&encode(6, &[&[2]])).is_err());
+        assert!(StakeInstruction::unpack(&encode(18, &[])).is_err());
+    }
+}
@@ -0,0 +1,463 @@
+use substreams::errors::Error;
+use substreams::pb::sf::substreams::index::v1::Keys;
+use substreams_solana::pb::sf::solana::r#type::v1::ConfirmedTransaction;
+use substreams_solana::pb::sf::solana::r#type::v1::Block;
+
+use substreams_solana_utils as utils;
+use utils::transaction::{get_context, TransactionContext};
+use utils::instruction::{get_structured_instructions, StructuredInstructions, StructuredInstruction};
+use utils::pubkey::Pubkey;
+
+use solana_substreams_common::{get_failed_instruction, get_transaction_envelope, is_program_invoked};
+use solana_substreams_common::params::Params;
+use solana_substreams_common::query::Query;
+use solana_substreams_common::instruction::{flattened_index, get_call_path, get_unknown_instruction, stack_depth};
+
+pub mod error;
+use error::ParseError;
+
+pub mod instruction;
+use instruction::{StakeInstruction, StakeAuthorize, STAKE_PROGRAM_ID};
+
+pub mod stake_error;
+use stake_error::stake_error_name;
+
+pub mod filter;
+use filter::Filter;
+
+pub mod index;
+use index::{event_keys, index_keys};
+
+pub mod pb;
+use pb::stake_program::*;
+use pb::stake_program::stake_program_event::Event;
+
+#[substreams::handlers::map]
+fn stake_program_events(params: String, block: Block) -> Result<StakeProgramBlockEvents, Error> {
+    Ok(_block_events(&block, &Params::parse(&params)))
+}
+
+#[substreams::handlers::map]
+fn stake_program_index(block: Block) -> Result<Keys, Error> {
+    let transactions = parse_block(&block, &Params::default());
+    Ok(index_keys(&transactions))
+}
+
+/// Events matching the block filter query given as params, such as `stake_account:<stake_account>`, on their own index keys.
+#[substreams::handlers::map]
+fn filtered_stake_program_events(query: String, block: Block) -> Result<StakeProgramBlockEvents, Error> {
+    let query = Query::parse(&query).map_err(Error::msg)?;
+    let mut block_events = _block_events(&block, &Params::default());
+    for transaction in block_events.transactions.iter_mut() {
+        transaction.events.retain(|x| query.matches(&event_keys(x)));
+    }
+    block_events.transactions.retain(|x| !x.events.is_empty());
+    Ok(block_events)
+}
+
+fn _block_events(block: &Block, params: &Params) -> StakeProgramBlockEvents {
+    let transactions = parse_block(block, params);
+    StakeProgramBlockEvents {
+        slot: block.slot,
+        block_time: block.block_time.as_ref().map(|x| x.timestamp).unwrap_or_default(),
+        block_hash: block.blockhash.clone(),
+        transactions,
+    }
+}
+
+pub fn parse_block(block: &Block, params: &Params) -> Vec<StakeProgramTransactionEvents> {
+    let include_failed = params.get_bool("include_failed");
+    let filter = Filter::new(params);
+    let mut block_events: Vec<StakeProgramTransactionEvents> = Vec::new();
+    for (i, transaction) in block.transactions.iter().enumerate() {
+        let failed = transaction.meta.as_ref().map_or(false, |x| x.err.is_some());
+        if failed && !include_failed {
+            continue;
+        }
+        let signature = utils::transaction::get_signature(&transaction);
+        let result = if failed {
+            Ok(parse_failed_transaction(transaction, &filter))
+        } else {
+            parse_transaction(transaction, &filter)
+        };
+        match result {
+            Ok(events) if !events.is_empty() => {
+                block_events.push(StakeProgramTransactionEvents {
+                    signature,
+                    transaction_index: i as u32,
+                    envelope: Some(get_transaction_envelope(transaction)),
+                    events,
+                });
+            },
+            Ok(_) => (),
+            Err(error) => substreams::log::println(format!("Failed to process transaction {}: {}", signature, error)),
+        }
+    }
+    block_events
+}
+
+pub fn parse_transaction(transaction: &ConfirmedTransaction, filter: &Filter) -> Result<Vec<StakeProgramEvent>, ParseError> {
+    if let Some(_) = transaction.meta.as_ref().ok_or(ParseError::MissingTransactionMeta)?.err {
+        return Ok(Vec::new())
+    }
+
+    let mut events: Vec<StakeProgramEvent> = Vec::new();
+
+    let context = get_context(transaction).map_err(|x| ParseError::TransactionContext(x.to_string()))?;
+    let instructions = get_structured_instructions(transaction).map_err(|x| ParseError::TransactionContext(x.to_string()))?;
+
+    let flattened = instructions.flattened();
+    for (i, instruction) in flattened.iter().enumerate() {
+        if instruction.program_id() != STAKE_PROGRAM_ID {
+            continue;
+        }
+
+        let call_path = get_call_path(instruction, &flattened);
+        match parse_instruction(instruction, &context) {
+            Ok(Some(event)) if filter.matches(&event) => {
+                events.push(StakeProgramEvent {
+                    instruction_index: i as u32,
+                    stack_depth: stack_depth(instruction),
+                    top_instruction_program_id: call_path.top_instruction_program_id,
+                    parent_instruction_program_id: call_path.parent_instruction_program_id,
+                    top_instruction_index: call_path.top_instruction_index,
+                    parent_instruction_index: call_path.parent_instruction_index,
+                    event: Some(event),
+                });
+            },
+            Ok(_) => (),
+            Err(error) => substreams::log::println(format!("Failed to process instruction of transaction {}: {}", &context.signature, error)),
+        }
+    }
+
+    Ok(events)
+}
+
+/// Parses a failed transaction into a `FailedInstruction` event, if it invoked the Stake program.
+pub fn parse_failed_transaction(transaction: &ConfirmedTransaction, filter: &Filter) -> Vec<StakeProgramEvent> {
+    if !is_program_invoked(transaction, &STAKE_PROGRAM_ID.to_string()) {
+        return Vec::new();
+    }
+    get_failed_instruction(transaction, custom_error_name)
+        .map(|x| StakeProgramEvent {
+            instruction_index: flattened_index(transaction, x.instruction_index),
+            stack_depth: 0,
+            top_instruction_program_id: None,
+            parent_instruction_program_id: None,
+            top_instruction_index: None,
+            parent_instruction_index: None,
+            event: Some(Event::FailedInstruction(x)),
+        })
+        .filter(|x| x.event.as_ref().map_or(false, |x| filter.matches(x)))
+        .into_iter()
+        .collect()
+}
+
+/// Name of a custom error code raised by the Stake program.
+pub fn custom_error_name(program_id: &str, code: u32) -> Option<String> {
+    if program_id == STAKE_PROGRAM_ID.to_string() {
+        stake_error_name(code)
+    } else {
+        None
+    }
+}
+
+pub fn parse_instruction<'a>(
+    instruction: &StructuredInstruction<'a>,
+    context: &TransactionContext
+) -> Result<Option<Event>, ParseError> {
+    if instruction.program_id() != STAKE_PROGRAM_ID {
+        return Err(ParseError::UnexpectedProgram(instruction.program_id().to_string()));
+    }
+    let unpacked = match StakeInstruction::unpack(instruction.data()) {
+        Ok(unpacked) => unpacked,
+        Err(_) => return Ok(Some(Event::UnknownInstruction(get_unknown_instruction(instruction, 4)))),
+    };
+    match unpacked {
+        StakeInstruction::Initialize(authorized, lockup) => {
+            _parse_initialize_instruction(instruction, context, authorized, lockup).map(|x| Some(Event::Initialize(x)))
+        },
+        StakeInstruction::InitializeChecked => {
+            _parse_initialize_checked_instruction(instruction, context).map(|x| Some(Event::InitializeChecked(x)))
+        },
+        StakeInstruction::Authorize(new_authority, stake_authorize) => {
+            _parse_authorize_instruction(instruction, context, Some(new_authority), stake_authorize).map(|x| Some(Event::Authorize(x)))
+        },
+        StakeInstruction::AuthorizeChecked(stake_authorize) => {
+            _parse_authorize_instruction(instruction, context, None, stake_authorize).map(|x| Some(Event::AuthorizeChecked(x)))
+        },
+        StakeInstruction::AuthorizeWithSeed(args) => {
+            _parse_authorize_with_seed_instruction(instruction, context, args).map(|x| Some(Event::AuthorizeWithSeed(x)))
+        },
+        StakeInstruction::AuthorizeCheckedWithSeed(args) => {
+            _parse_authorize_with_seed_instruction(instruction, context, args).map(|x| Some(Event::AuthorizeCheckedWithSeed(x)))
+        },
+        StakeInstruction::DelegateStake => {
+            _parse_delegate_instruction(instruction, context).map(|x| Some(Event::Delegate(x)))
+        },
+        StakeInstruction::Split(lamports) => {
+            _parse_split_instruction(instruction, context, lamports).map(|x| Some(Event::Split(x)))
+        },
+        StakeInstruction::Withdraw(lamports) => {
+            _parse_withdraw_instruction(instruction, context, lamports).map(|x| Some(Event::Withdraw(x)))
+        },
+        StakeInstruction::Deactivate => {
+            _parse_deactivate_instruction(instruction, context).map(|x| Some(Event::Deactivate(x)))
+        },
+        StakeInstruction::SetLockup(args) => {
+            _parse_set_lockup_instruction(instruction, context, args).map(|x| Some(Event::SetLockup(x)))
+        },
+        StakeInstruction::SetLockupChecked(args) => {
+            _parse_set_lockup_instruction(instruction, context, args).map(|x| Some(Event::SetLockupChecked(x)))
+        },
+        StakeInstruction::Merge => {
+            _parse_merge_instruction(instruction, context).map(|x| Some(Event::Merge(x)))
+        },
+        StakeInstruction::Redelegate => {
+            _parse_redelegate_instruction(instruction, context).map(|x| Some(Event::Redelegate(x)))
+        },
+        StakeInstruction::GetMinimumDelegation |
+        StakeInstruction::DeactivateDelinquent |
+        StakeInstruction::MoveStake(_) |
+        StakeInstruction::MoveLamports(_) => Ok(None),
+    }
+}
+
+fn _account(instruction: &StructuredInstruction, index: usize) -> Result<String, ParseError> {
+    instruction.accounts().get(index).map(|x| x.to_string()).ok_or(ParseError::MissingAccount(index))
+}
+
+fn _parse_initialize_instruction(
+    instruction: &StructuredInstruction,
+    _context: &TransactionContext,
+    authorized: instruction::Authorized,
+    lockup: instruction::Lockup,
+) -> Result<InitializeEvent, ParseError> {
+    let stake_account = _account(instruction, 0)?;
+    let staker = authorized.staker.to_string();
+    let withdrawer = authorized.withdrawer.to_string();
+    let lockup = Some(Lockup {
+        unix_timestamp: lockup.unix_timestamp,
+        epoch: lockup.epoch,
+        custodian: lockup.custodian.to_string(),
+    });
+
+    Ok(InitializeEvent {
+        stake_account,
+        staker,
+        withdrawer,
+        lockup,
+    })
+}
+
+fn _parse_initialize_checked_instruction(
+    instruction: &StructuredInstruction,
+    _context: &TransactionContext,
+) -> Result<InitializeEvent, ParseError> {
+    let stake_account = _account(instruction, 0)?;
+    let staker = _account(instruction, 2)?;
+    let withdrawer = _account(instruction, 3)?;
+
+    Ok(InitializeEvent {
+        stake_account,
+        staker,
+        withdrawer,
+        lockup: None,
+    })
+}
+
+/// Parses Authorize, or AuthorizeChecked when the new authority is an account of the instruction.
+fn _parse_authorize_instruction(
+    instruction: &StructuredInstruction,
+    _context: &TransactionContext,
+    new_authority: Option<Pubkey>,
+    stake_authorize: StakeAuthorize,
+) -> Result<AuthorizeEvent, ParseError> {
+    let stake_account = _account(instruction, 0)?;
+    let authority = _account(instruction, 2)?;
+    let (new_authority, custodian) = match new_authority {
+        Some(new_authority) => (new_authority.to_string(), _account(instruction, 3).ok()),
+        None => (_account(instruction, 3)?, _account(instruction, 4).ok()),
+    };
+    let authority_type = stake_authorize.as_str().to_string();
+
+    Ok(AuthorizeEvent {
+        stake_account,
+        authority,
+        new_authority,
+        authority_type,
+        custodian,
+    })
+}
+
+fn _parse_authorize_with_seed_instruction(
+    instruction: &StructuredInstruction,
+    _context: &TransactionContext,
+    args: instruction::AuthorizeWithSeedArgs,
+) -> Result<AuthorizeWithSeedEvent, ParseError> {
+    let stake_account = _account(instruction, 0)?;
+    let authority_base = _account(instruction, 1)?;
+    let (new_authority, custodian) = match args.new_authorized_pubkey {
+        Some(new_authority) => (new_authority.to_string(), _account(instruction, 3).ok()),
+        None => (_account(instruction, 3)?, _account(instruction, 4).ok()),
+    };
+    let authority_type = args.stake_authorize.as_str().to_string();
+
+    Ok(AuthorizeWithSeedEvent {
+        stake_account,
+        authority_base,
+        authority_seed: args.authority_seed,
+        authority_owner: args.authority_owner.to_string(),
+        new_authority,
+        authority_type,
+        custodian,
+    })
+}
+
+fn _parse_delegate_instruction(
+    instruction: &StructuredInstruction,
+    _context: &TransactionContext,
+) -> Result<DelegateEvent, ParseError> {
+    let stake_account = _account(instruction, 0)?;
+    let vote_account = _account(instruction, 1)?;
+    let stake_authority = _account(instruction, 5)?;
+
+    Ok(DelegateEvent {
+        stake_account,
+        vote_account,
+        stake_authority,
+    })
+}
+
+fn _parse_split_instruction(
+    instruction: &StructuredInstruction,
+    _context: &TransactionContext,
+    lamports: u64,
+) -> Result<SplitEvent, ParseError> {
+    let stake_account = _account(instruction, 0)?;
+    let split_stake_account = _account(instruction, 1)?;
+    let stake_authority = _account(instruction, 2)?;
+
+    Ok(SplitEvent {
+        stake_account,
+        split_stake_account,
+        stake_authority,
+        lamports,
+    })
+}
+
+fn _parse_withdraw_instruction(
+    instruction: &StructuredInstruction,
+    _context: &TransactionContext,
+    lamports: u64,
+) -> Result<WithdrawEvent, ParseError> {
+    let stake_account = _account(instruction, 0)?;
+    let recipient = _account(instruction, 1)?;
+    let withdraw_authority = _account(instruction, 4)?;
+    let custodian = _account(instruction, 5).ok();
+
+    Ok(WithdrawEvent {
+        stake_account,
+        recipient,
+        withdraw_authority,
+        lamports,
+        custodian,
+    })
+}
+
+fn _parse_deactivate_instruction(
+    instruction: &StructuredInstruction,
+    _context: &TransactionContext,
+) -> Result<DeactivateEvent, ParseError> {
+    let stake_account = _account(instruction, 0)?;
+    let stake_authority = _account(instruction, 2)?;
+
+    Ok(DeactivateEvent {
+        stake_account,
+        stake_authority,
+    })
+}
+
+/// Parses SetLockup, or SetLockupChecked when the new custodian is an account of the instruction.
+fn _parse_set_lockup_instruction(
+    instruction: &StructuredInstruction,
+    _context: &TransactionContext,
+    args: instruction::LockupArgs,
+) -> Result<SetLockupEvent, ParseError> {
+    let stake_account = _account(instruction, 0)?;
+    let authority = _account(instruction, 1)?;
+    let custodian = match args.custodian {
+        Some(custodian) => Some(custodian.to_string()),
+        None => _account(instruction, 2).ok(),
+    };
+
+    Ok(SetLockupEvent {
+        stake_account,
+        authority,
+        unix_timestamp: args.unix_timestamp,
+        epoch: args.epoch,
+        custodian,
+    })
+}
+
+fn _parse_merge_instruction(
+    instruction: &StructuredInstruction,
+    _context: &TransactionContext,
+) -> Result<MergeEvent, ParseError> {
+    let destination_stake_account = _account(instruction, 0)?;
+    let source_stake_account = _account(instruction, 1)?;
+    let stake_authority = _account(instruction, 4)?;
+
+    Ok(MergeEvent {
+        destination_stake_account,
+        source_stake_account,
+        stake_authority,
+    })
+}
+
+fn _parse_redelegate_instruction(
+    instruction: &StructuredInstruction,
+    _context: &TransactionContext,
+) -> Result<RedelegateEvent, ParseError> {
+    let stake_account = _account(instruction, 0)?;
+    let new_stake_account = _account(instruction, 1)?;
+    let vote_account = _account(instruction, 2)?;
+    let stake_authority = _account(instruction, 4)?;
+
+    Ok(RedelegateEvent {
+        stake_account,
+        new_stake_account,
+        vote_account,
+        stake_authority,
+    })
+}
+
+pub fn parse_delegate_instruction<'a>(
+    instruction: &StructuredInstruction<'a>,
+    context: &TransactionContext,
+) -> Result<DelegateEvent, ParseError> {
+    match parse_instruction(instruction, context)? {
+        Some(Event::Delegate(event)) => Ok(event),
+        _ => Err(ParseError::InstructionMismatch("DelegateStake")),
+    }
+}
+
+pub fn parse_withdraw_instruction<'a>(
+    instruction: &StructuredInstruction<'a>,
+    context: &TransactionContext,
+) -> Result<WithdrawEvent, ParseError> {
+    match parse_instruction(instruction, context)? {
+        Some(Event::Withdraw(event)) => Ok(event),
+        _ => Err(ParseError::InstructionMismatch("Withdraw")),
+    }
+}
+
+pub fn parse_split_instruction<'a>(
+    instruction: &StructuredInstruction<'a>,
+    context: &TransactionContext,
+) -> Result<SplitEvent, ParseError> {
+    match parse_instruction(instruction, context)? {
+        Some(Event::Split(event)) => Ok(event),
+        _ => Err(ParseError::InstructionMismatch("Split")),
+    }
+}
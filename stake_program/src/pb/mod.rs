@@ -0,0 +1,6 @@
+// @generated
+// @@protoc_insertion_point(attribute:stake_program)
+pub mod stake_program {
+    include!("stake_program.rs");
+    // @@protoc_insertion_point(stake_program)
+}
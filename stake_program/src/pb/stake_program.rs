@@ -0,0 +1,224 @@
+// @generated
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StakeProgramBlockEvents {
+    #[prost(uint64, tag="1")]
+    pub slot: u64,
+    #[prost(message, repeated, tag="2")]
+    pub transactions: ::prost::alloc::vec::Vec<StakeProgramTransactionEvents>,
+    #[prost(int64, tag="3")]
+    pub block_time: i64,
+    #[prost(string, tag="4")]
+    pub block_hash: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StakeProgramTransactionEvents {
+    #[prost(string, tag="1")]
+    pub signature: ::prost::alloc::string::String,
+    #[prost(uint32, tag="2")]
+    pub transaction_index: u32,
+    #[prost(message, repeated, tag="3")]
+    pub events: ::prost::alloc::vec::Vec<StakeProgramEvent>,
+    #[prost(message, optional, tag="4")]
+    pub envelope: ::core::option::Option<::solana_substreams_common::pb::common::TransactionEnvelope>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StakeProgramEvent {
+    #[prost(uint32, tag="1")]
+    pub instruction_index: u32,
+    /// Depth in the invocation stack, 0 for top level instructions.
+    #[prost(uint32, tag="18")]
+    pub stack_depth: u32,
+    #[prost(string, optional, tag="19")]
+    pub top_instruction_program_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag="20")]
+    pub parent_instruction_program_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(uint32, optional, tag="21")]
+    pub top_instruction_index: ::core::option::Option<u32>,
+    #[prost(uint32, optional, tag="22")]
+    pub parent_instruction_index: ::core::option::Option<u32>,
+    #[prost(oneof="stake_program_event::Event", tags="2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17")]
+    pub event: ::core::option::Option<stake_program_event::Event>,
+}
+/// Nested message and enum types in `StakeProgramEvent`.
+pub mod stake_program_event {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Event {
+        #[prost(message, tag="2")]
+        Initialize(super::InitializeEvent),
+        #[prost(message, tag="3")]
+        InitializeChecked(super::InitializeEvent),
+        #[prost(message, tag="4")]
+        Authorize(super::AuthorizeEvent),
+        #[prost(message, tag="5")]
+        AuthorizeChecked(super::AuthorizeEvent),
+        #[prost(message, tag="6")]
+        AuthorizeWithSeed(super::AuthorizeWithSeedEvent),
+        #[prost(message, tag="7")]
+        AuthorizeCheckedWithSeed(super::AuthorizeWithSeedEvent),
+        #[prost(message, tag="8")]
+        Delegate(super::DelegateEvent),
+        #[prost(message, tag="9")]
+        Split(super::SplitEvent),
+        #[prost(message, tag="10")]
+        Withdraw(super::WithdrawEvent),
+        #[prost(message, tag="11")]
+        Deactivate(super::DeactivateEvent),
+        #[prost(message, tag="12")]
+        SetLockup(super::SetLockupEvent),
+        #[prost(message, tag="13")]
+        SetLockupChecked(super::SetLockupEvent),
+        #[prost(message, tag="14")]
+        Merge(super::MergeEvent),
+        #[prost(message, tag="15")]
+        Redelegate(super::RedelegateEvent),
+        #[prost(message, tag="16")]
+        FailedInstruction(::solana_substreams_common::pb::common::FailedInstruction),
+        #[prost(message, tag="17")]
+        UnknownInstruction(::solana_substreams_common::pb::common::UnknownInstruction),
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Lockup {
+    #[prost(int64, tag="1")]
+    pub unix_timestamp: i64,
+    #[prost(uint64, tag="2")]
+    pub epoch: u64,
+    #[prost(string, tag="3")]
+    pub custodian: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct InitializeEvent {
+    #[prost(string, tag="1")]
+    pub stake_account: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub staker: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub withdrawer: ::prost::alloc::string::String,
+    /// Unset for InitializeChecked, which initializes the account without a lockup.
+    #[prost(message, optional, tag="4")]
+    pub lockup: ::core::option::Option<Lockup>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AuthorizeEvent {
+    #[prost(string, tag="1")]
+    pub stake_account: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub authority: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub new_authority: ::prost::alloc::string::String,
+    /// Either `Staker` or `Withdrawer`.
+    #[prost(string, tag="4")]
+    pub authority_type: ::prost::alloc::string::String,
+    #[prost(string, optional, tag="5")]
+    pub custodian: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AuthorizeWithSeedEvent {
+    #[prost(string, tag="1")]
+    pub stake_account: ::prost::alloc::string::String,
+    /// Base account of the authority address derived from the seed.
+    #[prost(string, tag="2")]
+    pub authority_base: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub authority_seed: ::prost::alloc::string::String,
+    #[prost(string, tag="4")]
+    pub authority_owner: ::prost::alloc::string::String,
+    #[prost(string, tag="5")]
+    pub new_authority: ::prost::alloc::string::String,
+    /// Either `Staker` or `Withdrawer`.
+    #[prost(string, tag="6")]
+    pub authority_type: ::prost::alloc::string::String,
+    #[prost(string, optional, tag="7")]
+    pub custodian: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DelegateEvent {
+    #[prost(string, tag="1")]
+    pub stake_account: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub vote_account: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub stake_authority: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SplitEvent {
+    #[prost(string, tag="1")]
+    pub stake_account: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub split_stake_account: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub stake_authority: ::prost::alloc::string::String,
+    #[prost(uint64, tag="4")]
+    pub lamports: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WithdrawEvent {
+    #[prost(string, tag="1")]
+    pub stake_account: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub recipient: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub withdraw_authority: ::prost::alloc::string::String,
+    #[prost(uint64, tag="4")]
+    pub lamports: u64,
+    #[prost(string, optional, tag="5")]
+    pub custodian: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeactivateEvent {
+    #[prost(string, tag="1")]
+    pub stake_account: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub stake_authority: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetLockupEvent {
+    #[prost(string, tag="1")]
+    pub stake_account: ::prost::alloc::string::String,
+    /// Lockup custodian, or withdraw authority if the lockup is not in force.
+    #[prost(string, tag="2")]
+    pub authority: ::prost::alloc::string::String,
+    #[prost(int64, optional, tag="3")]
+    pub unix_timestamp: ::core::option::Option<i64>,
+    #[prost(uint64, optional, tag="4")]
+    pub epoch: ::core::option::Option<u64>,
+    #[prost(string, optional, tag="5")]
+    pub custodian: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MergeEvent {
+    #[prost(string, tag="1")]
+    pub destination_stake_account: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub source_stake_account: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub stake_authority: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RedelegateEvent {
+    #[prost(string, tag="1")]
+    pub stake_account: ::prost::alloc::string::String,
+    /// Uninitialized stake account receiving the redelegated stake.
+    #[prost(string, tag="2")]
+    pub new_stake_account: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub vote_account: ::prost::alloc::string::String,
+    #[prost(string, tag="4")]
+    pub stake_authority: ::prost::alloc::string::String,
+}
+// @@protoc_insertion_point(module)
@@ -0,0 +1,69 @@
+//! Error types of the Stake program.
+
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+use thiserror::Error;
+
+/// Errors that may be returned by the Stake program.
+#[derive(Clone, Debug, Eq, Error, FromPrimitive, PartialEq)]
+pub enum StakeError {
+    /// 0 Not enough credits to redeem.
+    #[error("not enough credits to redeem")]
+    NoCreditsToRedeem,
+    /// Lockup has not yet expired.
+    #[error("lockup has not yet expired")]
+    LockupInForce,
+    /// Stake already deactivated.
+    #[error("stake already deactivated")]
+    AlreadyDeactivated,
+    /// One re-delegation permitted per epoch.
+    #[error("one re-delegation permitted per epoch")]
+    TooSoonToRedelegate,
+    /// Split amount is more than is staked.
+    #[error("split amount is more than is staked")]
+    InsufficientStake,
+
+    /// 5 Stake account with transient stake cannot be merged.
+    #[error("stake account with transient stake cannot be merged")]
+    MergeTransientStake,
+    /// Stake account merge failed due to different authority, lockups or state.
+    #[error("stake account merge failed due to different authority, lockups or state")]
+    MergeMismatch,
+    /// Custodian address not present.
+    #[error("custodian address not present")]
+    CustodianMissing,
+    /// Custodian signature not present.
+    #[error("custodian signature not present")]
+    CustodianSignatureMissing,
+    /// Insufficient voting activity in the reference vote account.
+    #[error("insufficient voting activity in the reference vote account")]
+    InsufficientReferenceVotes,
+
+    /// 10 Stake account is not delegated to the provided vote account.
+    #[error("stake account is not delegated to the provided vote account")]
+    VoteAddressMismatch,
+    /// Stake account has not been delinquent for the minimum epochs required for deactivation.
+    #[error("stake account has not been delinquent for the minimum epochs required for deactivation")]
+    MinimumDelinquentEpochsForDeactivationNotMet,
+    /// Delegation amount is less than the minimum.
+    #[error("delegation amount is less than the minimum")]
+    InsufficientDelegation,
+    /// Stake account with transient or inactive stake cannot be redelegated.
+    #[error("stake account with transient or inactive stake cannot be redelegated")]
+    RedelegateTransientOrInactiveStake,
+    /// Stake redelegation to the same vote account is not permitted.
+    #[error("stake redelegation to the same vote account is not permitted")]
+    RedelegateToSameVoteAccount,
+
+    /// 15 Redelegated stake must be fully activated before deactivation.
+    #[error("redelegated stake must be fully activated before deactivation")]
+    RedelegatedStakeMustFullyActivateBeforeDeactivationIsPermitted,
+    /// Stake action is not permitted while the epoch rewards period is active.
+    #[error("stake action is not permitted while the epoch rewards period is active")]
+    EpochRewardsActive,
+}
+
+/// Name of a Stake program custom error code, if any.
+pub fn stake_error_name(code: u32) -> Option<String> {
+    StakeError::from_u32(code).map(|x| format!("{:?}", x))
+}
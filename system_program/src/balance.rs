@@ -0,0 +1,215 @@
+use std::collections::HashMap;
+
+use substreams_solana::pb::sf::solana::r#type::v1::ConfirmedTransaction;
+
+use substreams_solana_utils as utils;
+use utils::instruction::{get_structured_instructions, StructuredInstructions, StructuredInstruction};
+use utils::spl_token::TokenInstruction;
+use utils::system_program::{SystemInstruction, SYSTEM_PROGRAM_ID};
+
+use solana_substreams_common::programs::is_token_program;
+
+use crate::error::ParseError;
+use crate::pb::system_program::SolBalanceChange;
+
+/// Lamport deltas of the transaction accounts from its `pre_balances` and `post_balances`, with
+/// each delta split by cause.
+///
+/// The fee is charged to the fee payer, and the System program transfers and token account closes
+/// are replayed in execution order. Only the fee applies to a failed transaction.
+///
+/// Whatever remains is moved by programs outside of any instruction we can decode. Since only the
+/// owner of an account can debit it, a negative remainder is a debit by the owning program, and
+/// positive remainders are attributed to those debits until they are used up. Anything left over
+/// is unattributed.
+///
+/// Only the accounts whose balance changed, or which were moved by an attributed cause, are returned.
+pub fn get_sol_balance_changes(transaction: &ConfirmedTransaction) -> Result<Vec<SolBalanceChange>, ParseError> {
+    let meta = transaction.meta.as_ref().ok_or(ParseError::MissingTransactionMeta)?;
+
+    let mut changes: Vec<SolBalanceChange> = transaction.resolved_accounts()
+        .iter()
+        .zip(meta.pre_balances.iter().zip(meta.post_balances.iter()))
+        .map(|(account, (pre_balance, post_balance))| SolBalanceChange {
+            account: bs58::encode(account).into_string(),
+            pre_balance: *pre_balance,
+            post_balance: *post_balance,
+            delta: *post_balance as i64 - *pre_balance as i64,
+            ..Default::default()
+        })
+        .collect();
+
+    if let Some(fee_payer) = changes.first_mut() {
+        fee_payer.fee = -(meta.fee as i64);
+    }
+
+    if meta.err.is_none() {
+        let instructions = get_structured_instructions(transaction).map_err(|x| ParseError::TransactionContext(x.to_string()))?;
+        let mut context = BalanceContext::new(&changes);
+        for instruction in instructions.flattened().iter() {
+            context.process(instruction, &mut changes);
+        }
+    }
+
+    attribute_remainders(&mut changes);
+
+    Ok(changes.into_iter()
+        .filter(|x| x.delta != 0 || x.fee != 0 || x.system_transfer != 0 || x.account_close != 0)
+        .collect())
+}
+
+/// Attributes what remains of each delta after the decoded causes to the owning programs, the
+/// credits being matched against the program debits in account order.
+fn attribute_remainders(changes: &mut [SolBalanceChange]) {
+    let mut program_debits: i64 = 0;
+    for change in changes.iter_mut() {
+        let remainder = change.delta - change.fee - change.system_transfer - change.account_close;
+        if remainder < 0 {
+            change.program_owned = remainder;
+            program_debits -= remainder;
+        }
+    }
+    for change in changes.iter_mut() {
+        let remainder = change.delta - change.fee - change.system_transfer - change.account_close - change.program_owned;
+        if remainder > 0 {
+            let credit = remainder.min(program_debits);
+            change.program_owned = credit;
+            change.unattributed = remainder - credit;
+            program_debits -= credit;
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Cause {
+    SystemTransfer,
+    AccountClose,
+}
+
+/// Replays the lamport movements of the instructions, starting from the balances after the fee.
+struct BalanceContext {
+    indexes: HashMap<String, usize>,
+    balances: Vec<u64>,
+}
+
+impl BalanceContext {
+    fn new(changes: &[SolBalanceChange]) -> Self {
+        Self {
+            indexes: changes.iter().enumerate().map(|(i, x)| (x.account.clone(), i)).collect(),
+            balances: changes.iter().map(|x| x.pre_balance.saturating_sub(x.fee.unsigned_abs())).collect(),
+        }
+    }
+
+    fn process(&mut self, instruction: &StructuredInstruction, changes: &mut [SolBalanceChange]) {
+        let accounts = instruction.accounts();
+        let movement = if instruction.program_id() == SYSTEM_PROGRAM_ID {
+            match SystemInstruction::unpack(instruction.data()) {
+                Ok(SystemInstruction::CreateAccount(create_account)) => Some((0, 1, Some(create_account.lamports))),
+                Ok(SystemInstruction::CreateAccountWithSeed(create_account_with_seed)) => Some((0, 1, Some(create_account_with_seed.lamports))),
+                Ok(SystemInstruction::Transfer(transfer)) => Some((0, 1, Some(transfer.lamports))),
+                Ok(SystemInstruction::TransferWithSeed(transfer_with_seed)) => Some((0, 2, Some(transfer_with_seed.lamports))),
+                Ok(SystemInstruction::WithdrawNonceAccount(lamports)) => Some((0, 1, Some(lamports))),
+                _ => None,
+            }.map(|x| (x, Cause::SystemTransfer))
+        } else if is_token_program(&instruction.program_id()) {
+            // A closed token account hands over its whole balance.
+            match TokenInstruction::unpack(instruction.data()) {
+                Ok(TokenInstruction::CloseAccount) => Some(((0, 1, None), Cause::AccountClose)),
+                _ => None,
+            }
+        } else {
+            None
+        };
+
+        if let Some(((from, to, lamports), cause)) = movement {
+            let from = accounts.get(from).and_then(|x| self.indexes.get(&x.to_string()).cloned());
+            let to = accounts.get(to).and_then(|x| self.indexes.get(&x.to_string()).cloned());
+            if let (Some(from), Some(to)) = (from, to) {
+                let lamports = lamports.unwrap_or(self.balances[from]);
+                self.transfer(from, to, lamports, cause, changes);
+            }
+        }
+    }
+
+    fn transfer(&mut self, from: usize, to: usize, lamports: u64, cause: Cause, changes: &mut [SolBalanceChange]) {
+        if from == to {
+            return;
+        }
+        self.balances[from] = self.balances[from].saturating_sub(lamports);
+        self.balances[to] = self.balances[to].saturating_add(lamports);
+        for (index, amount) in [(from, -(lamports as i64)), (to, lamports as i64)] {
+            match cause {
+                Cause::SystemTransfer => changes[index].system_transfer += amount,
+                Cause::AccountClose => changes[index].account_close += amount,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(account: &str, pre_balance: u64, post_balance: u64) -> SolBalanceChange {
+        SolBalanceChange {
+            account: account.to_string(),
+            pre_balance,
+            post_balance,
+            delta: post_balance as i64 - pre_balance as i64,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_system_transfer_is_attributed() {
+        let mut changes = vec![change("payer", 1_000_000, 895_000), change("recipient", 0, 100_000)];
+        changes[0].fee = -5_000;
+
+        let mut context = BalanceContext::new(&changes);
+        assert_eq!(context.balances, vec![995_000, 0]);
+        context.transfer(0, 1, 100_000, Cause::SystemTransfer, &mut changes);
+        attribute_remainders(&mut changes);
+
+        assert_eq!(changes[0].system_transfer, -100_000);
+        assert_eq!(changes[1].system_transfer, 100_000);
+        assert!(changes.iter().all(|x| x.program_owned == 0 && x.unattributed == 0));
+    }
+
+    #[test]
+    fn test_account_close_moves_whole_balance() {
+        let mut changes = vec![change("owner", 1_000_000, 3_039_280), change("token_account", 2_039_280, 0)];
+
+        let mut context = BalanceContext::new(&changes);
+        let lamports = context.balances[1];
+        context.transfer(1, 0, lamports, Cause::AccountClose, &mut changes);
+        attribute_remainders(&mut changes);
+
+        assert_eq!(context.balances, vec![3_039_280, 0]);
+        assert_eq!(changes[0].account_close, 2_039_280);
+        assert_eq!(changes[1].account_close, -2_039_280);
+        assert!(changes.iter().all(|x| x.program_owned == 0 && x.unattributed == 0));
+    }
+
+    #[test]
+    fn test_program_debits_are_matched_to_credits() {
+        let mut changes = vec![
+            change("pool", 5_000_000, 4_000_000),
+            change("user", 1_000_000, 1_600_000),
+            change("other", 0, 700_000),
+        ];
+        attribute_remainders(&mut changes);
+
+        assert_eq!(changes[0].program_owned, -1_000_000);
+        assert_eq!((changes[1].program_owned, changes[1].unattributed), (600_000, 0));
+        assert_eq!((changes[2].program_owned, changes[2].unattributed), (400_000, 300_000));
+    }
+
+    #[test]
+    fn test_self_transfer_is_ignored() {
+        let mut changes = vec![change("payer", 1_000_000, 1_000_000)];
+        let mut context = BalanceContext::new(&changes);
+        context.transfer(0, 0, 500, Cause::SystemTransfer, &mut changes);
+        assert_eq!(changes[0].system_transfer, 0);
+        assert_eq!(context.balances, vec![1_000_000]);
+    }
+}
@@ -0,0 +1,83 @@
+use std::collections::HashSet;
+
+use substreams_solana::pb::sf::solana::r#type::v1::ConfirmedTransaction;
+use substreams_solana_utils::instruction::StructuredInstruction;
+
+use solana_substreams_common::params::{matches, Params};
+use solana_substreams_common::query::Query;
+
+use crate::index::instruction_keys;
+
+/// Filters events by the `accounts` module param, matching the accounts of their instruction, or
+/// by the block filter query of `filtered_system_program_events`, matching the instruction keys.
+///
+/// An unset filter matches every event.
+#[derive(Clone, Debug, Default)]
+pub struct Filter {
+    accounts: Option<HashSet<String>>,
+    query: Option<Query>,
+}
+
+impl Filter {
+    pub fn new(params: &Params) -> Self {
+        Self {
+            accounts: params.get_set("accounts"),
+            query: None,
+        }
+    }
+
+    pub fn from_query(query: Query) -> Self {
+        Self {
+            accounts: None,
+            query: Some(query),
+        }
+    }
+
+    pub fn matches(&self, instruction: &StructuredInstruction) -> bool {
+        if self.accounts.is_none() && self.query.is_none() {
+            return true;
+        }
+        let accounts: Vec<String> = instruction.accounts().iter().map(|x| x.to_string()).collect();
+        self.matches_accounts(&accounts)
+    }
+
+    /// Matches the accounts of a top level instruction of the transaction, such as the one a
+    /// failed transaction failed on.
+    pub fn matches_top_level(&self, transaction: &ConfirmedTransaction, instruction_index: u32) -> bool {
+        if self.accounts.is_none() && self.query.is_none() {
+            return true;
+        }
+        let resolved_accounts = transaction.resolved_accounts();
+        let accounts: Vec<String> = transaction.transaction.iter()
+            .flat_map(|x| x.message.iter())
+            .flat_map(|x| x.instructions.get(instruction_index as usize))
+            .flat_map(|x| x.accounts.iter())
+            .filter_map(|x| resolved_accounts.get(*x as usize))
+            .map(|x| bs58::encode(x).into_string())
+            .collect();
+        self.matches_accounts(&accounts)
+    }
+
+    fn matches_accounts(&self, accounts: &[String]) -> bool {
+        let query_matches = match self.query.as_ref() {
+            Some(query) => query.matches(&instruction_keys(accounts)),
+            None => true,
+        };
+        query_matches && matches(&self.accounts, accounts.iter().map(|x| x.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_query() {
+        let filter = Filter::from_query(Query::parse("account:a || (account:b && -account:c)").unwrap());
+        assert!(filter.matches_accounts(&["a".to_string()]));
+        assert!(filter.matches_accounts(&["b".to_string(), "d".to_string()]));
+        assert!(!filter.matches_accounts(&["b".to_string(), "c".to_string()]));
+        assert!(!filter.matches_accounts(&[]));
+        assert!(Filter::new(&Params::default()).matches_accounts(&[]));
+    }
+}
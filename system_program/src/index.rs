@@ -0,0 +1,33 @@
+use std::collections::BTreeSet;
+
+use substreams::pb::sf::substreams::index::v1::Keys;
+use substreams_solana::pb::sf::solana::r#type::v1::Block;
+
+use substreams_solana_utils as utils;
+use utils::instruction::{get_structured_instructions, StructuredInstructions};
+use utils::system_program::SYSTEM_PROGRAM_ID;
+
+/// Block index keys of the System program instructions: `program:` and `account:`.
+pub fn index_keys(block: &Block) -> Keys {
+    let mut keys = BTreeSet::new();
+    for transaction in block.transactions() {
+        let instructions = match get_structured_instructions(transaction) {
+            Ok(instructions) => instructions,
+            Err(_) => continue,
+        };
+        for instruction in instructions.flattened().iter().filter(|x| x.program_id() == SYSTEM_PROGRAM_ID) {
+            let accounts: Vec<String> = instruction.accounts().iter().map(|x| x.to_string()).collect();
+            keys.extend(instruction_keys(&accounts));
+        }
+    }
+    Keys { keys: keys.into_iter().collect() }
+}
+
+/// Index keys of a System program instruction given its accounts, which the query of
+/// `filtered_system_program_events` is matched against.
+pub fn instruction_keys(accounts: &[String]) -> BTreeSet<String> {
+    let mut keys = BTreeSet::new();
+    keys.insert(format!("program:{}", SYSTEM_PROGRAM_ID));
+    keys.extend(accounts.iter().map(|x| format!("account:{}", x)));
+    keys
+}
@@ -0,0 +1,79 @@
+use std::rc::Rc;
+
+use substreams_solana_utils as utils;
+use utils::instruction::StructuredInstruction;
+use utils::spl_token::TokenInstruction;
+
+use solana_substreams_common::programs::{
+    is_token_program,
+    metadata_instruction,
+    MPL_TOKEN_METADATA_PROGRAM_ID,
+    PUMPFUN_CREATE_DISCRIMINATOR,
+    PUMPFUN_PROGRAM_ID,
+    RAYDIUM_AMM_INITIALIZE2_TAG,
+    RAYDIUM_AMM_PROGRAM_ID,
+};
+
+/// Classifies a created account by the first instruction initializing it after its creation, or
+/// else by the instruction which invoked the creation, such as the pumpfun Create deriving a
+/// bonding curve.
+///
+/// The kind is one of `mint`, `token_account`, `metadata`, `master_edition`, `bonding_curve` or
+/// `amm_state`, and is unset when no such instruction targets the account.
+pub fn get_account_kind<'a>(
+    account: &str,
+    instruction_index: usize,
+    flattened: &[Rc<StructuredInstruction<'a>>],
+) -> Option<&'static str> {
+    let next = flattened.iter().skip(instruction_index + 1).find_map(|x| initialized_kind(account, x));
+    next.or_else(|| {
+        let mut parent = flattened.get(instruction_index)?.parent_instruction();
+        while let Some(instruction) = parent {
+            if let Some(kind) = initialized_kind(account, &instruction) {
+                return Some(kind);
+            }
+            parent = instruction.parent_instruction();
+        }
+        None
+    })
+}
+
+/// Kind of the account if the instruction initializes it.
+fn initialized_kind(account: &str, instruction: &StructuredInstruction) -> Option<&'static str> {
+    let is_account = |index: usize| instruction.accounts().get(index).map_or(false, |x| x.to_string() == account);
+
+    if is_token_program(&instruction.program_id()) {
+        match TokenInstruction::unpack(instruction.data()).ok()? {
+            TokenInstruction::InitializeMint { .. } |
+            TokenInstruction::InitializeMint2 { .. } if is_account(0) => Some("mint"),
+            TokenInstruction::InitializeAccount |
+            TokenInstruction::InitializeAccount2 { .. } |
+            TokenInstruction::InitializeAccount3 { .. } if is_account(0) => Some("token_account"),
+            _ => None,
+        }
+    } else if instruction.program_id() == MPL_TOKEN_METADATA_PROGRAM_ID {
+        match *instruction.data().first()? {
+            metadata_instruction::CREATE_METADATA_ACCOUNT |
+            metadata_instruction::CREATE_METADATA_ACCOUNT_V2 |
+            metadata_instruction::CREATE_METADATA_ACCOUNT_V3 |
+            metadata_instruction::CREATE if is_account(0) => Some("metadata"),
+            metadata_instruction::CREATE_MASTER_EDITION |
+            metadata_instruction::CREATE_MASTER_EDITION_V3 if is_account(0) => Some("master_edition"),
+            metadata_instruction::CREATE if is_account(1) => Some("master_edition"),
+            _ => None,
+        }
+    } else if instruction.program_id() == RAYDIUM_AMM_PROGRAM_ID {
+        match *instruction.data().first()? {
+            RAYDIUM_AMM_INITIALIZE2_TAG if is_account(4) => Some("amm_state"),
+            _ => None,
+        }
+    } else if instruction.program_id() == PUMPFUN_PROGRAM_ID {
+        if instruction.data().starts_with(&PUMPFUN_CREATE_DISCRIMINATOR) && is_account(2) {
+            Some("bonding_curve")
+        } else {
+            None
+        }
+    } else {
+        None
+    }
+}
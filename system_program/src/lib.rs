@@ -1,6 +1,7 @@
-use anyhow::anyhow;
-use anyhow::Context;
+use std::rc::Rc;
+
 use substreams::errors::Error;
+use substreams::pb::sf::substreams::index::v1::Keys;
 use substreams_solana::pb::sf::solana::r#type::v1::ConfirmedTransaction;
 use substreams_solana::pb::sf::solana::r#type::v1::Block;
 
@@ -10,27 +11,182 @@ use utils::instruction::{get_structured_instructions, StructuredInstructions, St
 use utils::system_program::{self, SystemInstruction, SYSTEM_PROGRAM_ID};
 use utils::pubkey::Pubkey;
 
+use solana_substreams_common::{get_failed_instruction, get_transaction_envelope, is_program_invoked};
+use solana_substreams_common::params::Params;
+use solana_substreams_common::query::Query;
+use solana_substreams_common::address::is_seed_address;
+use solana_substreams_common::instruction::{flattened_index, get_call_path, get_unknown_instruction, stack_depth};
+
+use spl_memo_substream::get_transaction_memos;
+
+pub mod error;
+use error::ParseError;
+
+pub mod system_error;
+use system_error::system_error_name;
+
+pub mod filter;
+use filter::Filter;
+
+pub mod index;
+use index::index_keys;
+
+pub mod nonce;
+use nonce::get_durable_nonce;
+
+pub mod balance;
+use balance::get_sol_balance_changes;
+
+pub mod kind;
+use kind::get_account_kind;
+
 pub mod pb;
 use pb::system_program::*;
 use pb::system_program::system_program_event::Event;
 
-pub fn parse_transaction(transaction: &ConfirmedTransaction) -> Result<Vec<SystemProgramEvent>, Error> {
-    if let Some(_) = transaction.meta.as_ref().unwrap().err {
+#[substreams::handlers::map]
+fn system_program_events(params: String, block: Block) -> Result<SystemProgramBlockEvents, Error> {
+    Ok(_block_events(&block, parse_block(&block, &Params::parse(&params))))
+}
+
+#[substreams::handlers::map]
+fn system_program_index(block: Block) -> Result<Keys, Error> {
+    Ok(index_keys(&block))
+}
+
+/// Events matching the block filter query given as params, such as `account:<account>`, on their own index keys.
+#[substreams::handlers::map]
+fn filtered_system_program_events(query: String, block: Block) -> Result<SystemProgramBlockEvents, Error> {
+    let filter = Filter::from_query(Query::parse(&query).map_err(Error::msg)?);
+    Ok(_block_events(&block, _parse_block(&block, &Params::default(), &filter)))
+}
+
+/// Nonce account initializations, authorizations, withdrawals and upgrades of the block, by nonce account.
+#[substreams::handlers::map]
+fn nonce_account_lifecycles(block: Block) -> Result<NonceAccountLifecycles, Error> {
+    let transactions = parse_block(&block, &Params::default());
+    Ok(NonceAccountLifecycles {
+        slot: block.slot,
+        nonce_accounts: nonce::nonce_account_lifecycles(&transactions),
+    })
+}
+
+/// Lamport deltas of the accounts of each transaction of the block, split by cause.
+///
+/// The `accounts` param restricts the output to the given accounts.
+#[substreams::handlers::map]
+fn sol_balance_changes(params: String, block: Block) -> Result<SolBalanceChanges, Error> {
+    let params = Params::parse(&params);
+    let include_failed = params.get_bool("include_failed");
+    let accounts = params.get_set("accounts");
+    let mut transactions: Vec<TransactionSolBalanceChanges> = Vec::new();
+    for (i, transaction) in block.transactions.iter().enumerate() {
+        let failed = transaction.meta.as_ref().map_or(false, |x| x.err.is_some());
+        if failed && !include_failed {
+            continue;
+        }
+        let signature = utils::transaction::get_signature(&transaction);
+        match get_sol_balance_changes(transaction) {
+            Ok(mut balance_changes) => {
+                if let Some(accounts) = accounts.as_ref() {
+                    balance_changes.retain(|x| accounts.contains(&x.account));
+                }
+                if !balance_changes.is_empty() {
+                    transactions.push(TransactionSolBalanceChanges {
+                        signature,
+                        transaction_index: i as u32,
+                        balance_changes,
+                        failed,
+                    });
+                }
+            },
+            Err(error) => substreams::log::println(format!("Failed to process transaction {}: {}", signature, error)),
+        }
+    }
+    Ok(SolBalanceChanges {
+        slot: block.slot,
+        block_time: block.block_time.as_ref().map(|x| x.timestamp).unwrap_or_default(),
+        block_hash: block.blockhash.clone(),
+        transactions,
+    })
+}
+
+fn _block_events(block: &Block, transactions: Vec<SystemProgramTransactionEvents>) -> SystemProgramBlockEvents {
+    SystemProgramBlockEvents {
+        slot: block.slot,
+        block_time: block.block_time.as_ref().map(|x| x.timestamp).unwrap_or_default(),
+        block_hash: block.blockhash.clone(),
+        transactions,
+    }
+}
+
+pub fn parse_block(block: &Block, params: &Params) -> Vec<SystemProgramTransactionEvents> {
+    _parse_block(block, params, &Filter::new(params))
+}
+
+fn _parse_block(block: &Block, params: &Params, filter: &Filter) -> Vec<SystemProgramTransactionEvents> {
+    let include_failed = params.get_bool("include_failed");
+    let include_memos = params.get_bool("include_memos");
+    let mut block_events: Vec<SystemProgramTransactionEvents> = Vec::new();
+    for (i, transaction) in block.transactions.iter().enumerate() {
+        let failed = transaction.meta.as_ref().map_or(false, |x| x.err.is_some());
+        if failed && !include_failed {
+            continue;
+        }
+        let signature = utils::transaction::get_signature(&transaction);
+        let result = if failed {
+            Ok(parse_failed_transaction(transaction, filter))
+        } else {
+            parse_transaction(transaction, filter)
+        };
+        match result {
+            Ok(mut events) if !events.is_empty() => {
+                if include_memos {
+                    attach_memo(&mut events, transaction);
+                }
+                block_events.push(SystemProgramTransactionEvents {
+                    signature,
+                    transaction_index: i as u32,
+                    envelope: Some(get_transaction_envelope(transaction)),
+                    durable_nonce: get_durable_nonce(transaction),
+                    events,
+                });
+            },
+            Ok(_) => (),
+            Err(error) => substreams::log::println(format!("Failed to process transaction {}: {}", signature, error)),
+        }
+    }
+    block_events
+}
+
+pub fn parse_transaction(transaction: &ConfirmedTransaction, filter: &Filter) -> Result<Vec<SystemProgramEvent>, ParseError> {
+    if let Some(_) = transaction.meta.as_ref().ok_or(ParseError::MissingTransactionMeta)?.err {
         return Ok(Vec::new())
     }
 
     let mut events: Vec<SystemProgramEvent> = Vec::new();
 
-    let context = get_context(transaction)?;
-    let instructions = get_structured_instructions(transaction)?;
+    let context = get_context(transaction).map_err(|x| ParseError::TransactionContext(x.to_string()))?;
+    let instructions = get_structured_instructions(transaction).map_err(|x| ParseError::TransactionContext(x.to_string()))?;
 
-    for (i, instruction) in instructions.flattened().iter().enumerate() {
-        if instruction.program_id() == SYSTEM_PROGRAM_ID {
+    let flattened = instructions.flattened();
+    for (i, instruction) in flattened.iter().enumerate() {
+        if instruction.program_id() == SYSTEM_PROGRAM_ID && filter.matches(instruction) {
+            let call_path = get_call_path(instruction, &flattened);
             match parse_instruction(instruction, &context) {
-                Ok(event) => {
-                    events.push(SystemProgramEvent { instruction_index: i as u32, event });
+                Ok(mut event) => {
+                    set_account_kind(&mut event, i, &flattened);
+                    events.push(SystemProgramEvent {
+                        instruction_index: i as u32,
+                        stack_depth: stack_depth(instruction),
+                        top_instruction_program_id: call_path.top_instruction_program_id,
+                        parent_instruction_program_id: call_path.parent_instruction_program_id,
+                        top_instruction_index: call_path.top_instruction_index,
+                        parent_instruction_index: call_path.parent_instruction_index,
+                        event,
+                    });
                 },
-                Err(e) => return Err(anyhow!("Failed to parse transaction {} with error: {}", context.signature, e))
+                Err(error) => substreams::log::println(format!("Failed to process instruction of transaction {}: {}", &context.signature, error)),
             }
         }
     }
@@ -38,14 +194,79 @@ pub fn parse_transaction(transaction: &ConfirmedTransaction) -> Result<Vec<Syste
     Ok(events)
 }
 
+/// Classifies the account created by a CreateAccount or CreateAccountWithSeed event.
+fn set_account_kind<'a>(event: &mut Option<Event>, instruction_index: usize, flattened: &[Rc<StructuredInstruction<'a>>]) {
+    match event.as_mut() {
+        Some(Event::CreateAccount(create_account)) => {
+            create_account.kind = get_account_kind(&create_account.new_account, instruction_index, flattened).map(|x| x.to_string());
+        },
+        Some(Event::CreateAccountWithSeed(create_account_with_seed)) => {
+            create_account_with_seed.kind = get_account_kind(&create_account_with_seed.created_account, instruction_index, flattened).map(|x| x.to_string());
+        },
+        _ => (),
+    }
+}
+
+/// Sets the memo written right before each transfer at the same invocation level, if any, on its event.
+fn attach_memo(events: &mut [SystemProgramEvent], transaction: &ConfirmedTransaction) {
+    let memos = get_transaction_memos(transaction);
+    if memos.is_empty() {
+        return;
+    }
+    for event in events.iter_mut() {
+        let memo = memos.get(&event.instruction_index).cloned();
+        match event.event.as_mut() {
+            Some(Event::Transfer(transfer)) => transfer.memo = memo,
+            Some(Event::TransferWithSeed(transfer_with_seed)) => transfer_with_seed.memo = memo,
+            _ => (),
+        }
+    }
+}
+
+/// Parses a failed transaction into a `FailedInstruction` event, if it invoked the System program.
+pub fn parse_failed_transaction(transaction: &ConfirmedTransaction, filter: &Filter) -> Vec<SystemProgramEvent> {
+    if !is_program_invoked(transaction, &SYSTEM_PROGRAM_ID.to_string()) {
+        return Vec::new();
+    }
+    let failed_instruction = match get_failed_instruction(transaction, custom_error_name) {
+        Some(failed_instruction) => failed_instruction,
+        None => return Vec::new(),
+    };
+    if !filter.matches_top_level(transaction, failed_instruction.instruction_index) {
+        return Vec::new();
+    }
+
+    vec![SystemProgramEvent {
+        instruction_index: flattened_index(transaction, failed_instruction.instruction_index),
+        stack_depth: 0,
+        top_instruction_program_id: None,
+        parent_instruction_program_id: None,
+        top_instruction_index: None,
+        parent_instruction_index: None,
+        event: Some(Event::FailedInstruction(failed_instruction)),
+    }]
+}
+
+/// Name of a custom error code raised by the System program.
+pub fn custom_error_name(program_id: &str, code: u32) -> Option<String> {
+    if program_id == SYSTEM_PROGRAM_ID.to_string() {
+        system_error_name(code)
+    } else {
+        None
+    }
+}
+
 pub fn parse_instruction<'a>(
     instruction: &StructuredInstruction<'a>,
     context: &TransactionContext
-) -> Result<Option<Event>, Error> {
+) -> Result<Option<Event>, ParseError> {
     if instruction.program_id() != SYSTEM_PROGRAM_ID {
-        return Err(anyhow!("Not a System Program instruction."));
+        return Err(ParseError::UnexpectedProgram(instruction.program_id().to_string()));
     }
-    let unpacked = SystemInstruction::unpack(&instruction.data())?;
+    let unpacked = match SystemInstruction::unpack(&instruction.data()) {
+        Ok(unpacked) => unpacked,
+        Err(_) => return Ok(Some(Event::UnknownInstruction(get_unknown_instruction(instruction, 4)))),
+    };
     match unpacked {
         SystemInstruction::CreateAccount(create_account) => {
             _parse_create_account_instruction(instruction, context, &create_account).map(|x| Some(Event::CreateAccount(x)))
@@ -86,16 +307,20 @@ pub fn parse_instruction<'a>(
         SystemInstruction::UpgradeNonceAccount => {
             _parse_upgrade_nonce_account_instruction(instruction, context).map(|x| Some(Event::UpgradeNonceAccount(x)))
         }
-    }.context("Failed to parse System instruction")
+    }
+}
+
+fn _account(instruction: &StructuredInstruction, index: usize) -> Result<String, ParseError> {
+    instruction.accounts().get(index).map(|x| x.to_string()).ok_or(ParseError::MissingAccount(index))
 }
 
 fn _parse_create_account_instruction(
     instruction: &StructuredInstruction,
     _context: &TransactionContext,
     create_account: &system_program::CreateAccount,
-) -> Result<CreateAccountEvent, Error> {
-    let funding_account = instruction.accounts()[0].to_string();
-    let new_account = instruction.accounts()[1].to_string();
+) -> Result<CreateAccountEvent, ParseError> {
+    let funding_account = _account(instruction, 0)?;
+    let new_account = _account(instruction, 1)?;
     let lamports = create_account.lamports;
     let owner = create_account.owner.to_string();
     let space = create_account.space;
@@ -106,6 +331,7 @@ fn _parse_create_account_instruction(
         lamports,
         owner,
         space,
+        kind: None,
     })
 }
 
@@ -113,8 +339,8 @@ fn _parse_assign_instruction(
     instruction: &StructuredInstruction,
     _context: &TransactionContext,
     assign: &system_program::Assign,
-) -> Result<AssignEvent, Error> {
-    let assigned_account = instruction.accounts()[0].to_string();
+) -> Result<AssignEvent, ParseError> {
+    let assigned_account = _account(instruction, 0)?;
     let owner = assign.owner.to_string();
 
     Ok(AssignEvent {
@@ -127,15 +353,16 @@ fn _parse_transfer_instruction(
     instruction: &StructuredInstruction,
     _context: &TransactionContext,
     transfer: &system_program::Transfer,
-) -> Result<TransferEvent, Error> {
-    let funding_account = instruction.accounts()[0].to_string();
-    let recipient_account = instruction.accounts()[1].to_string();
+) -> Result<TransferEvent, ParseError> {
+    let funding_account = _account(instruction, 0)?;
+    let recipient_account = _account(instruction, 1)?;
     let lamports = transfer.lamports;
 
     Ok(TransferEvent {
         funding_account,
         recipient_account,
         lamports,
+        memo: None,
     })
 }
 
@@ -143,14 +370,15 @@ fn _parse_create_account_with_seed_instruction(
     instruction: &StructuredInstruction,
     _context: &TransactionContext,
     create_account_with_seed: &system_program::CreateAccountWithSeed,
-) -> Result<CreateAccountWithSeedEvent, Error> {
-    let funding_account = instruction.accounts()[0].to_string();
-    let created_account = instruction.accounts()[1].to_string();
+) -> Result<CreateAccountWithSeedEvent, ParseError> {
+    let funding_account = _account(instruction, 0)?;
+    let created_account = _account(instruction, 1)?;
     let base_account = create_account_with_seed.base.to_string();
     let lamports = create_account_with_seed.lamports;
     let owner = create_account_with_seed.owner.to_string();
     let seed = create_account_with_seed.seed.0.clone();
     let space = create_account_with_seed.space;
+    let derived_address_matches = is_seed_address(&created_account, &create_account_with_seed.base, &seed, &create_account_with_seed.owner);
 
     Ok(CreateAccountWithSeedEvent {
         funding_account,
@@ -160,15 +388,17 @@ fn _parse_create_account_with_seed_instruction(
         lamports,
         space,
         owner,
+        kind: None,
+        derived_address_matches,
     })
 }
 
 fn _parse_advance_nonce_account_instruction(
     instruction: &StructuredInstruction,
     _context: &TransactionContext,
-) -> Result<AdvanceNonceAccountEvent, Error> {
-    let nonce_account = instruction.accounts()[0].to_string();
-    let nonce_authority = instruction.accounts()[2].to_string();
+) -> Result<AdvanceNonceAccountEvent, ParseError> {
+    let nonce_account = _account(instruction, 0)?;
+    let nonce_authority = _account(instruction, 2)?;
 
     Ok(AdvanceNonceAccountEvent {
         nonce_account,
@@ -180,10 +410,10 @@ fn _parse_withdraw_nonce_account_instruction(
     instruction: &StructuredInstruction,
     _context: &TransactionContext,
     lamports: u64,
-) -> Result<WithdrawNonceAccountEvent, Error> {
-    let nonce_account = instruction.accounts()[0].to_string();
-    let recipient_account = instruction.accounts()[1].to_string();
-    let nonce_authority = instruction.accounts()[4].to_string();
+) -> Result<WithdrawNonceAccountEvent, ParseError> {
+    let nonce_account = _account(instruction, 0)?;
+    let recipient_account = _account(instruction, 1)?;
+    let nonce_authority = _account(instruction, 4)?;
 
     Ok(WithdrawNonceAccountEvent {
         nonce_account,
@@ -197,8 +427,8 @@ fn _parse_initialize_nonce_account_instruction(
     instruction: &StructuredInstruction,
     _context: &TransactionContext,
     authority: Pubkey,
-) -> Result<InitializeNonceAccountEvent, Error> {
-    let nonce_account = instruction.accounts()[0].to_string();
+) -> Result<InitializeNonceAccountEvent, ParseError> {
+    let nonce_account = _account(instruction, 0)?;
     let nonce_authority = authority.to_string();
 
     Ok(InitializeNonceAccountEvent {
@@ -211,9 +441,9 @@ fn _parse_authorize_nonce_account_instruction(
     instruction: &StructuredInstruction,
     _context: &TransactionContext,
     pubkey: Pubkey,
-) -> Result<AuthorizeNonceAccountEvent, Error> {
-    let nonce_account = instruction.accounts()[0].to_string();
-    let nonce_authority = instruction.accounts()[1].to_string();
+) -> Result<AuthorizeNonceAccountEvent, ParseError> {
+    let nonce_account = _account(instruction, 0)?;
+    let nonce_authority = _account(instruction, 1)?;
     let new_nonce_authority = pubkey.to_string();
 
     Ok(AuthorizeNonceAccountEvent {
@@ -227,8 +457,8 @@ fn _parse_allocate_instruction(
     instruction: &StructuredInstruction,
     _context: &TransactionContext,
     allocate: &system_program::Allocate,
-) -> Result<AllocateEvent, Error> {
-    let account = instruction.accounts()[0].to_string();
+) -> Result<AllocateEvent, ParseError> {
+    let account = _account(instruction, 0)?;
     let space = allocate.space;
 
     Ok(AllocateEvent {
@@ -241,12 +471,13 @@ fn _parse_allocate_with_seed_instruction(
     instruction: &StructuredInstruction,
     _context: &TransactionContext,
     allocate_with_seed: &system_program::AllocateWithSeed,
-) -> Result<AllocateWithSeedEvent, Error> {
-    let allocated_account = instruction.accounts()[0].to_string();
+) -> Result<AllocateWithSeedEvent, ParseError> {
+    let allocated_account = _account(instruction, 0)?;
     let space = allocate_with_seed.space;
     let base_account = allocate_with_seed.base.to_string();
     let owner = allocate_with_seed.owner.to_string();
     let seed = allocate_with_seed.seed.0.clone();
+    let derived_address_matches = is_seed_address(&allocated_account, &allocate_with_seed.base, &seed, &allocate_with_seed.owner);
 
     Ok(AllocateWithSeedEvent {
         allocated_account,
@@ -254,6 +485,7 @@ fn _parse_allocate_with_seed_instruction(
         seed,
         owner,
         space,
+        derived_address_matches,
     })
 }
 
@@ -261,17 +493,19 @@ fn _parse_assign_with_seed_instruction(
     instruction: &StructuredInstruction,
     _context: &TransactionContext,
     assign_with_seed: &system_program::AssignWithSeed,
-) -> Result<AssignWithSeedEvent, Error> {
-    let assigned_account = instruction.accounts()[0].to_string();
+) -> Result<AssignWithSeedEvent, ParseError> {
+    let assigned_account = _account(instruction, 0)?;
     let base_account = assign_with_seed.base.to_string();
     let owner = assign_with_seed.owner.to_string();
     let seed = assign_with_seed.seed.0.clone();
+    let derived_address_matches = is_seed_address(&assigned_account, &assign_with_seed.base, &seed, &assign_with_seed.owner);
 
     Ok(AssignWithSeedEvent {
         assigned_account,
         base_account,
         owner,
         seed,
+        derived_address_matches,
     })
 }
 
@@ -279,13 +513,15 @@ fn _parse_transfer_with_seed_instruction(
     instruction: &StructuredInstruction,
     _context: &TransactionContext,
     transfer_with_seed: system_program::TransferWithSeed
-) -> Result<TransferWithSeedEvent, Error> {
-    let funding_account = instruction.accounts()[0].to_string();
-    let base_account = instruction.accounts()[1].to_string();
-    let recipient_account = instruction.accounts()[2].to_string();
+) -> Result<TransferWithSeedEvent, ParseError> {
+    let funding_account = _account(instruction, 0)?;
+    let base_account = _account(instruction, 1)?;
+    let recipient_account = _account(instruction, 2)?;
     let from_owner = transfer_with_seed.from_owner.to_string();
     let from_seed = transfer_with_seed.from_seed.0.clone();
     let lamports = transfer_with_seed.lamports;
+    let derived_address_matches = instruction.accounts().get(1)
+        .map_or(false, |base| is_seed_address(&funding_account, base, &from_seed, &transfer_with_seed.from_owner));
 
     Ok(TransferWithSeedEvent {
         funding_account,
@@ -294,14 +530,16 @@ fn _parse_transfer_with_seed_instruction(
         from_owner,
         from_seed,
         lamports,
+        memo: None,
+        derived_address_matches,
     })
 }
 
 fn _parse_upgrade_nonce_account_instruction(
     instruction: &StructuredInstruction,
     _context: &TransactionContext,
-) -> Result<UpgradeNonceAccountEvent, Error> {
-    let nonce_account = instruction.accounts()[0].to_string();
+) -> Result<UpgradeNonceAccountEvent, ParseError> {
+    let nonce_account = _account(instruction, 0)?;
 
     Ok(UpgradeNonceAccountEvent {
         nonce_account,
@@ -311,129 +549,129 @@ fn _parse_upgrade_nonce_account_instruction(
 pub fn parse_create_account_instruction<'a>(
     instruction: &StructuredInstruction<'a>,
     context: &TransactionContext,
-) -> Result<CreateAccountEvent, Error> {
+) -> Result<CreateAccountEvent, ParseError> {
     match parse_instruction(instruction, context)? {
         Some(Event::CreateAccount(event)) => Ok(event),
-        _ => Err(anyhow!("Not a CreateAccountInstruction."))
+        _ => Err(ParseError::InstructionMismatch("CreateAccount")),
     }
 }
 
 pub fn parse_assign_instruction<'a>(
     instruction: &StructuredInstruction<'a>,
     context: &TransactionContext,
-) -> Result<AssignEvent, Error> {
+) -> Result<AssignEvent, ParseError> {
     match parse_instruction(instruction, context)? {
         Some(Event::Assign(event)) => Ok(event),
-        _ => Err(anyhow!("Not an AssignInstruction."))
+        _ => Err(ParseError::InstructionMismatch("Assign")),
     }
 }
 
 pub fn parse_transfer_instruction<'a>(
     instruction: &StructuredInstruction<'a>,
     context: &TransactionContext,
-) -> Result<TransferEvent, Error> {
+) -> Result<TransferEvent, ParseError> {
     match parse_instruction(instruction, context)? {
         Some(Event::Transfer(event)) => Ok(event),
-        _ => Err(anyhow!("Not a TransferInstruction."))
+        _ => Err(ParseError::InstructionMismatch("Transfer")),
     }
 }
 
 pub fn parse_create_account_with_seed_instruction<'a>(
     instruction: &StructuredInstruction<'a>,
     context: &TransactionContext,
-) -> Result<CreateAccountWithSeedEvent, Error> {
+) -> Result<CreateAccountWithSeedEvent, ParseError> {
     match parse_instruction(instruction, context)? {
         Some(Event::CreateAccountWithSeed(event)) => Ok(event),
-        _ => Err(anyhow!("Not a CreateAccountWithSeedInstruction."))
+        _ => Err(ParseError::InstructionMismatch("CreateAccountWithSeed")),
     }
 }
 
 pub fn parse_advance_nonce_account_instruction<'a>(
     instruction: &StructuredInstruction<'a>,
     context: &TransactionContext,
-) -> Result<AdvanceNonceAccountEvent, Error> {
+) -> Result<AdvanceNonceAccountEvent, ParseError> {
     match parse_instruction(instruction, context)? {
         Some(Event::AdvanceNonceAccount(event)) => Ok(event),
-        _ => Err(anyhow!("Not an AdvanceNonceAccountInstruction.")),
+        _ => Err(ParseError::InstructionMismatch("AdvanceNonceAccount")),
     }
 }
 
 pub fn parse_withdraw_nonce_account_instruction<'a>(
     instruction: &StructuredInstruction<'a>,
     context: &TransactionContext,
-) -> Result<WithdrawNonceAccountEvent, Error> {
+) -> Result<WithdrawNonceAccountEvent, ParseError> {
     match parse_instruction(instruction, context)? {
         Some(Event::WithdrawNonceAccount(event)) => Ok(event),
-        _ => Err(anyhow!("Not a WithdrawNonceAccountInstruction."))
+        _ => Err(ParseError::InstructionMismatch("WithdrawNonceAccount")),
     }
 }
 
 pub fn parse_initialize_nonce_account_instruction<'a>(
     instruction: &StructuredInstruction<'a>,
     context: &TransactionContext,
-) -> Result<InitializeNonceAccountEvent, Error> {
+) -> Result<InitializeNonceAccountEvent, ParseError> {
     match parse_instruction(instruction, context)? {
         Some(Event::InitializeNonceAccount(event)) => Ok(event),
-        _ => Err(anyhow!("Not an InitializeNonceAccountInstruction."))
+        _ => Err(ParseError::InstructionMismatch("InitializeNonceAccount")),
     }
 }
 
 pub fn parse_authorize_nonce_account_instruction<'a>(
     instruction: &StructuredInstruction<'a>,
     context: &TransactionContext,
-) -> Result<AuthorizeNonceAccountEvent, Error> {
+) -> Result<AuthorizeNonceAccountEvent, ParseError> {
     match parse_instruction(instruction, context)? {
         Some(Event::AuthorizeNonceAccount(event)) => Ok(event),
-        _ => Err(anyhow!("Not an AuthorizeNonceAccountInstruction."))
+        _ => Err(ParseError::InstructionMismatch("AuthorizeNonceAccount")),
     }
 }
 
 pub fn parse_allocate_instruction<'a>(
     instruction: &StructuredInstruction<'a>,
     context: &TransactionContext,
-) -> Result<AllocateEvent, Error> {
+) -> Result<AllocateEvent, ParseError> {
     match parse_instruction(instruction, context)? {
         Some(Event::Allocate(event)) => Ok(event),
-        _ => Err(anyhow!("Not an AllocateInstruction."))
+        _ => Err(ParseError::InstructionMismatch("Allocate")),
     }
 }
 
 pub fn parse_allocate_with_seed_instruction<'a>(
     instruction: &StructuredInstruction<'a>,
     context: &TransactionContext,
-) -> Result<AllocateWithSeedEvent, Error> {
+) -> Result<AllocateWithSeedEvent, ParseError> {
     match parse_instruction(instruction, context)? {
         Some(Event::AllocateWithSeed(event)) => Ok(event),
-        _ => Err(anyhow!("Not an AllocateWithSeedInstruction."))
+        _ => Err(ParseError::InstructionMismatch("AllocateWithSeed")),
     }
 }
 
 pub fn parse_assign_with_seed_instruction<'a>(
     instruction: &StructuredInstruction<'a>,
     context: &TransactionContext,
-) -> Result<AssignWithSeedEvent, Error> {
+) -> Result<AssignWithSeedEvent, ParseError> {
     match parse_instruction(instruction, context)? {
         Some(Event::AssignWithSeed(event)) => Ok(event),
-        _ => Err(anyhow!("Not an AssignWithSeedInstruction."))
+        _ => Err(ParseError::InstructionMismatch("AssignWithSeed")),
     }
 }
 
 pub fn parse_transfer_with_seed_instruction<'a>(
     instruction: &StructuredInstruction<'a>,
     context: &TransactionContext,
-) -> Result<TransferWithSeedEvent, Error> {
+) -> Result<TransferWithSeedEvent, ParseError> {
     match parse_instruction(instruction, context)? {
         Some(Event::TransferWithSeed(event)) => Ok(event),
-        _ => Err(anyhow!("Not a TransferWithSeedInstruction."))
+        _ => Err(ParseError::InstructionMismatch("TransferWithSeed")),
     }
 }
 
 pub fn parse_upgrade_nonce_account_instruction<'a>(
     instruction: &StructuredInstruction<'a>,
     context: &TransactionContext,
-) -> Result<UpgradeNonceAccountEvent, Error> {
+) -> Result<UpgradeNonceAccountEvent, ParseError> {
     match parse_instruction(instruction, context)? {
         Some(Event::UpgradeNonceAccount(event)) => Ok(event),
-        _ => Err(anyhow!("Not an UpgradeNonceAccountInstruction."))
+        _ => Err(ParseError::InstructionMismatch("UpgradeNonceAccount")),
     }
 }
@@ -0,0 +1,61 @@
+use std::collections::BTreeMap;
+
+use substreams_solana::pb::sf::solana::r#type::v1::ConfirmedTransaction;
+
+use substreams_solana_utils as utils;
+use utils::system_program::{SystemInstruction, SYSTEM_PROGRAM_ID};
+
+use crate::pb::system_program::{DurableNonce, NonceAccountLifecycle, NonceAccountLifecycleEvent, SystemProgramTransactionEvents};
+use crate::pb::system_program::system_program_event::Event;
+use crate::pb::system_program::nonce_account_lifecycle_event::Event as LifecycleEvent;
+
+/// Nonce account and authority of a durable nonce transaction, whose first instruction is a
+/// System program AdvanceNonceAccount.
+pub fn get_durable_nonce(transaction: &ConfirmedTransaction) -> Option<DurableNonce> {
+    let accounts = transaction.resolved_accounts();
+    let instruction = transaction.transaction.as_ref()?.message.as_ref()?.instructions.first()?;
+
+    let program_id = accounts.get(instruction.program_id_index as usize)?;
+    if program_id.as_slice() != SYSTEM_PROGRAM_ID.0 {
+        return None;
+    }
+    if !matches!(SystemInstruction::unpack(&instruction.data), Ok(SystemInstruction::AdvanceNonceAccount)) {
+        return None;
+    }
+
+    let account = |index: usize| {
+        instruction.accounts.get(index)
+            .and_then(|x| accounts.get(*x as usize))
+            .map(|x| bs58::encode(x).into_string())
+    };
+    Some(DurableNonce {
+        nonce_account: account(0)?,
+        nonce_authority: account(2)?,
+    })
+}
+
+/// Groups the nonce account initializations, authorizations, withdrawals and upgrades by nonce
+/// account, in block order.
+pub fn nonce_account_lifecycles(transactions: &[SystemProgramTransactionEvents]) -> Vec<NonceAccountLifecycle> {
+    let mut lifecycles: BTreeMap<String, Vec<NonceAccountLifecycleEvent>> = BTreeMap::new();
+    for transaction in transactions {
+        for event in transaction.events.iter() {
+            let (nonce_account, lifecycle_event) = match event.event.as_ref() {
+                Some(Event::InitializeNonceAccount(x)) => (&x.nonce_account, LifecycleEvent::Initialize(x.clone())),
+                Some(Event::AuthorizeNonceAccount(x)) => (&x.nonce_account, LifecycleEvent::Authorize(x.clone())),
+                Some(Event::WithdrawNonceAccount(x)) => (&x.nonce_account, LifecycleEvent::Withdraw(x.clone())),
+                Some(Event::UpgradeNonceAccount(x)) => (&x.nonce_account, LifecycleEvent::Upgrade(x.clone())),
+                _ => continue,
+            };
+            lifecycles.entry(nonce_account.clone()).or_default().push(NonceAccountLifecycleEvent {
+                signature: transaction.signature.clone(),
+                transaction_index: transaction.transaction_index,
+                instruction_index: event.instruction_index,
+                event: Some(lifecycle_event),
+            });
+        }
+    }
+    lifecycles.into_iter()
+        .map(|(nonce_account, events)| NonceAccountLifecycle { nonce_account, events })
+        .collect()
+}
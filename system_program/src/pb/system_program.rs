@@ -6,6 +6,10 @@ pub struct SystemProgramBlockEvents {
     pub slot: u64,
     #[prost(message, repeated, tag="2")]
     pub transactions: ::prost::alloc::vec::Vec<SystemProgramTransactionEvents>,
+    #[prost(int64, tag="3")]
+    pub block_time: i64,
+    #[prost(string, tag="4")]
+    pub block_hash: ::prost::alloc::string::String,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -16,13 +20,37 @@ pub struct SystemProgramTransactionEvents {
     pub transaction_index: u32,
     #[prost(message, repeated, tag="3")]
     pub events: ::prost::alloc::vec::Vec<SystemProgramEvent>,
+    #[prost(message, optional, tag="4")]
+    pub envelope: ::core::option::Option<::solana_substreams_common::pb::common::TransactionEnvelope>,
+    /// Set for durable nonce transactions, whose first instruction advances a nonce account.
+    #[prost(message, optional, tag="5")]
+    pub durable_nonce: ::core::option::Option<DurableNonce>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DurableNonce {
+    #[prost(string, tag="1")]
+    pub nonce_account: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub nonce_authority: ::prost::alloc::string::String,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SystemProgramEvent {
     #[prost(uint32, tag="1")]
     pub instruction_index: u32,
-    #[prost(oneof="system_program_event::Event", tags="2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14")]
+    /// Depth in the invocation stack, 0 for top level instructions.
+    #[prost(uint32, tag="16")]
+    pub stack_depth: u32,
+    #[prost(string, optional, tag="17")]
+    pub top_instruction_program_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag="18")]
+    pub parent_instruction_program_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(uint32, optional, tag="19")]
+    pub top_instruction_index: ::core::option::Option<u32>,
+    #[prost(uint32, optional, tag="20")]
+    pub parent_instruction_index: ::core::option::Option<u32>,
+    #[prost(oneof="system_program_event::Event", tags="2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 21")]
     pub event: ::core::option::Option<system_program_event::Event>,
 }
 /// Nested message and enum types in `SystemProgramEvent`.
@@ -56,6 +84,10 @@ pub mod system_program_event {
         TransferWithSeed(super::TransferWithSeedEvent),
         #[prost(message, tag="14")]
         UpgradeNonceAccount(super::UpgradeNonceAccountEvent),
+        #[prost(message, tag="15")]
+        FailedInstruction(::solana_substreams_common::pb::common::FailedInstruction),
+        #[prost(message, tag="21")]
+        UnknownInstruction(::solana_substreams_common::pb::common::UnknownInstruction),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -71,6 +103,9 @@ pub struct CreateAccountEvent {
     pub space: u64,
     #[prost(string, tag="5")]
     pub owner: ::prost::alloc::string::String,
+    /// What the account was created as, such as `mint`, `token_account`, `metadata`, `bonding_curve` or `amm_state`.
+    #[prost(string, optional, tag="6")]
+    pub kind: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -89,6 +124,9 @@ pub struct TransferEvent {
     pub recipient_account: ::prost::alloc::string::String,
     #[prost(uint64, tag="3")]
     pub lamports: u64,
+    /// Memo written right before the transfer at the same invocation level, set with the `include_memos` param.
+    #[prost(string, optional, tag="4")]
+    pub memo: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -107,6 +145,12 @@ pub struct CreateAccountWithSeedEvent {
     pub space: u64,
     #[prost(string, tag="7")]
     pub owner: ::prost::alloc::string::String,
+    /// What the account was created as, such as `mint`, `token_account`, `metadata`, `bonding_curve` or `amm_state`.
+    #[prost(string, optional, tag="8")]
+    pub kind: ::core::option::Option<::prost::alloc::string::String>,
+    /// Whether the created account is the address derived from the base account, seed and owner.
+    #[prost(bool, tag="9")]
+    pub derived_address_matches: bool,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -167,6 +211,9 @@ pub struct AllocateWithSeedEvent {
     pub space: u64,
     #[prost(string, tag="5")]
     pub owner: ::prost::alloc::string::String,
+    /// Whether the allocated account is the address derived from the base account, seed and owner.
+    #[prost(bool, tag="6")]
+    pub derived_address_matches: bool,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -179,6 +226,9 @@ pub struct AssignWithSeedEvent {
     pub seed: ::prost::alloc::string::String,
     #[prost(string, tag="4")]
     pub owner: ::prost::alloc::string::String,
+    /// Whether the assigned account is the address derived from the base account, seed and owner.
+    #[prost(bool, tag="5")]
+    pub derived_address_matches: bool,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -195,6 +245,12 @@ pub struct TransferWithSeedEvent {
     pub from_seed: ::prost::alloc::string::String,
     #[prost(string, tag="6")]
     pub from_owner: ::prost::alloc::string::String,
+    /// Memo written right before the transfer at the same invocation level, set with the `include_memos` param.
+    #[prost(string, optional, tag="7")]
+    pub memo: ::core::option::Option<::prost::alloc::string::String>,
+    /// Whether the funding account is the address derived from the base account, seed and owner.
+    #[prost(bool, tag="8")]
+    pub derived_address_matches: bool,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -202,4 +258,98 @@ pub struct UpgradeNonceAccountEvent {
     #[prost(string, tag="1")]
     pub nonce_account: ::prost::alloc::string::String,
 }
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct NonceAccountLifecycles {
+    #[prost(uint64, tag="1")]
+    pub slot: u64,
+    #[prost(message, repeated, tag="2")]
+    pub nonce_accounts: ::prost::alloc::vec::Vec<NonceAccountLifecycle>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct NonceAccountLifecycle {
+    #[prost(string, tag="1")]
+    pub nonce_account: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag="2")]
+    pub events: ::prost::alloc::vec::Vec<NonceAccountLifecycleEvent>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct NonceAccountLifecycleEvent {
+    #[prost(string, tag="1")]
+    pub signature: ::prost::alloc::string::String,
+    #[prost(uint32, tag="2")]
+    pub transaction_index: u32,
+    #[prost(uint32, tag="3")]
+    pub instruction_index: u32,
+    #[prost(oneof="nonce_account_lifecycle_event::Event", tags="4, 5, 6, 7")]
+    pub event: ::core::option::Option<nonce_account_lifecycle_event::Event>,
+}
+/// Nested message and enum types in `NonceAccountLifecycleEvent`.
+pub mod nonce_account_lifecycle_event {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Event {
+        #[prost(message, tag="4")]
+        Initialize(super::InitializeNonceAccountEvent),
+        #[prost(message, tag="5")]
+        Authorize(super::AuthorizeNonceAccountEvent),
+        #[prost(message, tag="6")]
+        Withdraw(super::WithdrawNonceAccountEvent),
+        #[prost(message, tag="7")]
+        Upgrade(super::UpgradeNonceAccountEvent),
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SolBalanceChanges {
+    #[prost(uint64, tag="1")]
+    pub slot: u64,
+    #[prost(message, repeated, tag="2")]
+    pub transactions: ::prost::alloc::vec::Vec<TransactionSolBalanceChanges>,
+    #[prost(int64, tag="3")]
+    pub block_time: i64,
+    #[prost(string, tag="4")]
+    pub block_hash: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TransactionSolBalanceChanges {
+    #[prost(string, tag="1")]
+    pub signature: ::prost::alloc::string::String,
+    #[prost(uint32, tag="2")]
+    pub transaction_index: u32,
+    #[prost(message, repeated, tag="3")]
+    pub balance_changes: ::prost::alloc::vec::Vec<SolBalanceChange>,
+    #[prost(bool, tag="4")]
+    pub failed: bool,
+}
+/// Lamport delta of an account over a transaction, split by cause. The causes add up to the delta.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SolBalanceChange {
+    #[prost(string, tag="1")]
+    pub account: ::prost::alloc::string::String,
+    #[prost(uint64, tag="2")]
+    pub pre_balance: u64,
+    #[prost(uint64, tag="3")]
+    pub post_balance: u64,
+    #[prost(int64, tag="4")]
+    pub delta: i64,
+    /// Transaction fee, charged to the fee payer.
+    #[prost(int64, tag="5")]
+    pub fee: i64,
+    /// Lamports moved by System program transfers, account creations and nonce withdrawals.
+    #[prost(int64, tag="6")]
+    pub system_transfer: i64,
+    /// Rent refunded by closing token accounts.
+    #[prost(int64, tag="7")]
+    pub account_close: i64,
+    /// Lamports moved directly by the programs owning the debited accounts.
+    #[prost(int64, tag="8")]
+    pub program_owned: i64,
+    #[prost(int64, tag="9")]
+    pub unattributed: i64,
+}
 // @@protoc_insertion_point(module)
@@ -0,0 +1,43 @@
+//! Error types of the System program.
+
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+use thiserror::Error;
+
+/// Errors that may be returned by the System program.
+#[derive(Clone, Debug, Eq, Error, FromPrimitive, PartialEq)]
+pub enum SystemError {
+    /// 0 An account with the same address already exists.
+    #[error("an account with the same address already exists")]
+    AccountAlreadyInUse,
+    /// Account does not have enough SOL to perform the operation.
+    #[error("account does not have enough SOL to perform the operation")]
+    ResultWithNegativeLamports,
+    /// Cannot assign account to this program id.
+    #[error("cannot assign account to this program id")]
+    InvalidProgramId,
+    /// Cannot allocate account data of this length.
+    #[error("cannot allocate account data of this length")]
+    InvalidAccountDataLength,
+    /// Length of requested seed is too long.
+    #[error("length of requested seed is too long")]
+    MaxSeedLengthExceeded,
+
+    /// 5 Provided address does not match addressed derived from seed.
+    #[error("provided address does not match addressed derived from seed")]
+    AddressWithSeedMismatch,
+    /// Advancing stored nonce requires a populated RecentBlockhashes sysvar.
+    #[error("advancing stored nonce requires a populated RecentBlockhashes sysvar")]
+    NonceNoRecentBlockhashes,
+    /// Stored nonce is still in recent_blockhashes.
+    #[error("stored nonce is still in recent_blockhashes")]
+    NonceBlockhashNotExpired,
+    /// Specified nonce does not match stored nonce.
+    #[error("specified nonce does not match stored nonce")]
+    NonceUnexpectedBlockhashValue,
+}
+
+/// Name of a System program custom error code, if any.
+pub fn system_error_name(code: u32) -> Option<String> {
+    SystemError::from_u32(code).map(|x| format!("{:?}", x))
+}